@@ -0,0 +1,73 @@
+use super::{LadderFilter, SallenKeyFilter};
+
+/// Initializes `LadderFilter` struct
+#[no_mangle]
+extern "C" fn ladder_filter_init(sr: f32) -> LadderFilter {
+    LadderFilter::init(sr)
+}
+
+/// Returns next sample
+#[no_mangle]
+unsafe extern "C" fn ladder_filter_tick(ptr: *mut LadderFilter, sample: f32) -> f32 {
+    ptr.as_mut().unwrap_unchecked().tick(sample)
+}
+
+/// Cutoff frequency in Hz
+#[no_mangle]
+unsafe extern "C" fn ladder_filter_set_cutoff(ptr: *mut LadderFilter, freq: f32) {
+    ptr.as_mut().unwrap_unchecked().set_cutoff(freq);
+}
+
+/// Only accepts values between 0.0 and 1.0, otherwise clamps
+#[no_mangle]
+unsafe extern "C" fn ladder_filter_set_resonance(ptr: *mut LadderFilter, val: f32) {
+    ptr.as_mut().unwrap_unchecked().set_resonance(val);
+}
+
+/// Only accepts values between 0.0 and 1.0, otherwise clamps
+#[no_mangle]
+unsafe extern "C" fn ladder_filter_set_drive(ptr: *mut LadderFilter, val: f32) {
+    ptr.as_mut().unwrap_unchecked().set_drive(val);
+}
+
+/// Time in ms that cutoff, resonance and drive take to reach a new value
+#[no_mangle]
+unsafe extern "C" fn ladder_filter_set_smoothing_time(ptr: *mut LadderFilter, ms: f32) {
+    ptr.as_mut().unwrap_unchecked().set_smoothing_time(ms);
+}
+
+/// Initializes `SallenKeyFilter` struct
+#[no_mangle]
+extern "C" fn sallen_key_filter_init(sr: f32) -> SallenKeyFilter {
+    SallenKeyFilter::init(sr)
+}
+
+/// Returns next sample
+#[no_mangle]
+unsafe extern "C" fn sallen_key_filter_tick(ptr: *mut SallenKeyFilter, sample: f32) -> f32 {
+    ptr.as_mut().unwrap_unchecked().tick(sample)
+}
+
+/// Cutoff frequency in Hz
+#[no_mangle]
+unsafe extern "C" fn sallen_key_filter_set_cutoff(ptr: *mut SallenKeyFilter, freq: f32) {
+    ptr.as_mut().unwrap_unchecked().set_cutoff(freq);
+}
+
+/// Only accepts values between 0.0 and 1.0, otherwise clamps
+#[no_mangle]
+unsafe extern "C" fn sallen_key_filter_set_resonance(ptr: *mut SallenKeyFilter, val: f32) {
+    ptr.as_mut().unwrap_unchecked().set_resonance(val);
+}
+
+/// Only accepts values between 0.0 and 1.0, otherwise clamps
+#[no_mangle]
+unsafe extern "C" fn sallen_key_filter_set_drive(ptr: *mut SallenKeyFilter, val: f32) {
+    ptr.as_mut().unwrap_unchecked().set_drive(val);
+}
+
+/// Time in ms that cutoff, resonance and drive take to reach a new value
+#[no_mangle]
+unsafe extern "C" fn sallen_key_filter_set_smoothing_time(ptr: *mut SallenKeyFilter, ms: f32) {
+    ptr.as_mut().unwrap_unchecked().set_smoothing_time(ms);
+}
@@ -0,0 +1,117 @@
+use libm::tanhf;
+
+use super::{prewarp, OnePole};
+use crate::smoothed_param::{SmoothedParam, SmoothingMode, DEFAULT_SMOOTHING_MS};
+
+/// cbindgen:ignore
+const MAX_FEEDBACK: f32 = 4.2; // slightly above the self-oscillation point of 4.0
+
+/// cbindgen:ignore
+const MAX_DRIVE: f32 = 16.0;
+
+/// Moog-style 4-pole lowpass ladder with zero-delay feedback
+///
+/// The feedback loop is saturated with `tanh`, which keeps self-oscillation stable.
+#[repr(C)]
+pub struct LadderFilter {
+    stages: [OnePole; 4],
+    sr: f32,
+    fc: SmoothedParam,
+    /// `1 / (1 + k * G^4)`, resolves the delay-free loop
+    alpha_0: f32,
+    feedback: SmoothedParam,
+    drive: SmoothedParam,
+    smoothing_ms: f32,
+}
+
+impl LadderFilter {
+    pub fn init(sr: f32) -> LadderFilter {
+        let mut ladder = LadderFilter {
+            stages: [OnePole::new(); 4],
+            sr,
+            fc: SmoothedParam::new(1000.0, SmoothingMode::OnePole),
+            alpha_0: 1.0,
+            feedback: SmoothedParam::new(0.0, SmoothingMode::OnePole),
+            drive: SmoothedParam::new(1.0, SmoothingMode::Linear),
+            smoothing_ms: DEFAULT_SMOOTHING_MS,
+        };
+
+        ladder.set_smoothing_time(DEFAULT_SMOOTHING_MS);
+        ladder.update_coeffs();
+        ladder
+    }
+
+    pub fn tick(&mut self, input: f32) -> f32 {
+        if self.fc.is_settling() || self.feedback.is_settling() {
+            self.fc.tick();
+            self.feedback.tick();
+            self.update_coeffs();
+        }
+        let drive = self.drive.tick();
+        let feedback = self.feedback.value();
+
+        let sigma: f32 = self.stages.iter().map(OnePole::feedback_output).sum();
+
+        // drive into the saturator, with passband gain compensation for the resonance
+        let u = tanhf(drive * (input * (1.0 + feedback) - feedback * sigma) * self.alpha_0);
+
+        let out = self
+            .stages
+            .iter_mut()
+            .fold(u, |signal, stage| stage.lowpass(signal));
+
+        out / drive
+    }
+
+    /// Cutoff frequency in Hz
+    pub fn set_cutoff(&mut self, freq: f32) {
+        self.fc.set_target(freq);
+        self.update_coeffs();
+    }
+
+    /// Only accepts values between 0.0 and 1.0, otherwise clamps. Self-oscillates close to 1.0.
+    pub fn set_resonance(&mut self, val: f32) {
+        self.feedback.set_target(val.clamp(0.0, 1.0) * MAX_FEEDBACK);
+        self.update_coeffs();
+    }
+
+    /// Only accepts values between 0.0 and 1.0, otherwise clamps
+    pub fn set_drive(&mut self, val: f32) {
+        self.drive
+            .set_target(1.0 + val.clamp(0.0, 1.0) * (MAX_DRIVE - 1.0));
+    }
+
+    /// Time in ms that cutoff, resonance and drive take to reach a new value
+    pub fn set_smoothing_time(&mut self, ms: f32) {
+        self.smoothing_ms = ms;
+        self.fc.set_time_ms(ms, self.sr);
+        self.feedback.set_time_ms(ms, self.sr);
+        self.drive.set_time_ms(ms, self.sr);
+    }
+
+    pub fn update_sr(&mut self, sr: f32) {
+        self.sr = sr;
+        self.set_smoothing_time(self.smoothing_ms);
+        self.update_coeffs();
+    }
+
+    /// Clears the filter memory
+    pub fn reset(&mut self) {
+        self.stages.iter_mut().for_each(OnePole::reset);
+    }
+
+    fn update_coeffs(&mut self) {
+        let g = prewarp(self.fc.value(), self.sr);
+        let alpha = g / (1.0 + g);
+
+        // each stage feeds the global loop with the gain of all stages that follow it
+        let mut beta = 1.0 / (1.0 + g);
+        for stage in self.stages.iter_mut().rev() {
+            stage.alpha = alpha;
+            stage.beta = beta;
+            beta *= alpha;
+        }
+
+        self.alpha_0 = 1.0 / (1.0 + self.feedback.value() * alpha * alpha * alpha * alpha);
+    }
+}
@@ -0,0 +1,153 @@
+mod ffi;
+mod ladder;
+mod sallen_key;
+
+pub use ladder::LadderFilter;
+pub use sallen_key::SallenKeyFilter;
+
+use core::f32::consts::PI;
+
+use libm::tanf;
+
+/// Lowest cutoff frequency of the analog models
+/// cbindgen:ignore
+const MIN_CUTOFF: f32 = 10.0; // Hz
+
+/// Highest cutoff frequency relative to the sample rate, keeps the prewarping stable
+/// cbindgen:ignore
+const MAX_CUTOFF_RATIO: f32 = 0.45;
+
+/// Zero-delay-feedback one-pole in topology-preserving transform form
+#[derive(Clone, Copy)]
+#[repr(C)]
+pub struct OnePole {
+    state: f32,
+    /// `g / (1 + g)`
+    alpha: f32,
+    /// Scales the state for the global feedback path
+    beta: f32,
+}
+
+impl OnePole {
+    fn new() -> OnePole {
+        OnePole {
+            state: 0.0,
+            alpha: 0.0,
+            beta: 0.0,
+        }
+    }
+
+    #[inline(always)]
+    fn lowpass(&mut self, input: f32) -> f32 {
+        let v = (input - self.state) * self.alpha;
+        let lp = v + self.state;
+        self.state = lp + v;
+        lp
+    }
+
+    #[inline(always)]
+    fn highpass(&mut self, input: f32) -> f32 {
+        input - self.lowpass(input)
+    }
+
+    #[inline(always)]
+    fn feedback_output(&self) -> f32 {
+        self.beta * self.state
+    }
+
+    fn reset(&mut self) {
+        self.state = 0.0;
+    }
+}
+
+/// Prewarped integrator gain `g` for the cutoff `fc` in Hz
+fn prewarp(fc: f32, sr: f32) -> f32 {
+    tanf(PI * fc.clamp(MIN_CUTOFF, MAX_CUTOFF_RATIO * sr) / sr)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    use libm::sinf;
+
+    const SR: f32 = 48000.0;
+
+    /// Peak output amplitude of a settled sine at `freq` with amplitude `amp`
+    fn sine_peak(mut filter: impl FnMut(f32) -> f32, freq: f32, amp: f32) -> f32 {
+        let mut peak = 0.0_f32;
+        for i in 0..SR as usize / 5 {
+            let out = filter(amp * sinf(2.0 * PI * freq * i as f32 / SR));
+            if i > SR as usize / 10 {
+                peak = peak.max(out.abs());
+            }
+        }
+        peak
+    }
+
+    #[test]
+    fn ladder_is_lowpass() {
+        let mut ladder = LadderFilter::init(SR);
+        ladder.set_cutoff(500.0);
+
+        assert!(sine_peak(|x| ladder.tick(x), 50.0, 0.1) > 0.09);
+        ladder.reset();
+        // 24 dB/oct, three octaves above cutoff
+        assert!(sine_peak(|x| ladder.tick(x), 4000.0, 0.1) < 0.1 * 0.005);
+    }
+
+    #[test]
+    fn ladder_self_oscillates() {
+        let mut ladder = LadderFilter::init(SR);
+        ladder.set_cutoff(1000.0);
+        ladder.set_resonance(1.0);
+
+        ladder.tick(1.0);
+        for _ in 0..SR as usize {
+            ladder.tick(0.0);
+        }
+
+        let peak = (0..480).map(|_| ladder.tick(0.0).abs()).fold(0.0, f32::max);
+        assert!(peak > 0.1, "oscillation died out: {}", peak);
+        assert!(peak <= 1.0);
+    }
+
+    #[test]
+    fn sallen_key_is_lowpass() {
+        let mut filter = SallenKeyFilter::init(SR);
+        filter.set_cutoff(500.0);
+
+        assert!(sine_peak(|x| filter.tick(x), 50.0, 0.1) > 0.09);
+        filter.reset();
+        // 12 dB/oct, three octaves above cutoff
+        assert!(sine_peak(|x| filter.tick(x), 4000.0, 0.1) < 0.1 * 0.05);
+    }
+
+    #[test]
+    fn resonance_boosts_cutoff() {
+        let mut flat = SallenKeyFilter::init(SR);
+        flat.set_cutoff(1000.0);
+        let mut resonant = SallenKeyFilter::init(SR);
+        resonant.set_cutoff(1000.0);
+        resonant.set_resonance(0.8);
+
+        assert!(
+            sine_peak(|x| resonant.tick(x), 1000.0, 0.01)
+                > 2.0 * sine_peak(|x| flat.tick(x), 1000.0, 0.01)
+        );
+    }
+
+    #[test]
+    fn drive_saturates() {
+        let mut ladder = LadderFilter::init(SR);
+        ladder.set_cutoff(20000.0);
+        ladder.set_drive(1.0);
+
+        let clean = sine_peak(|x| ladder.tick(x), 100.0, 0.01);
+        ladder.reset();
+        let driven = sine_peak(|x| ladder.tick(x), 100.0, 1.0);
+
+        // compressed compared to the small signal gain
+        assert!(driven < 100.0 * clean * 0.5);
+    }
+}
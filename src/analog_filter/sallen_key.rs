@@ -0,0 +1,125 @@
+use libm::tanhf;
+
+use super::{prewarp, OnePole};
+use crate::smoothed_param::{SmoothedParam, SmoothingMode, DEFAULT_SMOOTHING_MS};
+
+/// cbindgen:ignore
+const MIN_FEEDBACK: f32 = 0.01;
+
+/// cbindgen:ignore
+const MAX_FEEDBACK: f32 = 1.99; // self-oscillates at 2.0
+
+/// cbindgen:ignore
+const MAX_DRIVE: f32 = 16.0;
+
+/// MS-20-style 2-pole Sallen-Key lowpass (Korg35) with zero-delay feedback
+///
+/// The resonance path is saturated with `tanh`, which gives the typical screaming character.
+#[repr(C)]
+pub struct SallenKeyFilter {
+    lpf_1: OnePole,
+    lpf_2: OnePole,
+    hpf_3: OnePole,
+    sr: f32,
+    fc: SmoothedParam,
+    /// `1 / (1 - k * G + k * G^2)`, resolves the delay-free loop
+    alpha_0: f32,
+    feedback: SmoothedParam,
+    drive: SmoothedParam,
+    smoothing_ms: f32,
+}
+
+impl SallenKeyFilter {
+    pub fn init(sr: f32) -> SallenKeyFilter {
+        let mut filter = SallenKeyFilter {
+            lpf_1: OnePole::new(),
+            lpf_2: OnePole::new(),
+            hpf_3: OnePole::new(),
+            sr,
+            fc: SmoothedParam::new(1000.0, SmoothingMode::OnePole),
+            alpha_0: 1.0,
+            feedback: SmoothedParam::new(MIN_FEEDBACK, SmoothingMode::OnePole),
+            drive: SmoothedParam::new(1.0, SmoothingMode::Linear),
+            smoothing_ms: DEFAULT_SMOOTHING_MS,
+        };
+
+        filter.set_smoothing_time(DEFAULT_SMOOTHING_MS);
+        filter.update_coeffs();
+        filter
+    }
+
+    pub fn tick(&mut self, input: f32) -> f32 {
+        if self.fc.is_settling() || self.feedback.is_settling() {
+            self.fc.tick();
+            self.feedback.tick();
+            self.update_coeffs();
+        }
+        let drive = self.drive.tick();
+        let feedback = self.feedback.value();
+
+        let y_1 = self.lpf_1.lowpass(input * drive);
+        let sigma = self.lpf_2.feedback_output() + self.hpf_3.feedback_output();
+
+        let u = tanhf(self.alpha_0 * (y_1 + sigma));
+
+        let out = feedback * self.lpf_2.lowpass(u);
+        self.hpf_3.highpass(out);
+
+        out / (feedback * drive)
+    }
+
+    /// Cutoff frequency in Hz
+    pub fn set_cutoff(&mut self, freq: f32) {
+        self.fc.set_target(freq);
+        self.update_coeffs();
+    }
+
+    /// Only accepts values between 0.0 and 1.0, otherwise clamps
+    pub fn set_resonance(&mut self, val: f32) {
+        self.feedback
+            .set_target(MIN_FEEDBACK + val.clamp(0.0, 1.0) * (MAX_FEEDBACK - MIN_FEEDBACK));
+        self.update_coeffs();
+    }
+
+    /// Only accepts values between 0.0 and 1.0, otherwise clamps
+    pub fn set_drive(&mut self, val: f32) {
+        self.drive
+            .set_target(1.0 + val.clamp(0.0, 1.0) * (MAX_DRIVE - 1.0));
+    }
+
+    /// Time in ms that cutoff, resonance and drive take to reach a new value
+    pub fn set_smoothing_time(&mut self, ms: f32) {
+        self.smoothing_ms = ms;
+        self.fc.set_time_ms(ms, self.sr);
+        self.feedback.set_time_ms(ms, self.sr);
+        self.drive.set_time_ms(ms, self.sr);
+    }
+
+    pub fn update_sr(&mut self, sr: f32) {
+        self.sr = sr;
+        self.set_smoothing_time(self.smoothing_ms);
+        self.update_coeffs();
+    }
+
+    /// Clears the filter memory
+    pub fn reset(&mut self) {
+        self.lpf_1.reset();
+        self.lpf_2.reset();
+        self.hpf_3.reset();
+    }
+
+    fn update_coeffs(&mut self) {
+        let g = prewarp(self.fc.value(), self.sr);
+        let alpha = g / (1.0 + g);
+        let k = self.feedback.value();
+
+        self.lpf_1.alpha = alpha;
+        self.lpf_2.alpha = alpha;
+        self.hpf_3.alpha = alpha;
+
+        self.lpf_2.beta = (k - k * alpha) / (1.0 + g);
+        self.hpf_3.beta = -1.0 / (1.0 + g);
+
+        self.alpha_0 = 1.0 / (1.0 - k * alpha + k * alpha * alpha);
+    }
+}
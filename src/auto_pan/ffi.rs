@@ -0,0 +1,55 @@
+use crate::tools::Waveform;
+use crate::AutoPan;
+
+/// Initializes `AutoPan` struct
+#[no_mangle]
+extern "C" fn auto_pan_init(sr: f32) -> AutoPan {
+    AutoPan::init(sr)
+}
+
+/// Returns next stereo samples. Raw pointer `stereo_samples` assumes to have exactly two elements!
+#[no_mangle]
+unsafe extern "C" fn auto_pan_tick(ptr: *mut AutoPan, stereo_samples: *mut f32) {
+    let samples = core::slice::from_raw_parts_mut(stereo_samples, 2);
+
+    (samples[0], samples[1]) = ptr
+        .as_mut()
+        .unwrap_unchecked()
+        .tick((samples[0], samples[1]));
+}
+
+/// Modulation speed between 0.01 and 20 Hz, otherwise clamps
+#[no_mangle]
+unsafe extern "C" fn auto_pan_set_rate(ptr: *mut AutoPan, hz: f32) {
+    ptr.as_mut().unwrap_unchecked().set_rate(hz);
+}
+
+/// One period every `beats` beats at `bpm`
+#[no_mangle]
+unsafe extern "C" fn auto_pan_set_tempo_sync(ptr: *mut AutoPan, bpm: f32, beats: f32) {
+    ptr.as_mut().unwrap_unchecked().set_tempo_sync(bpm, beats);
+}
+
+/// Restarts the modulation
+#[no_mangle]
+unsafe extern "C" fn auto_pan_reset_phase(ptr: *mut AutoPan) {
+    ptr.as_mut().unwrap_unchecked().reset_phase();
+}
+
+/// Shape of the modulation
+#[no_mangle]
+unsafe extern "C" fn auto_pan_set_waveform(ptr: *mut AutoPan, wave: Waveform) {
+    ptr.as_mut().unwrap_unchecked().set_waveform(wave);
+}
+
+/// Only accepts values between 0.0 and 1.0, otherwise clamps
+#[no_mangle]
+unsafe extern "C" fn auto_pan_set_depth(ptr: *mut AutoPan, val: f32) {
+    ptr.as_mut().unwrap_unchecked().set_depth(val);
+}
+
+/// Time in ms that depth changes take
+#[no_mangle]
+unsafe extern "C" fn auto_pan_set_smoothing_time(ptr: *mut AutoPan, ms: f32) {
+    ptr.as_mut().unwrap_unchecked().set_smoothing_time(ms);
+}
@@ -0,0 +1,132 @@
+mod ffi;
+
+use core::f32::consts::{FRAC_PI_4, SQRT_2};
+
+use libm::{cosf, sinf};
+
+use crate::lfo::{tempo_sync_freq, Lfo};
+use crate::smoothed_param::{SmoothedParam, SmoothingMode, DEFAULT_SMOOTHING_MS};
+use crate::tools::Waveform;
+
+/// cbindgen:ignore
+const MIN_RATE: f32 = 0.01; // Hz
+
+/// cbindgen:ignore
+const MAX_RATE: f32 = 20.0; // Hz
+
+/// Moves a signal between the channels with an equal-power pan law
+///
+/// Both inputs are summed to mono before panning, so a hard-left source also reaches the right
+/// channel. The gains are scaled to 1.0 in the center, so the power stays constant during the sweep.
+#[repr(C)]
+pub struct AutoPan {
+    lfo: Lfo,
+
+    sr: f32,
+    smoothing_ms: f32,
+    depth: SmoothedParam,
+}
+
+impl AutoPan {
+    pub fn init(sr: f32) -> AutoPan {
+        let mut auto_pan = AutoPan {
+            lfo: Lfo::new(1.0, sr),
+
+            sr,
+            smoothing_ms: DEFAULT_SMOOTHING_MS,
+            depth: SmoothedParam::new(0.5, SmoothingMode::Linear),
+        };
+
+        auto_pan.set_smoothing_time(DEFAULT_SMOOTHING_MS);
+        auto_pan
+    }
+
+    pub fn tick(&mut self, input: (f32, f32)) -> (f32, f32) {
+        let pan = self.depth.tick() * self.lfo.tick();
+        let angle = (pan + 1.0) * FRAC_PI_4;
+        let mono = 0.5 * (input.0 + input.1);
+
+        (mono * SQRT_2 * cosf(angle), mono * SQRT_2 * sinf(angle))
+    }
+
+    /// Modulation speed between 0.01 and 20 Hz, otherwise clamps
+    pub fn set_rate(&mut self, hz: f32) {
+        self.lfo.set_freq(hz.clamp(MIN_RATE, MAX_RATE));
+    }
+
+    /// One period every `beats` beats at `bpm`, within the same limits as the rate
+    pub fn set_tempo_sync(&mut self, bpm: f32, beats: f32) {
+        if let Some(hz) = tempo_sync_freq(bpm, beats) {
+            self.set_rate(hz);
+        }
+    }
+
+    /// Restarts the modulation, e.g. on a bar line
+    pub fn reset_phase(&mut self) {
+        self.lfo.reset_phase(0.0);
+    }
+
+    pub fn set_waveform(&mut self, wave: Waveform) {
+        self.lfo.set_waveform(wave);
+    }
+
+    /// Width of the sweep, 1.0 pans fully left and right. Only accepts values between 0.0 and 1.0, otherwise clamps
+    pub fn set_depth(&mut self, val: f32) {
+        self.depth.set_target(val.clamp(0.0, 1.0));
+    }
+
+    /// Time in ms that depth changes take
+    pub fn set_smoothing_time(&mut self, ms: f32) {
+        self.smoothing_ms = ms;
+        self.depth.set_time_ms(ms, self.sr);
+    }
+
+    pub fn update_sr(&mut self, sr: f32) {
+        self.sr = sr;
+        self.set_smoothing_time(self.smoothing_ms);
+        self.lfo.set_sr(sr);
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn equal_power() {
+        let mut auto_pan = AutoPan::init(48000.0);
+        auto_pan.set_smoothing_time(0.0);
+        auto_pan.set_depth(1.0);
+
+        for _ in 0..48000 {
+            let out = auto_pan.tick((1.0, 1.0));
+            assert!((out.0 * out.0 + out.1 * out.1 - 2.0).abs() < 1e-4);
+        }
+    }
+
+    #[test]
+    fn hard_left_reaches_right() {
+        let mut auto_pan = AutoPan::init(48000.0);
+        auto_pan.set_smoothing_time(0.0);
+        auto_pan.set_depth(1.0);
+
+        let mut right_peak: f32 = 0.0;
+        for _ in 0..48000 {
+            right_peak = right_peak.max(auto_pan.tick((1.0, 0.0)).1.abs());
+        }
+        assert!(right_peak > 0.7);
+    }
+
+    #[test]
+    fn tempo_sync_clamps() {
+        // 80 Hz at 300 bpm and 64th notes
+        let mut synced = AutoPan::init(48000.0);
+        synced.set_tempo_sync(300.0, 0.0625);
+        let mut clamped = AutoPan::init(48000.0);
+        clamped.set_rate(MAX_RATE);
+
+        for _ in 0..4800 {
+            assert_eq!(synced.tick((1.0, 1.0)), clamped.tick((1.0, 1.0)));
+        }
+    }
+}
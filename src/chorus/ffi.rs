@@ -0,0 +1,62 @@
+use crate::Chorus;
+
+/// Initializes `Chorus` struct
+#[no_mangle]
+extern "C" fn chorus_init(sr: f32) -> Chorus {
+    Chorus::init(sr)
+}
+
+/// Initializes `Chorus` delay buffers. `length` needs to be `>= 1920` for `sr = 48000`.
+#[no_mangle]
+unsafe extern "C" fn chorus_set_buffer(ptr: *mut Chorus, buffer: *mut f32, length: usize) {
+    ptr.as_mut()
+        .unwrap_unchecked()
+        .set_buffer(core::slice::from_raw_parts_mut(buffer, length));
+}
+
+/// Returns next stereo samples. Raw pointer `stereo_samples` assumes to have exactly two elements!
+#[no_mangle]
+unsafe extern "C" fn chorus_tick(ptr: *mut Chorus, stereo_samples: *mut f32) {
+    let samples = core::slice::from_raw_parts_mut(stereo_samples, 2);
+
+    (samples[0], samples[1]) = ptr
+        .as_mut()
+        .unwrap_unchecked()
+        .tick((samples[0], samples[1]));
+}
+
+/// Number of voices between 1 and 4, otherwise clamps
+#[no_mangle]
+unsafe extern "C" fn chorus_set_voices(ptr: *mut Chorus, voices: usize) {
+    ptr.as_mut().unwrap_unchecked().set_voices(voices);
+}
+
+/// Modulation speed between 0.01 and 10 Hz, otherwise clamps
+#[no_mangle]
+unsafe extern "C" fn chorus_set_rate(ptr: *mut Chorus, hz: f32) {
+    ptr.as_mut().unwrap_unchecked().set_rate(hz);
+}
+
+/// Only accepts values between 0.0 and 1.0, otherwise clamps
+#[no_mangle]
+unsafe extern "C" fn chorus_set_depth(ptr: *mut Chorus, val: f32) {
+    ptr.as_mut().unwrap_unchecked().set_depth(val);
+}
+
+/// Only accepts values between 0.0 and 1.0, otherwise clamps
+#[no_mangle]
+unsafe extern "C" fn chorus_set_spread(ptr: *mut Chorus, val: f32) {
+    ptr.as_mut().unwrap_unchecked().set_spread(val);
+}
+
+/// Dry/Wet mixing
+#[no_mangle]
+unsafe extern "C" fn chorus_set_mix(ptr: *mut Chorus, val: f32) {
+    ptr.as_mut().unwrap_unchecked().set_mix(val);
+}
+
+/// Time in ms that depth and mix changes take
+#[no_mangle]
+unsafe extern "C" fn chorus_set_smoothing_time(ptr: *mut Chorus, ms: f32) {
+    ptr.as_mut().unwrap_unchecked().set_smoothing_time(ms);
+}
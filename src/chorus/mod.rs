@@ -0,0 +1,166 @@
+mod ffi;
+
+use crate::lfo::Lfo;
+use crate::smoothed_param::{SmoothedParam, SmoothingMode, DEFAULT_SMOOTHING_MS};
+use crate::tools::{
+    memory_access::{from_slice_mut, null_mut},
+    DelayLine,
+};
+
+/// cbindgen:ignore
+const MAX_CHORUS_VOICES: usize = 4;
+
+/// cbindgen:ignore
+const BASE_DELAY: f32 = 0.012; // s
+
+/// cbindgen:ignore
+const MAX_DEPTH: f32 = 0.008; // s
+
+/// cbindgen:ignore
+const MIN_RATE: f32 = 0.01; // Hz
+
+/// cbindgen:ignore
+const MAX_RATE: f32 = 10.0; // Hz
+
+/// Stereo chorus with up to four voices reading from modulated delay taps
+///
+/// The voices are spread evenly over the LFO period, the right channel is offset by the spread.
+#[repr(C)]
+pub struct Chorus {
+    delay_l: DelayLine,
+    delay_r: DelayLine,
+    lfo: Lfo,
+
+    sr: f32,
+    smoothing_ms: f32,
+    voices: usize,
+    spread: f32,
+    depth: SmoothedParam,
+    mix: SmoothedParam,
+}
+
+impl Chorus {
+    pub fn init(sr: f32) -> Chorus {
+        let mut chorus = Chorus {
+            delay_l: DelayLine::new(null_mut()),
+            delay_r: DelayLine::new(null_mut()),
+            lfo: Lfo::new(0.5, sr),
+
+            sr,
+            smoothing_ms: DEFAULT_SMOOTHING_MS,
+            voices: 2,
+            spread: 0.5,
+            depth: SmoothedParam::new(0.5, SmoothingMode::OnePole),
+            mix: SmoothedParam::new(0.5, SmoothingMode::Linear),
+        };
+
+        chorus.set_smoothing_time(DEFAULT_SMOOTHING_MS);
+        chorus
+    }
+
+    /// Splits `buffer` into one delay line per channel
+    ///
+    /// Needs `length >= 2 * 0.02 * sr` for the deepest modulation, e.g. 1920 for `sr = 48000`.
+    pub fn set_buffer(&mut self, buffer: &mut [f32]) {
+        let (left, right) = buffer.split_at_mut(buffer.len() / 2);
+        self.delay_l.change_buffer(from_slice_mut(left));
+        self.delay_r.change_buffer(from_slice_mut(right));
+    }
+
+    pub fn tick(&mut self, input: (f32, f32)) -> (f32, f32) {
+        let depth = self.depth.tick() * MAX_DEPTH * self.sr;
+        let base = BASE_DELAY * self.sr;
+        let max_delay = self.delay_l.len().saturating_sub(2) as f32;
+
+        let mut wet = (0.0, 0.0);
+        for voice in 0..self.voices {
+            let offset = voice as f32 / self.voices as f32;
+
+            // unipolar modulation, so the delay never drops below the base delay
+            let mod_l = 0.5 + 0.5 * self.lfo.value_at(offset);
+            let mod_r = 0.5 + 0.5 * self.lfo.value_at(offset + 0.5 * self.spread);
+
+            wet.0 += self
+                .delay_l
+                .read_lerp_wrapped_at(-(base + depth * mod_l).min(max_delay));
+            wet.1 += self
+                .delay_r
+                .read_lerp_wrapped_at(-(base + depth * mod_r).min(max_delay));
+        }
+        self.lfo.advance(1);
+
+        self.delay_l.write_and_advance(input.0);
+        self.delay_r.write_and_advance(input.1);
+
+        let mix = self.mix.tick();
+        let gain = mix / self.voices as f32;
+
+        (
+            (1.0 - mix) * input.0 + gain * wet.0,
+            (1.0 - mix) * input.1 + gain * wet.1,
+        )
+    }
+
+    /// Number of voices between 1 and 4, otherwise clamps
+    pub fn set_voices(&mut self, voices: usize) {
+        self.voices = voices.clamp(1, MAX_CHORUS_VOICES);
+    }
+
+    /// Modulation speed between 0.01 and 10 Hz, otherwise clamps
+    pub fn set_rate(&mut self, hz: f32) {
+        self.lfo.set_freq(hz.clamp(MIN_RATE, MAX_RATE));
+    }
+
+    /// Modulation up to 8 ms. Only accepts values between 0.0 and 1.0, otherwise clamps
+    pub fn set_depth(&mut self, val: f32) {
+        self.depth.set_target(val.clamp(0.0, 1.0));
+    }
+
+    /// LFO phase offset of the right channel, 1.0 is half a period. Only accepts values between 0.0 and 1.0, otherwise clamps
+    pub fn set_spread(&mut self, val: f32) {
+        self.spread = val.clamp(0.0, 1.0);
+    }
+
+    /// Dry/Wet mixing. Only accepts values between 0.0 and 1.0, otherwise clamps
+    pub fn set_mix(&mut self, val: f32) {
+        self.mix.set_target(val.clamp(0.0, 1.0));
+    }
+
+    /// Time in ms that depth and mix changes take
+    pub fn set_smoothing_time(&mut self, ms: f32) {
+        self.smoothing_ms = ms;
+        self.depth.set_time_ms(ms, self.sr);
+        self.mix.set_time_ms(ms, self.sr);
+    }
+
+    pub fn update_sr(&mut self, sr: f32) {
+        self.sr = sr;
+        self.set_smoothing_time(self.smoothing_ms);
+        self.lfo.set_sr(sr);
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    const SR: f32 = 48000.0;
+
+    #[test]
+    fn wet_is_delayed_and_modulated() {
+        let mut buffer = [0.0; 2048];
+        let mut chorus = Chorus::init(SR);
+        chorus.set_buffer(&mut buffer);
+        chorus.set_mix(1.0);
+        chorus.set_smoothing_time(0.0);
+
+        // an impulse comes out after the base delay, not right away
+        assert_eq!(chorus.tick((1.0, 1.0)), (0.0, 0.0));
+        let out: [(f32, f32); 1000] = core::array::from_fn(|_| chorus.tick((0.0, 0.0)));
+        let first = out.iter().position(|x| x.0 != 0.0).unwrap();
+        assert!(first >= (BASE_DELAY * SR) as usize - 2);
+
+        // the spread sends different taps to both channels
+        assert!(out.iter().any(|x| x.0 != x.1));
+    }
+}
@@ -0,0 +1,79 @@
+use crate::{Distortion, Waveshaper};
+
+/// Initializes `Distortion` struct
+#[no_mangle]
+extern "C" fn distortion_init(sr: f32) -> Distortion {
+    Distortion::init(sr)
+}
+
+/// Returns next sample
+#[no_mangle]
+unsafe extern "C" fn distortion_tick(ptr: *mut Distortion, sample: f32) -> f32 {
+    ptr.as_mut().unwrap_unchecked().tick(sample)
+}
+
+/// Curve of the waveshaper
+#[no_mangle]
+unsafe extern "C" fn distortion_set_shape(ptr: *mut Distortion, shape: Waveshaper) {
+    ptr.as_mut().unwrap_unchecked().set_shape(shape);
+}
+
+/// Drive in dB between 0 and 48, otherwise clamps
+#[no_mangle]
+unsafe extern "C" fn distortion_set_drive(ptr: *mut Distortion, db: f32) {
+    ptr.as_mut().unwrap_unchecked().set_drive(db);
+}
+
+/// Output level in dB
+#[no_mangle]
+unsafe extern "C" fn distortion_set_output(ptr: *mut Distortion, db: f32) {
+    ptr.as_mut().unwrap_unchecked().set_output(db);
+}
+
+/// Dry/Wet mixing
+#[no_mangle]
+unsafe extern "C" fn distortion_set_mix(ptr: *mut Distortion, val: f32) {
+    ptr.as_mut().unwrap_unchecked().set_mix(val);
+}
+
+/// Center of the emphasized band between 20 Hz and 20 kHz, otherwise clamps
+#[no_mangle]
+unsafe extern "C" fn distortion_set_emphasis_freq(ptr: *mut Distortion, hz: f32) {
+    ptr.as_mut().unwrap_unchecked().set_emphasis_freq(hz);
+}
+
+/// Emphasis in dB between -24 and 24, otherwise clamps
+#[no_mangle]
+unsafe extern "C" fn distortion_set_emphasis_gain(ptr: *mut Distortion, db: f32) {
+    ptr.as_mut().unwrap_unchecked().set_emphasis_gain(db);
+}
+
+/// Resolution between 1 and 24 bits, otherwise clamps. 24 bits bypasses the quantization.
+#[no_mangle]
+unsafe extern "C" fn distortion_set_bits(ptr: *mut Distortion, bits: f32) {
+    ptr.as_mut().unwrap_unchecked().set_bits(bits);
+}
+
+/// Sample rate reduction factor between 1 and 64, otherwise clamps
+#[no_mangle]
+unsafe extern "C" fn distortion_set_downsample(ptr: *mut Distortion, factor: f32) {
+    ptr.as_mut().unwrap_unchecked().set_downsample(factor);
+}
+
+/// Oversampling of the waveshaper, 1, 2, 4 or 8. Other values round down.
+#[no_mangle]
+unsafe extern "C" fn distortion_set_oversampling(ptr: *mut Distortion, factor: usize) {
+    ptr.as_mut().unwrap_unchecked().set_oversampling(factor);
+}
+
+/// Delay of the output in samples caused by the oversampling
+#[no_mangle]
+unsafe extern "C" fn distortion_latency_samples(ptr: *const Distortion) -> f32 {
+    ptr.as_ref().unwrap_unchecked().latency_samples()
+}
+
+/// Time in ms that drive, output and mix changes take
+#[no_mangle]
+unsafe extern "C" fn distortion_set_smoothing_time(ptr: *mut Distortion, ms: f32) {
+    ptr.as_mut().unwrap_unchecked().set_smoothing_time(ms);
+}
@@ -0,0 +1,209 @@
+mod ffi;
+
+use embedded_audio_tools::filter::{Biquad, BiquadCoeffs, Butterworth};
+
+use crate::multi_filter::db_to_gain;
+use crate::oversampler::Oversampler;
+use crate::smoothed_param::{SmoothedParam, SmoothingMode, DEFAULT_SMOOTHING_MS};
+use crate::waveshaper::{Bitcrusher, Waveshaper};
+
+/// cbindgen:ignore
+const MAX_DRIVE: f32 = 48.0; // dB
+
+/// cbindgen:ignore
+const MAX_EMPHASIS: f32 = 24.0; // dB
+
+/// cbindgen:ignore
+const EMPHASIS_Q: f32 = 0.707;
+
+/// cbindgen:ignore
+const LOWEST_EMPHASIS_FREQ: f32 = 20.0; // Hz
+
+/// cbindgen:ignore
+const HIGHEST_EMPHASIS_FREQ: f32 = 20000.0; // Hz
+
+/// Waveshaping distortion with pre- and post-emphasis and a bitcrusher
+///
+/// The pre-emphasis bell boosts a band before the waveshaper so it distorts first, the post-emphasis
+/// bell cuts it by the same amount afterwards. Without drive both cancel out.
+///
+/// The waveshaper can run oversampled, the dry signal is delayed by the same latency.
+#[repr(C)]
+pub struct Distortion {
+    pre: Biquad<Butterworth>,
+    post: Biquad<Butterworth>,
+    crusher: Bitcrusher,
+    oversampler: Oversampler,
+
+    sr: f32,
+    smoothing_ms: f32,
+    shape: Waveshaper,
+    emphasis_freq: f32,
+    emphasis_gain: f32,
+    drive: SmoothedParam,
+    compensation: SmoothedParam,
+    output: SmoothedParam,
+    mix: SmoothedParam,
+}
+
+impl Distortion {
+    pub fn init(sr: f32) -> Distortion {
+        let mut distortion = Distortion {
+            pre: Biquad::new(BiquadCoeffs::new()),
+            post: Biquad::new(BiquadCoeffs::new()),
+            crusher: Bitcrusher::new(),
+            oversampler: Oversampler::new(),
+
+            sr,
+            smoothing_ms: DEFAULT_SMOOTHING_MS,
+            shape: Waveshaper::Tanh,
+            emphasis_freq: 1000.0,
+            emphasis_gain: 0.0,
+            drive: SmoothedParam::new(1.0, SmoothingMode::OnePole),
+            compensation: SmoothedParam::new(1.0, SmoothingMode::OnePole),
+            output: SmoothedParam::new(1.0, SmoothingMode::Linear),
+            mix: SmoothedParam::new(1.0, SmoothingMode::Linear),
+        };
+
+        distortion.update_emphasis();
+        distortion.set_drive(0.0);
+        distortion.set_smoothing_time(DEFAULT_SMOOTHING_MS);
+        distortion
+    }
+
+    pub fn tick(&mut self, input: f32) -> f32 {
+        let driven = self.pre.process(input) * self.drive.tick();
+        let shape = self.shape;
+        let shaped =
+            self.oversampler.process(driven, |x| shape.process(x)) * self.compensation.tick();
+        let wet = self.post.process(self.crusher.process(shaped));
+        let dry = self.oversampler.delay(input);
+
+        let mix = self.mix.tick();
+        self.output.tick() * ((1.0 - mix) * dry + mix * wet)
+    }
+
+    /// Curve of the waveshaper
+    pub fn set_shape(&mut self, shape: Waveshaper) {
+        self.shape = shape;
+        self.update_compensation();
+    }
+
+    /// Gain into the waveshaper in dB between 0 and 48, otherwise clamps
+    pub fn set_drive(&mut self, db: f32) {
+        self.drive.set_target(db_to_gain(db.clamp(0.0, MAX_DRIVE)));
+        self.update_compensation();
+    }
+
+    /// Output level in dB
+    pub fn set_output(&mut self, db: f32) {
+        self.output.set_target(db_to_gain(db));
+    }
+
+    /// Dry/Wet mixing. Only accepts values between 0.0 and 1.0, otherwise clamps
+    pub fn set_mix(&mut self, val: f32) {
+        self.mix.set_target(val.clamp(0.0, 1.0));
+    }
+
+    /// Center of the emphasized band between 20 Hz and 20 kHz, otherwise clamps
+    pub fn set_emphasis_freq(&mut self, hz: f32) {
+        self.emphasis_freq = hz.clamp(LOWEST_EMPHASIS_FREQ, HIGHEST_EMPHASIS_FREQ);
+        self.update_emphasis();
+    }
+
+    /// Boost before and cut after the waveshaper in dB between -24 and 24, otherwise clamps
+    pub fn set_emphasis_gain(&mut self, db: f32) {
+        self.emphasis_gain = db.clamp(-MAX_EMPHASIS, MAX_EMPHASIS);
+        self.update_emphasis();
+    }
+
+    /// Resolution between 1 and 24 bits, otherwise clamps. 24 bits bypasses the quantization.
+    pub fn set_bits(&mut self, bits: f32) {
+        self.crusher.set_bits(bits);
+    }
+
+    /// Sample rate reduction factor between 1 and 64, otherwise clamps
+    pub fn set_downsample(&mut self, factor: f32) {
+        self.crusher.set_downsample(factor);
+    }
+
+    /// Oversampling of the waveshaper, 1, 2, 4 or 8. Other values round down.
+    pub fn set_oversampling(&mut self, factor: usize) {
+        self.oversampler.set_factor(factor);
+    }
+
+    /// Delay of the output in samples caused by the oversampling, can be fractional
+    pub fn latency_samples(&self) -> f32 {
+        self.oversampler.latency_samples()
+    }
+
+    /// Time in ms that drive, output and mix changes take
+    pub fn set_smoothing_time(&mut self, ms: f32) {
+        self.smoothing_ms = ms;
+        for param in [
+            &mut self.drive,
+            &mut self.compensation,
+            &mut self.output,
+            &mut self.mix,
+        ] {
+            param.set_time_ms(ms, self.sr);
+        }
+    }
+
+    pub fn update_sr(&mut self, sr: f32) {
+        self.sr = sr;
+        self.set_smoothing_time(self.smoothing_ms);
+        self.update_emphasis();
+    }
+
+    fn update_compensation(&mut self) {
+        self.compensation
+            .set_target(self.shape.compensation(self.drive.target()));
+    }
+
+    fn update_emphasis(&mut self) {
+        let gain = db_to_gain(self.emphasis_gain);
+        // the bells with inverse gains cancel each other exactly
+        self.pre
+            .coeffs
+            .bell(self.emphasis_freq, EMPHASIS_Q, gain, self.sr);
+        self.post
+            .coeffs
+            .bell(self.emphasis_freq, EMPHASIS_Q, 1.0 / gain, self.sr);
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    const SR: f32 = 48000.0;
+
+    #[test]
+    fn emphasis_cancels_without_clipping() {
+        let mut distortion = Distortion::init(SR);
+        distortion.set_smoothing_time(0.0);
+        distortion.set_shape(Waveshaper::HardClip);
+        distortion.set_emphasis_gain(12.0);
+
+        for i in 0..4800 {
+            let x = 0.1 * libm::sinf(2.0 * core::f32::consts::PI * 1000.0 * i as f32 / SR);
+            assert!((distortion.tick(x) - x).abs() < 1e-4);
+        }
+    }
+
+    #[test]
+    fn mix_blends_dry() {
+        let mut distortion = Distortion::init(SR);
+        distortion.set_smoothing_time(0.0);
+        distortion.set_drive(MAX_DRIVE);
+        distortion.set_shape(Waveshaper::HardClip);
+
+        distortion.set_mix(0.0);
+        assert_eq!(distortion.tick(0.5), 0.5);
+
+        distortion.set_mix(1.0);
+        distortion.set_output(-6.0);
+        assert!(distortion.tick(0.5) <= db_to_gain(-6.0) * distortion.compensation.value() + 1e-6);
+    }
+}
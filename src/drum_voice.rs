@@ -0,0 +1,197 @@
+use core::ffi::c_void;
+
+use crate::{SynthClap, SynthHat, SynthKick, SynthSnare, SynthTom};
+
+/// cbindgen:ignore
+const MAX_SCHEDULED_TRIGGERS: usize = 4;
+
+/// Common interface of the drum synths, e.g. to build choke groups
+pub trait DrumVoice {
+    /// Triggers on the next call of `tick`
+    fn trigger(&mut self);
+
+    /// Triggers on the `offset`th call of `tick`, counting from zero
+    fn trigger_at(&mut self, offset: usize);
+
+    /// Quickly fades out a ringing hit
+    fn choke(&mut self);
+
+    fn tick(&mut self) -> f32;
+
+    /// Renders a whole block, scheduled triggers land on their exact sample
+    fn process(&mut self, out: &mut [f32]) {
+        out.iter_mut().for_each(|sample| *sample = self.tick());
+    }
+}
+
+/// Triggers `voices[index]` and chokes all other voices of the group, like an open and a closed hi-hat
+pub fn trigger_in_choke_group(voices: &mut [&mut dyn DrumVoice], index: usize) {
+    for (i, voice) in voices.iter_mut().enumerate() {
+        if i == index {
+            voice.trigger();
+        } else {
+            voice.choke();
+        }
+    }
+}
+
+/// Drum synth behind a `DrumVoicePtr`
+#[derive(Clone, Copy, PartialEq)]
+#[repr(C)]
+pub enum DrumKind {
+    Kick,
+    Snare,
+    Hat,
+    Clap,
+    Tom,
+}
+
+/// Pointer to any drum synth, so C can build choke groups of mixed voices
+#[repr(C)]
+pub struct DrumVoicePtr {
+    pub kind: DrumKind,
+    pub ptr: *mut c_void,
+}
+
+impl DrumVoicePtr {
+    /// # Safety
+    /// `ptr` has to point to a valid drum synth of type `kind`
+    pub unsafe fn as_voice_mut(&mut self) -> &mut dyn DrumVoice {
+        match self.kind {
+            DrumKind::Kick => &mut *(self.ptr as *mut SynthKick),
+            DrumKind::Snare => &mut *(self.ptr as *mut SynthSnare),
+            DrumKind::Hat => &mut *(self.ptr as *mut SynthHat),
+            DrumKind::Clap => &mut *(self.ptr as *mut SynthClap),
+            DrumKind::Tom => &mut *(self.ptr as *mut SynthTom),
+        }
+    }
+}
+
+#[derive(Clone, Copy)]
+#[repr(C)]
+pub struct ScheduledTrigger {
+    offset: usize,
+    pub velocity: f32,
+    pub accent: bool,
+}
+
+/// Pending triggers with sample offsets, so they don't get quantized to the processing block
+#[repr(C)]
+pub struct TriggerSchedule {
+    triggers: [ScheduledTrigger; 4],
+    len: usize,
+}
+
+impl TriggerSchedule {
+    pub fn new() -> TriggerSchedule {
+        TriggerSchedule {
+            triggers: [ScheduledTrigger {
+                offset: 0,
+                velocity: 0.0,
+                accent: false,
+            }; MAX_SCHEDULED_TRIGGERS],
+            len: 0,
+        }
+    }
+
+    /// Returns `false` if four triggers are already pending and the trigger got dropped
+    pub fn schedule(&mut self, offset: usize, velocity: f32, accent: bool) -> bool {
+        if self.len == MAX_SCHEDULED_TRIGGERS {
+            return false;
+        }
+
+        self.triggers[self.len] = ScheduledTrigger {
+            offset,
+            velocity,
+            accent,
+        };
+        self.len += 1;
+
+        true
+    }
+
+    /// Needs to be called once before rendering every sample. Returns the trigger due on that sample.
+    ///
+    /// Triggers due on the same sample are merged into one hit with the highest velocity,
+    /// accented if any of them is.
+    #[inline(always)]
+    pub fn advance(&mut self) -> Option<ScheduledTrigger> {
+        let mut due: Option<ScheduledTrigger> = None;
+        let mut i = 0;
+
+        while i < self.len {
+            if self.triggers[i].offset == 0 {
+                let trigger = self.triggers[i];
+                due = Some(match due {
+                    Some(other) => ScheduledTrigger {
+                        offset: 0,
+                        velocity: other.velocity.max(trigger.velocity),
+                        accent: other.accent || trigger.accent,
+                    },
+                    None => trigger,
+                });
+                self.len -= 1;
+                self.triggers[i] = self.triggers[self.len];
+            } else {
+                self.triggers[i].offset -= 1;
+                i += 1;
+            }
+        }
+
+        due
+    }
+
+    pub fn clear(&mut self) {
+        self.len = 0;
+    }
+}
+
+impl Default for TriggerSchedule {
+    fn default() -> Self {
+        TriggerSchedule::new()
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn triggers_land_on_their_offset() {
+        let mut schedule = TriggerSchedule::new();
+        assert!(schedule.schedule(3, 1.0, false));
+        assert!(schedule.schedule(0, 0.5, true));
+
+        let due: [Option<ScheduledTrigger>; 5] = core::array::from_fn(|_| schedule.advance());
+
+        assert!(due[0].is_some_and(|t| t.velocity == 0.5 && t.accent));
+        assert!(due[1].is_none() && due[2].is_none());
+        assert!(due[3].is_some_and(|t| t.velocity == 1.0 && !t.accent));
+        assert!(due[4].is_none());
+    }
+
+    #[test]
+    fn triggers_on_the_same_sample_merge() {
+        let mut schedule = TriggerSchedule::new();
+        assert!(schedule.schedule(2, 0.8, false));
+        assert!(schedule.schedule(2, 0.3, true));
+
+        let due: [Option<ScheduledTrigger>; 4] = core::array::from_fn(|_| schedule.advance());
+
+        assert!(due[0].is_none() && due[1].is_none());
+        assert!(due[2].is_some_and(|t| t.velocity == 0.8 && t.accent));
+        assert!(due[3].is_none());
+    }
+
+    #[test]
+    fn full_schedule_drops_triggers() {
+        let mut schedule = TriggerSchedule::new();
+        for offset in 0..MAX_SCHEDULED_TRIGGERS {
+            assert!(schedule.schedule(offset, 1.0, false));
+        }
+        assert!(!schedule.schedule(0, 1.0, false));
+
+        schedule.clear();
+        assert!(schedule.advance().is_none());
+    }
+}
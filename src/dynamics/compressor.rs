@@ -0,0 +1,138 @@
+use super::{compression_db, Detector, DetectorMode};
+use crate::multi_filter::db_to_gain;
+use crate::smoothed_param::{SmoothedParam, SmoothingMode, DEFAULT_SMOOTHING_MS};
+
+/// cbindgen:ignore
+const MAX_RATIO: f32 = 100.0;
+
+/// cbindgen:ignore
+const MAX_KNEE: f32 = 24.0; // dB
+
+/// Feed-forward stereo compressor with soft knee and makeup gain
+#[repr(C)]
+pub struct Compressor {
+    detector: Detector,
+
+    threshold: f32,
+    ratio: f32,
+    knee: f32,
+    makeup: SmoothedParam,
+}
+
+impl Compressor {
+    pub fn init(sr: f32) -> Compressor {
+        let mut compressor = Compressor {
+            detector: Detector::new(sr, 10.0, 100.0),
+
+            threshold: -20.0,
+            ratio: 4.0,
+            knee: 6.0,
+            makeup: SmoothedParam::new(1.0, SmoothingMode::Linear),
+        };
+
+        compressor.makeup.set_time_ms(DEFAULT_SMOOTHING_MS, sr);
+        compressor
+    }
+
+    pub fn tick(&mut self, input: (f32, f32)) -> (f32, f32) {
+        self.tick_with_sidechain(input, input)
+    }
+
+    /// Compresses `input` depending on the level of `sidechain`, e.g. for ducking
+    pub fn tick_with_sidechain(&mut self, input: (f32, f32), sidechain: (f32, f32)) -> (f32, f32) {
+        let levels = self.detector.levels_db(sidechain);
+        let gains = self.detector.apply_ballistics((
+            compression_db(levels.0, self.threshold, self.ratio, self.knee),
+            compression_db(levels.1, self.threshold, self.ratio, self.knee),
+        ));
+
+        let makeup = self.makeup.tick();
+        (input.0 * gains.0 * makeup, input.1 * gains.1 * makeup)
+    }
+
+    /// Current gain reduction in dB as a positive value, for metering
+    pub fn gain_reduction_db(&self) -> f32 {
+        self.detector.gain_reduction_db()
+    }
+
+    /// Threshold in dB
+    pub fn set_threshold(&mut self, db: f32) {
+        self.threshold = db;
+    }
+
+    /// Ratio between 1 and 100, otherwise clamps
+    pub fn set_ratio(&mut self, ratio: f32) {
+        self.ratio = ratio.clamp(1.0, MAX_RATIO);
+    }
+
+    /// Knee width in dB between 0 and 24, otherwise clamps
+    pub fn set_knee(&mut self, db: f32) {
+        self.knee = db.clamp(0.0, MAX_KNEE);
+    }
+
+    /// Attack time in ms between 0 and 500, otherwise clamps
+    pub fn set_attack(&mut self, ms: f32) {
+        self.detector.set_attack(ms);
+    }
+
+    /// Release time in ms between 1 and 5000, otherwise clamps
+    pub fn set_release(&mut self, ms: f32) {
+        self.detector.set_release(ms);
+    }
+
+    /// Makeup gain in dB
+    pub fn set_makeup(&mut self, db: f32) {
+        self.makeup.set_target(db_to_gain(db));
+    }
+
+    pub fn set_detector_mode(&mut self, mode: DetectorMode) {
+        self.detector.set_mode(mode);
+    }
+
+    pub fn set_stereo_link(&mut self, link: bool) {
+        self.detector.set_stereo_link(link);
+    }
+
+    pub fn update_sr(&mut self, sr: f32) {
+        self.detector.update_sr(sr);
+        self.makeup.set_time_ms(DEFAULT_SMOOTHING_MS, sr);
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn compresses_above_threshold() {
+        let mut compressor = Compressor::init(48000.0);
+        compressor.set_knee(0.0);
+        compressor.set_attack(0.0);
+
+        // 0 dB input, 20 dB over the threshold at 4:1 leaves -15 dB
+        let out = compressor.tick((1.0, 1.0));
+        assert!((out.0 - db_to_gain(-15.0)).abs() < 1e-4);
+        assert!((compressor.gain_reduction_db() - 15.0).abs() < 1e-3);
+
+        // quiet signals pass
+        let mut compressor = Compressor::init(48000.0);
+        assert_eq!(compressor.tick((0.01, 0.01)), (0.01, 0.01));
+    }
+
+    #[test]
+    fn sidechain_and_link() {
+        let mut compressor = Compressor::init(48000.0);
+        compressor.set_attack(0.0);
+
+        // a loud left sidechain ducks both linked channels
+        let out = compressor.tick_with_sidechain((0.01, 0.01), (1.0, 0.0));
+        assert!(out.0 < 0.01 && out.0 == out.1);
+
+        compressor.set_stereo_link(false);
+        for _ in 0..48000 {
+            compressor.tick((0.0, 0.0));
+        }
+        let out = compressor.tick_with_sidechain((0.01, 0.01), (1.0, 0.0));
+        assert!(out.0 < 0.01 && (out.1 - 0.01).abs() < 1e-6);
+    }
+}
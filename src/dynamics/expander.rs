@@ -0,0 +1,130 @@
+use super::{expansion_db, Detector, DetectorMode};
+
+/// cbindgen:ignore
+const MAX_RATIO: f32 = 100.0;
+
+/// cbindgen:ignore
+const MAX_KNEE: f32 = 24.0; // dB
+
+/// cbindgen:ignore
+const MAX_RANGE: f32 = 120.0; // dB
+
+/// Feed-forward stereo downward expander, attenuates signals below the threshold
+#[repr(C)]
+pub struct Expander {
+    detector: Detector,
+
+    threshold: f32,
+    ratio: f32,
+    knee: f32,
+    range: f32,
+}
+
+impl Expander {
+    pub fn init(sr: f32) -> Expander {
+        let mut expander = Expander {
+            detector: Detector::new(sr, 1.0, 100.0),
+
+            threshold: -40.0,
+            ratio: 2.0,
+            knee: 6.0,
+            range: 40.0,
+        };
+
+        expander.detector.set_expanding(true);
+        expander
+    }
+
+    pub fn tick(&mut self, input: (f32, f32)) -> (f32, f32) {
+        self.tick_with_sidechain(input, input)
+    }
+
+    /// Expands `input` depending on the level of `sidechain`
+    pub fn tick_with_sidechain(&mut self, input: (f32, f32), sidechain: (f32, f32)) -> (f32, f32) {
+        let levels = self.detector.levels_db(sidechain);
+        let gains = self.detector.apply_ballistics((
+            expansion_db(levels.0, self.threshold, self.ratio, self.knee).min(self.range),
+            expansion_db(levels.1, self.threshold, self.ratio, self.knee).min(self.range),
+        ));
+
+        (input.0 * gains.0, input.1 * gains.1)
+    }
+
+    /// Current gain reduction in dB as a positive value, for metering
+    pub fn gain_reduction_db(&self) -> f32 {
+        self.detector.gain_reduction_db()
+    }
+
+    /// Threshold in dB
+    pub fn set_threshold(&mut self, db: f32) {
+        self.threshold = db;
+    }
+
+    /// Ratio between 1 and 100, otherwise clamps
+    pub fn set_ratio(&mut self, ratio: f32) {
+        self.ratio = ratio.clamp(1.0, MAX_RATIO);
+    }
+
+    /// Knee width in dB between 0 and 24, otherwise clamps
+    pub fn set_knee(&mut self, db: f32) {
+        self.knee = db.clamp(0.0, MAX_KNEE);
+    }
+
+    /// Maximum attenuation in dB between 0 and 120, otherwise clamps
+    pub fn set_range(&mut self, db: f32) {
+        self.range = db.clamp(0.0, MAX_RANGE);
+    }
+
+    /// Attack time in ms, how fast the gain recovers once the level rises. Between 0 and 500, otherwise clamps
+    pub fn set_attack(&mut self, ms: f32) {
+        self.detector.set_attack(ms);
+    }
+
+    /// Release time in ms, how fast the gain falls once the level drops. Between 1 and 5000, otherwise clamps
+    pub fn set_release(&mut self, ms: f32) {
+        self.detector.set_release(ms);
+    }
+
+    pub fn set_detector_mode(&mut self, mode: DetectorMode) {
+        self.detector.set_mode(mode);
+    }
+
+    pub fn set_stereo_link(&mut self, link: bool) {
+        self.detector.set_stereo_link(link);
+    }
+
+    pub fn update_sr(&mut self, sr: f32) {
+        self.detector.update_sr(sr);
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    const SR: f32 = 48000.0;
+
+    #[test]
+    fn expands_below_the_threshold() {
+        let mut expander = Expander::init(SR);
+        expander.set_threshold(-20.0);
+        expander.set_ratio(2.0);
+        expander.set_knee(0.0);
+
+        // -40 dB is 20 dB below the threshold, a ratio of 2 pushes it 20 dB further down
+        for _ in 0..48000 {
+            expander.tick((0.01, 0.01));
+        }
+        let out = expander.tick((0.01, 0.01));
+        assert!((out.0 - 0.001).abs() < 1e-5);
+        assert!((expander.gain_reduction_db() - 20.0).abs() < 0.1);
+
+        // -6 dB passes unchanged
+        for _ in 0..4800 {
+            expander.tick((0.5, 0.5));
+        }
+        let out = expander.tick((0.5, 0.5));
+        assert!((out.0 - 0.5).abs() < 1e-5);
+        assert!(expander.gain_reduction_db() < 0.01);
+    }
+}
@@ -0,0 +1,359 @@
+use super::{Compressor, DetectorMode, Expander, Limiter, NoiseGate};
+
+///////////////////////////////////////////////////////////////////////////////
+// Compressor
+///////////////////////////////////////////////////////////////////////////////
+
+/// Initializes `Compressor` struct
+#[no_mangle]
+extern "C" fn compressor_init(sr: f32) -> Compressor {
+    Compressor::init(sr)
+}
+
+/// Returns next stereo samples. Raw pointer `stereo_samples` assumes to have exactly two elements!
+#[no_mangle]
+unsafe extern "C" fn compressor_tick(ptr: *mut Compressor, stereo_samples: *mut f32) {
+    let samples = core::slice::from_raw_parts_mut(stereo_samples, 2);
+
+    (samples[0], samples[1]) = ptr
+        .as_mut()
+        .unwrap_unchecked()
+        .tick((samples[0], samples[1]));
+}
+
+/// Returns next stereo samples compressed by the sidechain level. Both raw pointers assume to have exactly two elements!
+#[no_mangle]
+unsafe extern "C" fn compressor_tick_with_sidechain(
+    ptr: *mut Compressor,
+    stereo_samples: *mut f32,
+    sidechain_samples: *const f32,
+) {
+    let samples = core::slice::from_raw_parts_mut(stereo_samples, 2);
+    let sidechain = core::slice::from_raw_parts(sidechain_samples, 2);
+
+    (samples[0], samples[1]) = ptr
+        .as_mut()
+        .unwrap_unchecked()
+        .tick_with_sidechain((samples[0], samples[1]), (sidechain[0], sidechain[1]));
+}
+
+/// Current gain reduction in dB as a positive value
+#[no_mangle]
+unsafe extern "C" fn compressor_gain_reduction_db(ptr: *const Compressor) -> f32 {
+    ptr.as_ref().unwrap_unchecked().gain_reduction_db()
+}
+
+/// Threshold in dB
+#[no_mangle]
+unsafe extern "C" fn compressor_set_threshold(ptr: *mut Compressor, db: f32) {
+    ptr.as_mut().unwrap_unchecked().set_threshold(db);
+}
+
+/// Ratio between 1 and 100, otherwise clamps
+#[no_mangle]
+unsafe extern "C" fn compressor_set_ratio(ptr: *mut Compressor, ratio: f32) {
+    ptr.as_mut().unwrap_unchecked().set_ratio(ratio);
+}
+
+/// Knee width in dB between 0 and 24, otherwise clamps
+#[no_mangle]
+unsafe extern "C" fn compressor_set_knee(ptr: *mut Compressor, db: f32) {
+    ptr.as_mut().unwrap_unchecked().set_knee(db);
+}
+
+/// Attack time in ms between 0 and 500, otherwise clamps
+#[no_mangle]
+unsafe extern "C" fn compressor_set_attack(ptr: *mut Compressor, ms: f32) {
+    ptr.as_mut().unwrap_unchecked().set_attack(ms);
+}
+
+/// Release time in ms between 1 and 5000, otherwise clamps
+#[no_mangle]
+unsafe extern "C" fn compressor_set_release(ptr: *mut Compressor, ms: f32) {
+    ptr.as_mut().unwrap_unchecked().set_release(ms);
+}
+
+/// Makeup gain in dB
+#[no_mangle]
+unsafe extern "C" fn compressor_set_makeup(ptr: *mut Compressor, db: f32) {
+    ptr.as_mut().unwrap_unchecked().set_makeup(db);
+}
+
+#[no_mangle]
+unsafe extern "C" fn compressor_set_detector_mode(ptr: *mut Compressor, mode: DetectorMode) {
+    ptr.as_mut().unwrap_unchecked().set_detector_mode(mode);
+}
+
+#[no_mangle]
+unsafe extern "C" fn compressor_set_stereo_link(ptr: *mut Compressor, link: bool) {
+    ptr.as_mut().unwrap_unchecked().set_stereo_link(link);
+}
+
+///////////////////////////////////////////////////////////////////////////////
+// Limiter
+///////////////////////////////////////////////////////////////////////////////
+
+/// Initializes `Limiter` struct
+#[no_mangle]
+extern "C" fn limiter_init(sr: f32) -> Limiter {
+    Limiter::init(sr)
+}
+
+/// Initializes `Limiter` lookahead buffers. `length` needs to be `>= 964` for `sr = 48000`.
+#[no_mangle]
+unsafe extern "C" fn limiter_set_buffer(ptr: *mut Limiter, buffer: *mut f32, length: usize) {
+    ptr.as_mut()
+        .unwrap_unchecked()
+        .set_buffer(core::slice::from_raw_parts_mut(buffer, length));
+}
+
+/// Returns next stereo samples. Raw pointer `stereo_samples` assumes to have exactly two elements!
+#[no_mangle]
+unsafe extern "C" fn limiter_tick(ptr: *mut Limiter, stereo_samples: *mut f32) {
+    let samples = core::slice::from_raw_parts_mut(stereo_samples, 2);
+
+    (samples[0], samples[1]) = ptr
+        .as_mut()
+        .unwrap_unchecked()
+        .tick((samples[0], samples[1]));
+}
+
+/// Returns next stereo samples limited by the sidechain level. Both raw pointers assume to have exactly two elements!
+#[no_mangle]
+unsafe extern "C" fn limiter_tick_with_sidechain(
+    ptr: *mut Limiter,
+    stereo_samples: *mut f32,
+    sidechain_samples: *const f32,
+) {
+    let samples = core::slice::from_raw_parts_mut(stereo_samples, 2);
+    let sidechain = core::slice::from_raw_parts(sidechain_samples, 2);
+
+    (samples[0], samples[1]) = ptr
+        .as_mut()
+        .unwrap_unchecked()
+        .tick_with_sidechain((samples[0], samples[1]), (sidechain[0], sidechain[1]));
+}
+
+/// Current gain reduction in dB as a positive value
+#[no_mangle]
+unsafe extern "C" fn limiter_gain_reduction_db(ptr: *const Limiter) -> f32 {
+    ptr.as_ref().unwrap_unchecked().gain_reduction_db()
+}
+
+/// Delay of the output in samples
+#[no_mangle]
+unsafe extern "C" fn limiter_latency_samples(ptr: *const Limiter) -> usize {
+    ptr.as_ref().unwrap_unchecked().latency_samples()
+}
+
+/// Lookahead in ms between 0 and 10, otherwise clamps
+#[no_mangle]
+unsafe extern "C" fn limiter_set_lookahead(ptr: *mut Limiter, ms: f32) {
+    ptr.as_mut().unwrap_unchecked().set_lookahead(ms);
+}
+
+/// Threshold and output ceiling in dB
+#[no_mangle]
+unsafe extern "C" fn limiter_set_threshold(ptr: *mut Limiter, db: f32) {
+    ptr.as_mut().unwrap_unchecked().set_threshold(db);
+}
+
+/// Knee width in dB between 0 and 12, otherwise clamps
+#[no_mangle]
+unsafe extern "C" fn limiter_set_knee(ptr: *mut Limiter, db: f32) {
+    ptr.as_mut().unwrap_unchecked().set_knee(db);
+}
+
+/// Release time in ms between 1 and 5000, otherwise clamps
+#[no_mangle]
+unsafe extern "C" fn limiter_set_release(ptr: *mut Limiter, ms: f32) {
+    ptr.as_mut().unwrap_unchecked().set_release(ms);
+}
+
+#[no_mangle]
+unsafe extern "C" fn limiter_set_stereo_link(ptr: *mut Limiter, link: bool) {
+    ptr.as_mut().unwrap_unchecked().set_stereo_link(link);
+}
+
+///////////////////////////////////////////////////////////////////////////////
+// Expander
+///////////////////////////////////////////////////////////////////////////////
+
+/// Initializes `Expander` struct
+#[no_mangle]
+extern "C" fn expander_init(sr: f32) -> Expander {
+    Expander::init(sr)
+}
+
+/// Returns next stereo samples. Raw pointer `stereo_samples` assumes to have exactly two elements!
+#[no_mangle]
+unsafe extern "C" fn expander_tick(ptr: *mut Expander, stereo_samples: *mut f32) {
+    let samples = core::slice::from_raw_parts_mut(stereo_samples, 2);
+
+    (samples[0], samples[1]) = ptr
+        .as_mut()
+        .unwrap_unchecked()
+        .tick((samples[0], samples[1]));
+}
+
+/// Returns next stereo samples expanded by the sidechain level. Both raw pointers assume to have exactly two elements!
+#[no_mangle]
+unsafe extern "C" fn expander_tick_with_sidechain(
+    ptr: *mut Expander,
+    stereo_samples: *mut f32,
+    sidechain_samples: *const f32,
+) {
+    let samples = core::slice::from_raw_parts_mut(stereo_samples, 2);
+    let sidechain = core::slice::from_raw_parts(sidechain_samples, 2);
+
+    (samples[0], samples[1]) = ptr
+        .as_mut()
+        .unwrap_unchecked()
+        .tick_with_sidechain((samples[0], samples[1]), (sidechain[0], sidechain[1]));
+}
+
+/// Current gain reduction in dB as a positive value
+#[no_mangle]
+unsafe extern "C" fn expander_gain_reduction_db(ptr: *const Expander) -> f32 {
+    ptr.as_ref().unwrap_unchecked().gain_reduction_db()
+}
+
+/// Threshold in dB
+#[no_mangle]
+unsafe extern "C" fn expander_set_threshold(ptr: *mut Expander, db: f32) {
+    ptr.as_mut().unwrap_unchecked().set_threshold(db);
+}
+
+/// Ratio between 1 and 100, otherwise clamps
+#[no_mangle]
+unsafe extern "C" fn expander_set_ratio(ptr: *mut Expander, ratio: f32) {
+    ptr.as_mut().unwrap_unchecked().set_ratio(ratio);
+}
+
+/// Knee width in dB between 0 and 24, otherwise clamps
+#[no_mangle]
+unsafe extern "C" fn expander_set_knee(ptr: *mut Expander, db: f32) {
+    ptr.as_mut().unwrap_unchecked().set_knee(db);
+}
+
+/// Maximum attenuation in dB between 0 and 120, otherwise clamps
+#[no_mangle]
+unsafe extern "C" fn expander_set_range(ptr: *mut Expander, db: f32) {
+    ptr.as_mut().unwrap_unchecked().set_range(db);
+}
+
+/// Attack time in ms between 0 and 500, otherwise clamps
+#[no_mangle]
+unsafe extern "C" fn expander_set_attack(ptr: *mut Expander, ms: f32) {
+    ptr.as_mut().unwrap_unchecked().set_attack(ms);
+}
+
+/// Release time in ms between 1 and 5000, otherwise clamps
+#[no_mangle]
+unsafe extern "C" fn expander_set_release(ptr: *mut Expander, ms: f32) {
+    ptr.as_mut().unwrap_unchecked().set_release(ms);
+}
+
+#[no_mangle]
+unsafe extern "C" fn expander_set_detector_mode(ptr: *mut Expander, mode: DetectorMode) {
+    ptr.as_mut().unwrap_unchecked().set_detector_mode(mode);
+}
+
+#[no_mangle]
+unsafe extern "C" fn expander_set_stereo_link(ptr: *mut Expander, link: bool) {
+    ptr.as_mut().unwrap_unchecked().set_stereo_link(link);
+}
+
+///////////////////////////////////////////////////////////////////////////////
+// Noise Gate
+///////////////////////////////////////////////////////////////////////////////
+
+/// Initializes `NoiseGate` struct
+#[no_mangle]
+extern "C" fn noise_gate_init(sr: f32) -> NoiseGate {
+    NoiseGate::init(sr)
+}
+
+/// Returns next stereo samples. Raw pointer `stereo_samples` assumes to have exactly two elements!
+#[no_mangle]
+unsafe extern "C" fn noise_gate_tick(ptr: *mut NoiseGate, stereo_samples: *mut f32) {
+    let samples = core::slice::from_raw_parts_mut(stereo_samples, 2);
+
+    (samples[0], samples[1]) = ptr
+        .as_mut()
+        .unwrap_unchecked()
+        .tick((samples[0], samples[1]));
+}
+
+/// Returns next stereo samples gated by the sidechain level. Both raw pointers assume to have exactly two elements!
+#[no_mangle]
+unsafe extern "C" fn noise_gate_tick_with_sidechain(
+    ptr: *mut NoiseGate,
+    stereo_samples: *mut f32,
+    sidechain_samples: *const f32,
+) {
+    let samples = core::slice::from_raw_parts_mut(stereo_samples, 2);
+    let sidechain = core::slice::from_raw_parts(sidechain_samples, 2);
+
+    (samples[0], samples[1]) = ptr
+        .as_mut()
+        .unwrap_unchecked()
+        .tick_with_sidechain((samples[0], samples[1]), (sidechain[0], sidechain[1]));
+}
+
+/// Current gain reduction in dB as a positive value
+#[no_mangle]
+unsafe extern "C" fn noise_gate_gain_reduction_db(ptr: *const NoiseGate) -> f32 {
+    ptr.as_ref().unwrap_unchecked().gain_reduction_db()
+}
+
+#[no_mangle]
+unsafe extern "C" fn noise_gate_is_open(ptr: *const NoiseGate) -> bool {
+    ptr.as_ref().unwrap_unchecked().is_open()
+}
+
+/// Threshold in dB
+#[no_mangle]
+unsafe extern "C" fn noise_gate_set_threshold(ptr: *mut NoiseGate, db: f32) {
+    ptr.as_mut().unwrap_unchecked().set_threshold(db);
+}
+
+/// Hysteresis in dB between 0 and 24, otherwise clamps
+#[no_mangle]
+unsafe extern "C" fn noise_gate_set_hysteresis(ptr: *mut NoiseGate, db: f32) {
+    ptr.as_mut().unwrap_unchecked().set_hysteresis(db);
+}
+
+/// Hold time in ms between 0 and 2000, otherwise clamps
+#[no_mangle]
+unsafe extern "C" fn noise_gate_set_hold(ptr: *mut NoiseGate, ms: f32) {
+    ptr.as_mut().unwrap_unchecked().set_hold(ms);
+}
+
+/// Attenuation of the closed gate in dB between 0 and 120, otherwise clamps
+#[no_mangle]
+unsafe extern "C" fn noise_gate_set_range(ptr: *mut NoiseGate, db: f32) {
+    ptr.as_mut().unwrap_unchecked().set_range(db);
+}
+
+/// Opening time in ms between 0 and 500, otherwise clamps
+#[no_mangle]
+unsafe extern "C" fn noise_gate_set_attack(ptr: *mut NoiseGate, ms: f32) {
+    ptr.as_mut().unwrap_unchecked().set_attack(ms);
+}
+
+/// Closing time in ms between 1 and 5000, otherwise clamps
+#[no_mangle]
+unsafe extern "C" fn noise_gate_set_release(ptr: *mut NoiseGate, ms: f32) {
+    ptr.as_mut().unwrap_unchecked().set_release(ms);
+}
+
+#[no_mangle]
+unsafe extern "C" fn noise_gate_set_detector_mode(ptr: *mut NoiseGate, mode: DetectorMode) {
+    ptr.as_mut().unwrap_unchecked().set_detector_mode(mode);
+}
+
+#[no_mangle]
+unsafe extern "C" fn noise_gate_set_stereo_link(ptr: *mut NoiseGate, link: bool) {
+    ptr.as_mut().unwrap_unchecked().set_stereo_link(link);
+}
@@ -0,0 +1,196 @@
+use super::{Detector, DetectorMode};
+
+/// cbindgen:ignore
+const MAX_HYSTERESIS: f32 = 24.0; // dB
+
+/// cbindgen:ignore
+const MAX_HOLD: f32 = 2000.0; // ms
+
+/// cbindgen:ignore
+const MAX_RANGE: f32 = 120.0; // dB
+
+/// Stereo noise gate with hysteresis and hold time
+///
+/// Opens above the threshold and closes once the level stayed below `threshold - hysteresis` for the hold time.
+#[repr(C)]
+pub struct NoiseGate {
+    detector: Detector,
+
+    sr: f32,
+    open: [bool; 2],
+    hold_left: [usize; 2],
+
+    threshold: f32,
+    hysteresis: f32,
+    hold: f32,
+    hold_samples: usize,
+    range: f32,
+}
+
+impl NoiseGate {
+    pub fn init(sr: f32) -> NoiseGate {
+        let mut gate = NoiseGate {
+            detector: Detector::new(sr, 0.5, 50.0),
+
+            sr,
+            open: [false; 2],
+            hold_left: [0; 2],
+
+            threshold: -50.0,
+            hysteresis: 6.0,
+            hold: 20.0,
+            hold_samples: 0,
+            range: 80.0,
+        };
+
+        gate.detector.set_expanding(true);
+        gate.set_hold(gate.hold);
+        gate
+    }
+
+    pub fn tick(&mut self, input: (f32, f32)) -> (f32, f32) {
+        self.tick_with_sidechain(input, input)
+    }
+
+    /// Gates `input` depending on the level of `sidechain`, e.g. a filtered copy of it
+    pub fn tick_with_sidechain(&mut self, input: (f32, f32), sidechain: (f32, f32)) -> (f32, f32) {
+        let levels = self.detector.levels_db(sidechain);
+
+        for (channel, level) in [levels.0, levels.1].into_iter().enumerate() {
+            if level > self.threshold {
+                self.open[channel] = true;
+                self.hold_left[channel] = self.hold_samples;
+            } else if level < self.threshold - self.hysteresis {
+                if self.hold_left[channel] > 0 {
+                    self.hold_left[channel] -= 1;
+                } else {
+                    self.open[channel] = false;
+                }
+            }
+        }
+
+        let target = |open: bool| if open { 0.0 } else { self.range };
+        let gains = self
+            .detector
+            .apply_ballistics((target(self.open[0]), target(self.open[1])));
+
+        (input.0 * gains.0, input.1 * gains.1)
+    }
+
+    /// Current gain reduction in dB as a positive value, for metering
+    pub fn gain_reduction_db(&self) -> f32 {
+        self.detector.gain_reduction_db()
+    }
+
+    pub fn is_open(&self) -> bool {
+        self.open[0] || self.open[1]
+    }
+
+    /// Threshold in dB
+    pub fn set_threshold(&mut self, db: f32) {
+        self.threshold = db;
+    }
+
+    /// Distance in dB below the threshold at which the gate closes, between 0 and 24, otherwise clamps
+    pub fn set_hysteresis(&mut self, db: f32) {
+        self.hysteresis = db.clamp(0.0, MAX_HYSTERESIS);
+    }
+
+    /// Time in ms the gate stays open after the level dropped, between 0 and 2000, otherwise clamps
+    pub fn set_hold(&mut self, ms: f32) {
+        self.hold = ms.clamp(0.0, MAX_HOLD);
+        self.hold_samples = (self.hold * 0.001 * self.sr) as usize;
+    }
+
+    /// Attenuation of the closed gate in dB between 0 and 120, otherwise clamps
+    pub fn set_range(&mut self, db: f32) {
+        self.range = db.clamp(0.0, MAX_RANGE);
+    }
+
+    /// Opening time in ms between 0 and 500, otherwise clamps
+    pub fn set_attack(&mut self, ms: f32) {
+        self.detector.set_attack(ms);
+    }
+
+    /// Closing time in ms between 1 and 5000, otherwise clamps
+    pub fn set_release(&mut self, ms: f32) {
+        self.detector.set_release(ms);
+    }
+
+    pub fn set_detector_mode(&mut self, mode: DetectorMode) {
+        self.detector.set_mode(mode);
+    }
+
+    pub fn set_stereo_link(&mut self, link: bool) {
+        self.detector.set_stereo_link(link);
+    }
+
+    pub fn update_sr(&mut self, sr: f32) {
+        self.sr = sr;
+        self.detector.update_sr(sr);
+        self.set_hold(self.hold);
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    const SR: f32 = 48000.0;
+
+    #[test]
+    fn hysteresis_and_hold() {
+        let mut gate = NoiseGate::init(SR);
+        gate.set_threshold(-20.0);
+        gate.set_hysteresis(10.0);
+        gate.set_hold(10.0);
+
+        for _ in 0..4800 {
+            gate.tick((0.001, 0.001));
+        }
+        assert!(gate.tick((0.001, 0.001)).0 < 0.001 * 0.01);
+
+        gate.tick((1.0, 1.0));
+        assert!(gate.is_open());
+
+        // -26 dB is below the threshold but within the hysteresis
+        for _ in 0..4800 {
+            gate.tick((0.05, 0.05));
+        }
+        assert!(gate.is_open());
+
+        // closes after the hold time
+        for _ in 0..479 {
+            gate.tick((0.0, 0.0));
+        }
+        assert!(gate.is_open());
+        for _ in 0..2 {
+            gate.tick((0.0, 0.0));
+        }
+        assert!(!gate.is_open());
+    }
+
+    #[test]
+    fn opens_with_attack_time() {
+        let mut gate = NoiseGate::init(SR);
+        gate.set_threshold(-20.0);
+        gate.set_attack(0.5);
+        gate.set_release(50.0);
+
+        for _ in 0..24000 {
+            gate.tick((0.0, 0.0));
+        }
+        assert!(gate.gain_reduction_db() > 79.0);
+
+        // fully open 5 ms after a step, ten times the attack time
+        let out: [(f32, f32); 240] = core::array::from_fn(|_| gate.tick((1.0, 1.0)));
+        assert!(out[23].0 < 0.5);
+        assert!(out[239].0 > 0.99);
+
+        // closes with the much slower release
+        for _ in 0..960 + 240 {
+            gate.tick((0.0, 0.0));
+        }
+        assert!(gate.gain_reduction_db() > 1.0 && gate.gain_reduction_db() < 40.0);
+    }
+}
@@ -0,0 +1,265 @@
+use libm::roundf;
+
+use super::{compression_db, Detector};
+use crate::multi_filter::db_to_gain;
+use crate::tools::{
+    memory_access::{from_slice_mut, null_mut},
+    DelayLine,
+};
+
+/// cbindgen:ignore
+const MAX_LOOKAHEAD: f32 = 10.0; // ms
+
+/// Time constants the attack needs to reach 99.9% of the reduction, ln(1000)
+/// cbindgen:ignore
+const ATTACK_PER_LOOKAHEAD: f32 = 6.9;
+
+/// Blocks the peak hold window is split into
+/// cbindgen:ignore
+const HOLD_BLOCKS: usize = 16;
+
+/// cbindgen:ignore
+const MAX_KNEE: f32 = 12.0; // dB
+
+/// Running maximum over a sliding window, rounded up to whole blocks
+///
+/// Keeps the maximum of each block instead of every sample, so it needs no buffer.
+#[repr(C)]
+pub struct PeakHold {
+    blocks: [f32; 16],
+    current: f32,
+    index: usize,
+    filled: usize,
+    block_len: usize,
+}
+
+impl PeakHold {
+    pub fn new() -> PeakHold {
+        PeakHold {
+            blocks: [0.0; HOLD_BLOCKS],
+            current: 0.0,
+            index: 0,
+            filled: 0,
+            block_len: 1,
+        }
+    }
+
+    /// Holds every value for at least `samples`, including the current one
+    pub fn set_window(&mut self, samples: usize) {
+        self.block_len = samples.div_ceil(HOLD_BLOCKS).max(1);
+    }
+
+    /// Maximum of `x` and all values within the window, expects values >= 0.0
+    #[inline]
+    pub fn tick(&mut self, x: f32) -> f32 {
+        self.current = self.current.max(x);
+        let held = self.blocks.iter().fold(self.current, |max, x| max.max(*x));
+
+        self.filled += 1;
+        if self.filled >= self.block_len {
+            self.blocks[self.index] = self.current;
+            self.index = (self.index + 1) % HOLD_BLOCKS;
+            self.current = 0.0;
+            self.filled = 0;
+        }
+
+        held
+    }
+}
+
+/// Stereo peak limiter with lookahead
+///
+/// The audio is delayed by the lookahead, so the gain reduction is in place before a peak arrives.
+/// Without a buffer it works as a fast compressor with an infinite ratio.
+#[repr(C)]
+pub struct Limiter {
+    delay_l: DelayLine,
+    delay_r: DelayLine,
+    detector: Detector,
+    hold: [PeakHold; 2],
+
+    sr: f32,
+    lookahead: f32,
+    lookahead_samples: f32,
+    threshold: f32,
+    ceiling: f32,
+    knee: f32,
+}
+
+impl Limiter {
+    pub fn init(sr: f32) -> Limiter {
+        let mut limiter = Limiter {
+            delay_l: DelayLine::new(null_mut()),
+            delay_r: DelayLine::new(null_mut()),
+            detector: Detector::new(sr, 0.0, 50.0),
+            hold: [PeakHold::new(), PeakHold::new()],
+
+            sr,
+            lookahead: 0.0,
+            lookahead_samples: 0.0,
+            threshold: -1.0,
+            ceiling: db_to_gain(-1.0),
+            knee: 0.0,
+        };
+
+        limiter.set_lookahead(5.0);
+        limiter
+    }
+
+    /// Split into both channels. Needs `length >= 2 * (0.01 * sr + 2)` for the longest lookahead, e.g. 964 for `sr = 48000`
+    pub fn set_buffer(&mut self, buffer: &mut [f32]) {
+        let (left, right) = buffer.split_at_mut(buffer.len() / 2);
+        self.delay_l.change_buffer(from_slice_mut(left));
+        self.delay_r.change_buffer(from_slice_mut(right));
+        self.set_lookahead(self.lookahead);
+    }
+
+    pub fn tick(&mut self, input: (f32, f32)) -> (f32, f32) {
+        self.tick_with_sidechain(input, input)
+    }
+
+    /// Limits `input` depending on the level of `sidechain`
+    pub fn tick_with_sidechain(&mut self, input: (f32, f32), sidechain: (f32, f32)) -> (f32, f32) {
+        let levels = self.detector.levels_db(sidechain);
+
+        // every peak keeps its reduction until it left the lookahead
+        let gains = self.detector.apply_ballistics((
+            self.hold[0].tick(compression_db(
+                levels.0,
+                self.threshold,
+                f32::INFINITY,
+                self.knee,
+            )),
+            self.hold[1].tick(compression_db(
+                levels.1,
+                self.threshold,
+                f32::INFINITY,
+                self.knee,
+            )),
+        ));
+
+        let delayed = if self.delay_l.len() > 0 {
+            self.delay_l.write_and_advance(input.0);
+            self.delay_r.write_and_advance(input.1);
+
+            // the newest sample sits one behind the write position
+            let offset = -(1.0 + self.lookahead_samples);
+            (
+                self.delay_l.read_lerp_wrapped_at(offset),
+                self.delay_r.read_lerp_wrapped_at(offset),
+            )
+        } else {
+            input
+        };
+
+        // the attack leaves 0.1% of the reduction in dB, the clamp catches that rest
+        (
+            (delayed.0 * gains.0).clamp(-self.ceiling, self.ceiling),
+            (delayed.1 * gains.1).clamp(-self.ceiling, self.ceiling),
+        )
+    }
+
+    /// Current gain reduction in dB as a positive value, for metering
+    pub fn gain_reduction_db(&self) -> f32 {
+        self.detector.gain_reduction_db()
+    }
+
+    /// Delay of the output in samples, for latency compensation
+    pub fn latency_samples(&self) -> usize {
+        self.lookahead_samples as usize
+    }
+
+    /// Lookahead in ms between 0 and 10, otherwise clamps. Also sets the attack time.
+    ///
+    /// Limited by the buffer length, without a buffer there is no lookahead.
+    pub fn set_lookahead(&mut self, ms: f32) {
+        self.lookahead = ms.clamp(0.0, MAX_LOOKAHEAD);
+
+        let max_samples = self.delay_l.len().saturating_sub(2) as f32;
+        self.lookahead_samples = roundf(self.lookahead * 0.001 * self.sr).min(max_samples);
+        self.detector
+            .set_attack(1000.0 * self.lookahead_samples / (self.sr * ATTACK_PER_LOOKAHEAD));
+        for hold in self.hold.iter_mut() {
+            hold.set_window(self.lookahead_samples as usize + 1);
+        }
+    }
+
+    /// Threshold and output ceiling in dB
+    pub fn set_threshold(&mut self, db: f32) {
+        self.threshold = db;
+        self.ceiling = db_to_gain(db);
+    }
+
+    /// Knee width in dB between 0 and 12, otherwise clamps
+    pub fn set_knee(&mut self, db: f32) {
+        self.knee = db.clamp(0.0, MAX_KNEE);
+    }
+
+    /// Release time in ms between 1 and 5000, otherwise clamps
+    pub fn set_release(&mut self, ms: f32) {
+        self.detector.set_release(ms);
+    }
+
+    pub fn set_stereo_link(&mut self, link: bool) {
+        self.detector.set_stereo_link(link);
+    }
+
+    pub fn update_sr(&mut self, sr: f32) {
+        self.sr = sr;
+        self.detector.update_sr(sr);
+        self.set_lookahead(self.lookahead);
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    const SR: f32 = 48000.0;
+
+    #[test]
+    fn never_exceeds_ceiling() {
+        let mut buffer = [0.0; 1024];
+        let mut limiter = Limiter::init(SR);
+        limiter.set_buffer(&mut buffer);
+        limiter.set_threshold(-6.0);
+
+        let ceiling = db_to_gain(-6.0);
+        let latency = limiter.latency_samples();
+        assert_eq!(latency, 240);
+
+        let out: [(f32, f32); 2000] = core::array::from_fn(|i| {
+            let x = if i % 500 == 0 { 4.0 } else { 0.1 };
+            limiter.tick((x, -x))
+        });
+        assert!(out
+            .iter()
+            .all(|x| x.0.abs() <= ceiling && x.1.abs() <= ceiling));
+
+        // the peak arrives delayed, with the gain reduction nearly in place
+        assert!(out[latency].0 > 0.99 * ceiling);
+        assert_eq!(out[latency - 1].0, 0.0);
+    }
+
+    #[test]
+    fn reduction_in_place_without_clamp() {
+        let mut buffer = [0.0; 1024];
+        let mut limiter = Limiter::init(SR);
+        limiter.set_buffer(&mut buffer);
+        limiter.set_threshold(-6.0);
+
+        let ceiling = db_to_gain(-6.0);
+        let latency = limiter.latency_samples();
+
+        // single sample peaks, the gain alone keeps them within 0.05 dB of the ceiling
+        for i in 0..2000 {
+            let x = if i % 500 == 0 { 4.0 } else { 0.1 };
+            limiter.tick((x, x));
+
+            if i % 500 == latency {
+                let unclamped = 4.0 * db_to_gain(-limiter.gain_reduction_db());
+                assert!(unclamped < 1.005 * ceiling);
+            }
+        }
+    }
+}
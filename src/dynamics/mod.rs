@@ -0,0 +1,237 @@
+mod compressor;
+mod expander;
+mod ffi;
+mod gate;
+mod limiter;
+
+pub use compressor::Compressor;
+pub use expander::Expander;
+pub use gate::NoiseGate;
+pub use limiter::Limiter;
+
+use libm::{expf, sqrtf};
+
+use crate::multi_filter::{db_to_gain, gain_to_db};
+
+/// Averaging time of the RMS detector
+/// cbindgen:ignore
+const RMS_WINDOW: f32 = 0.010; // s
+
+/// cbindgen:ignore
+const MAX_ATTACK: f32 = 500.0; // ms
+
+/// cbindgen:ignore
+const MIN_RELEASE: f32 = 1.0; // ms
+
+/// cbindgen:ignore
+const MAX_RELEASE: f32 = 5000.0; // ms
+
+/// How the level of the sidechain is measured
+#[derive(Clone, Copy, PartialEq)]
+#[repr(C)]
+pub enum DetectorMode {
+    /// Absolute sample value, reacts to every transient
+    Peak,
+    /// Average power over 10 ms, closer to the perceived loudness
+    Rms,
+}
+
+/// Level detection and attack/release ballistics shared by all dynamics processors
+///
+/// The ballistics smooth the gain reduction in dB, which keeps the static curve independent of the timing.
+#[repr(C)]
+pub struct Detector {
+    mode: DetectorMode,
+    stereo_link: bool,
+    expanding: bool,
+    sr: f32,
+
+    attack: f32,
+    release: f32,
+    attack_coeff: f32,
+    release_coeff: f32,
+    rms_coeff: f32,
+
+    mean_square: [f32; 2],
+    reduction: [f32; 2],
+}
+
+impl Detector {
+    pub fn new(sr: f32, attack_ms: f32, release_ms: f32) -> Detector {
+        let mut detector = Detector {
+            mode: DetectorMode::Peak,
+            stereo_link: true,
+            expanding: false,
+            sr,
+
+            attack: attack_ms,
+            release: release_ms,
+            attack_coeff: 0.0,
+            release_coeff: 0.0,
+            rms_coeff: 0.0,
+
+            mean_square: [0.0; 2],
+            reduction: [0.0; 2],
+        };
+
+        detector.update_coeffs();
+        detector
+    }
+
+    /// Levels of both sidechain channels in dB. Linked channels share the louder level.
+    #[inline]
+    pub fn levels_db(&mut self, sidechain: (f32, f32)) -> (f32, f32) {
+        let levels = match self.mode {
+            DetectorMode::Peak => (sidechain.0.abs(), sidechain.1.abs()),
+            DetectorMode::Rms => {
+                for (mean_square, x) in self.mean_square.iter_mut().zip([sidechain.0, sidechain.1])
+                {
+                    *mean_square += self.rms_coeff * (x * x - *mean_square);
+                }
+                (sqrtf(self.mean_square[0]), sqrtf(self.mean_square[1]))
+            }
+        };
+
+        if self.stereo_link {
+            let linked = gain_to_db(levels.0.max(levels.1));
+            (linked, linked)
+        } else {
+            (gain_to_db(levels.0), gain_to_db(levels.1))
+        }
+    }
+
+    /// Smooths the target gain reductions in dB with attack and release, returns linear gains
+    #[inline]
+    pub fn apply_ballistics(&mut self, target: (f32, f32)) -> (f32, f32) {
+        for (reduction, target) in self.reduction.iter_mut().zip([target.0, target.1]) {
+            // compressors attack while the reduction grows, expanders and gates while it falls
+            let coeff = if (target > *reduction) != self.expanding {
+                self.attack_coeff
+            } else {
+                self.release_coeff
+            };
+            *reduction = target + coeff * (*reduction - target);
+        }
+
+        (
+            db_to_gain(-self.reduction[0]),
+            db_to_gain(-self.reduction[1]),
+        )
+    }
+
+    /// Current gain reduction in dB as a positive value, the larger one of both channels
+    pub fn gain_reduction_db(&self) -> f32 {
+        self.reduction[0].max(self.reduction[1])
+    }
+
+    /// Expanding processors attack while the gain reduction falls, i.e. while a gate opens
+    pub fn set_expanding(&mut self, expanding: bool) {
+        self.expanding = expanding;
+    }
+
+    pub fn set_mode(&mut self, mode: DetectorMode) {
+        self.mode = mode;
+    }
+
+    /// Linked channels get the same gain reduction, which keeps the stereo image stable
+    pub fn set_stereo_link(&mut self, link: bool) {
+        self.stereo_link = link;
+    }
+
+    /// Attack time in ms between 0 and 500, otherwise clamps
+    pub fn set_attack(&mut self, ms: f32) {
+        self.attack = ms.clamp(0.0, MAX_ATTACK);
+        self.update_coeffs();
+    }
+
+    /// Release time in ms between 1 and 5000, otherwise clamps
+    pub fn set_release(&mut self, ms: f32) {
+        self.release = ms.clamp(MIN_RELEASE, MAX_RELEASE);
+        self.update_coeffs();
+    }
+
+    pub fn update_sr(&mut self, sr: f32) {
+        self.sr = sr;
+        self.update_coeffs();
+    }
+
+    fn update_coeffs(&mut self) {
+        self.attack_coeff = time_coeff(self.attack * 0.001, self.sr);
+        self.release_coeff = time_coeff(self.release * 0.001, self.sr);
+        self.rms_coeff = 1.0 - time_coeff(RMS_WINDOW, self.sr);
+    }
+}
+
+/// Pole of a one-pole smoother with time constant `seconds`, 0.0 reacts instantly
+fn time_coeff(seconds: f32, sr: f32) -> f32 {
+    if seconds > 0.0 {
+        expf(-1.0 / (seconds * sr))
+    } else {
+        0.0
+    }
+}
+
+/// Gain reduction in dB of a downward compressor with a soft knee of `knee` dB
+pub fn compression_db(level_db: f32, threshold_db: f32, ratio: f32, knee_db: f32) -> f32 {
+    let over = level_db - threshold_db;
+    let slope = 1.0 - 1.0 / ratio;
+
+    if 2.0 * over <= -knee_db {
+        0.0
+    } else if 2.0 * over < knee_db {
+        // quadratic interpolation through the knee
+        slope * (over + 0.5 * knee_db) * (over + 0.5 * knee_db) / (2.0 * knee_db)
+    } else {
+        slope * over
+    }
+}
+
+/// Gain reduction in dB of a downward expander with a soft knee of `knee` dB
+pub fn expansion_db(level_db: f32, threshold_db: f32, ratio: f32, knee_db: f32) -> f32 {
+    let under = threshold_db - level_db;
+    let slope = ratio - 1.0;
+
+    if 2.0 * under <= -knee_db {
+        0.0
+    } else if 2.0 * under < knee_db {
+        slope * (under + 0.5 * knee_db) * (under + 0.5 * knee_db) / (2.0 * knee_db)
+    } else {
+        slope * under
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn static_curves() {
+        // hard knee
+        assert_eq!(compression_db(-30.0, -20.0, 4.0, 0.0), 0.0);
+        assert_eq!(compression_db(-12.0, -20.0, 4.0, 0.0), 6.0);
+        assert_eq!(expansion_db(-10.0, -20.0, 2.0, 0.0), 0.0);
+        assert_eq!(expansion_db(-30.0, -20.0, 2.0, 0.0), 10.0);
+
+        // the soft knee meets the hard curve at its edges and is continuous
+        assert!(compression_db(-23.0, -20.0, 4.0, 6.0).abs() < 1e-6);
+        assert!((compression_db(-17.0, -20.0, 4.0, 6.0) - 2.25).abs() < 1e-5);
+        assert!(compression_db(-20.0, -20.0, 4.0, 6.0) > 0.0);
+    }
+
+    #[test]
+    fn ballistics() {
+        let sr = 48000.0;
+        let mut detector = Detector::new(sr, 1.0, 100.0);
+
+        for _ in 0..480 {
+            detector.apply_ballistics((10.0, 10.0));
+        }
+        assert!(detector.gain_reduction_db() > 9.9);
+
+        // release is much slower than the attack
+        for _ in 0..480 {
+            detector.apply_ballistics((0.0, 0.0));
+        }
+        assert!(detector.gain_reduction_db() > 9.0);
+    }
+}
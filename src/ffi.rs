@@ -1,4 +1,5 @@
-use crate::DSPUtility;
+use crate::drum_voice::{DrumVoicePtr, ScheduledTrigger};
+use crate::{DSPUtility, TriggerSchedule};
 
 #[no_mangle]
 extern "C" fn f32_samples_to_seconds(val: f32, sr: f32) -> f32 {
@@ -19,3 +20,62 @@ extern "C" fn f32_seconds_to_samples(val: f32, sr: f32) -> f32 {
 extern "C" fn f32_millis_to_samples(val: f32, sr: f32) -> f32 {
     val.millis_to_samples(sr)
 }
+
+/// Triggers `voices[index]` and chokes all other voices of the group, like an open and a closed hi-hat
+#[no_mangle]
+unsafe extern "C" fn drum_voice_trigger_in_choke_group(
+    voices: *mut DrumVoicePtr,
+    length: usize,
+    index: usize,
+) {
+    for (i, voice) in core::slice::from_raw_parts_mut(voices, length)
+        .iter_mut()
+        .enumerate()
+    {
+        if i == index {
+            voice.as_voice_mut().trigger();
+        } else {
+            voice.as_voice_mut().choke();
+        }
+    }
+}
+
+/// Initializes an empty `TriggerSchedule` struct
+#[no_mangle]
+extern "C" fn trigger_schedule_init() -> TriggerSchedule {
+    TriggerSchedule::new()
+}
+
+/// Returns `false` if four triggers are already pending and the trigger got dropped
+#[no_mangle]
+unsafe extern "C" fn trigger_schedule_schedule(
+    ptr: *mut TriggerSchedule,
+    offset: usize,
+    velocity: f32,
+    accent: bool,
+) -> bool {
+    ptr.as_mut()
+        .unwrap_unchecked()
+        .schedule(offset, velocity, accent)
+}
+
+/// Needs to be called once before rendering every sample. Writes the trigger due on that sample to `due` and returns `true`, if there is one. Triggers on the same sample merge into the loudest, accented if any is.
+#[no_mangle]
+unsafe extern "C" fn trigger_schedule_advance(
+    ptr: *mut TriggerSchedule,
+    due: *mut ScheduledTrigger,
+) -> bool {
+    match ptr.as_mut().unwrap_unchecked().advance() {
+        Some(trigger) => {
+            *due.as_mut().unwrap_unchecked() = trigger;
+            true
+        }
+        None => false,
+    }
+}
+
+/// Drops all pending triggers
+#[no_mangle]
+unsafe extern "C" fn trigger_schedule_clear(ptr: *mut TriggerSchedule) {
+    ptr.as_mut().unwrap_unchecked().clear();
+}
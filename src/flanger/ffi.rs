@@ -0,0 +1,65 @@
+use crate::Flanger;
+
+/// Initializes `Flanger` struct
+#[no_mangle]
+extern "C" fn flanger_init(sr: f32) -> Flanger {
+    Flanger::init(sr)
+}
+
+/// Initializes `Flanger` delay buffer. `length` needs to be `>= 720` for `sr = 48000`.
+#[no_mangle]
+unsafe extern "C" fn flanger_set_buffer(ptr: *mut Flanger, buffer: *mut f32, length: usize) {
+    ptr.as_mut()
+        .unwrap_unchecked()
+        .set_buffer(core::slice::from_raw_parts_mut(buffer, length));
+}
+
+/// Returns next sample
+#[no_mangle]
+unsafe extern "C" fn flanger_tick(ptr: *mut Flanger, sample: f32) -> f32 {
+    ptr.as_mut().unwrap_unchecked().tick(sample)
+}
+
+/// Sweep speed between 0.01 and 10 Hz, otherwise clamps
+#[no_mangle]
+unsafe extern "C" fn flanger_set_rate(ptr: *mut Flanger, hz: f32) {
+    ptr.as_mut().unwrap_unchecked().set_rate(hz);
+}
+
+/// Only accepts values between 0.0 and 1.0, otherwise clamps
+#[no_mangle]
+unsafe extern "C" fn flanger_set_depth(ptr: *mut Flanger, val: f32) {
+    ptr.as_mut().unwrap_unchecked().set_depth(val);
+}
+
+/// Only accepts values between 0.0 and 1.0, otherwise clamps
+#[no_mangle]
+unsafe extern "C" fn flanger_set_manual(ptr: *mut Flanger, val: f32) {
+    ptr.as_mut().unwrap_unchecked().set_manual(val);
+}
+
+/// Only accepts values between -1.0 and 1.0, otherwise clamps
+#[no_mangle]
+unsafe extern "C" fn flanger_set_feedback(ptr: *mut Flanger, val: f32) {
+    ptr.as_mut().unwrap_unchecked().set_feedback(val);
+}
+
+/// Delays the dry signal as well, so the sweep passes through zero
+#[no_mangle]
+unsafe extern "C" fn flanger_set_through_zero(ptr: *mut Flanger, through_zero: bool) {
+    ptr.as_mut()
+        .unwrap_unchecked()
+        .set_through_zero(through_zero);
+}
+
+/// Dry/Wet mixing
+#[no_mangle]
+unsafe extern "C" fn flanger_set_mix(ptr: *mut Flanger, val: f32) {
+    ptr.as_mut().unwrap_unchecked().set_mix(val);
+}
+
+/// Time in ms that manual, depth, feedback and mix changes take
+#[no_mangle]
+unsafe extern "C" fn flanger_set_smoothing_time(ptr: *mut Flanger, ms: f32) {
+    ptr.as_mut().unwrap_unchecked().set_smoothing_time(ms);
+}
@@ -0,0 +1,193 @@
+mod ffi;
+
+use crate::lfo::Lfo;
+use crate::smoothed_param::{SmoothedParam, SmoothingMode, DEFAULT_SMOOTHING_MS};
+use crate::tools::{
+    memory_access::{from_slice_mut, null_mut},
+    DelayLine,
+};
+
+/// cbindgen:ignore
+const SHORTEST_MANUAL: f32 = 0.0001; // s
+
+/// cbindgen:ignore
+const LONGEST_MANUAL: f32 = 0.005; // s
+
+/// cbindgen:ignore
+const MAX_DEPTH: f32 = 0.005; // s
+
+/// cbindgen:ignore
+const MAX_FEEDBACK: f32 = 0.95;
+
+/// cbindgen:ignore
+const MIN_RATE: f32 = 0.01; // Hz
+
+/// cbindgen:ignore
+const MAX_RATE: f32 = 10.0; // Hz
+
+/// Flanger with feedback on a single modulated delay tap
+///
+/// In through-zero mode the dry signal is delayed as well, so the sweep passes through zero delay
+/// difference like two tape machines.
+#[repr(C)]
+pub struct Flanger {
+    delay_line: DelayLine,
+    lfo: Lfo,
+
+    sr: f32,
+    smoothing_ms: f32,
+    through_zero: bool,
+    manual: SmoothedParam,
+    depth: SmoothedParam,
+    feedback: SmoothedParam,
+    mix: SmoothedParam,
+}
+
+impl Flanger {
+    pub fn init(sr: f32) -> Flanger {
+        let mut flanger = Flanger {
+            delay_line: DelayLine::new(null_mut()),
+            lfo: Lfo::new(0.2, sr),
+
+            sr,
+            smoothing_ms: DEFAULT_SMOOTHING_MS,
+            through_zero: false,
+            manual: SmoothedParam::new(0.001, SmoothingMode::OnePole),
+            depth: SmoothedParam::new(0.5 * MAX_DEPTH, SmoothingMode::OnePole),
+            feedback: SmoothedParam::new(0.5, SmoothingMode::Linear),
+            mix: SmoothedParam::new(0.5, SmoothingMode::Linear),
+        };
+
+        flanger.set_smoothing_time(DEFAULT_SMOOTHING_MS);
+        flanger
+    }
+
+    /// Needs `length >= 0.015 * sr` for the longest delay, e.g. 720 for `sr = 48000`
+    pub fn set_buffer(&mut self, buffer: &mut [f32]) {
+        self.delay_line.change_buffer(from_slice_mut(buffer));
+    }
+
+    pub fn tick(&mut self, input: f32) -> f32 {
+        let manual = self.manual.tick() * self.sr;
+        let depth = self.depth.tick() * self.sr;
+        let lfo = self.lfo.tick();
+        let max_delay = self.delay_line.len().saturating_sub(2) as f32;
+
+        let (dry, delay) = if self.through_zero {
+            // the wet tap sweeps around the fixed dry tap
+            let center = (manual + depth).min(max_delay);
+            (
+                self.delay_line.read_lerp_wrapped_at(-center),
+                center + depth * lfo,
+            )
+        } else {
+            (input, manual + depth * (0.5 + 0.5 * lfo))
+        };
+
+        let wet = self
+            .delay_line
+            .read_lerp_wrapped_at(-delay.clamp(1.0, max_delay.max(1.0)));
+
+        self.delay_line
+            .write_and_advance(input + self.feedback.tick() * wet);
+
+        let mix = self.mix.tick();
+        (1.0 - mix) * dry + mix * wet
+    }
+
+    /// Sweep speed between 0.01 and 10 Hz, otherwise clamps
+    pub fn set_rate(&mut self, hz: f32) {
+        self.lfo.set_freq(hz.clamp(MIN_RATE, MAX_RATE));
+    }
+
+    /// Sweep range up to 5 ms. Only accepts values between 0.0 and 1.0, otherwise clamps
+    pub fn set_depth(&mut self, val: f32) {
+        self.depth.set_target(val.clamp(0.0, 1.0) * MAX_DEPTH);
+    }
+
+    /// Delay the sweep starts from, between 0.1 and 5 ms. Only accepts values between 0.0 and 1.0, otherwise clamps
+    pub fn set_manual(&mut self, val: f32) {
+        self.manual
+            .set_target(SHORTEST_MANUAL + val.clamp(0.0, 1.0) * (LONGEST_MANUAL - SHORTEST_MANUAL));
+    }
+
+    /// Negative feedback gives a hollower sound. Only accepts values between -1.0 and 1.0, otherwise clamps
+    pub fn set_feedback(&mut self, val: f32) {
+        self.feedback
+            .set_target(val.clamp(-1.0, 1.0) * MAX_FEEDBACK);
+    }
+
+    pub fn set_through_zero(&mut self, through_zero: bool) {
+        self.through_zero = through_zero;
+    }
+
+    /// Dry/Wet mixing. Only accepts values between 0.0 and 1.0, otherwise clamps
+    pub fn set_mix(&mut self, val: f32) {
+        self.mix.set_target(val.clamp(0.0, 1.0));
+    }
+
+    /// Time in ms that manual, depth, feedback and mix changes take
+    pub fn set_smoothing_time(&mut self, ms: f32) {
+        self.smoothing_ms = ms;
+        for param in [
+            &mut self.manual,
+            &mut self.depth,
+            &mut self.feedback,
+            &mut self.mix,
+        ] {
+            param.set_time_ms(ms, self.sr);
+        }
+    }
+
+    pub fn update_sr(&mut self, sr: f32) {
+        self.sr = sr;
+        self.set_smoothing_time(self.smoothing_ms);
+        self.lfo.set_sr(sr);
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    const SR: f32 = 48000.0;
+
+    #[test]
+    fn through_zero_delays_dry() {
+        let mut buffer = [0.0; 1024];
+        let mut flanger = Flanger::init(SR);
+        flanger.set_buffer(&mut buffer);
+        flanger.set_smoothing_time(0.0);
+        flanger.set_through_zero(true);
+        flanger.set_feedback(0.0);
+        flanger.set_depth(0.0);
+
+        // without depth both taps are equal, so the output is just the delayed input
+        flanger.set_mix(0.5);
+        let out: [f32; 512] =
+            core::array::from_fn(|i| flanger.tick(if i == 0 { 1.0 } else { 0.0 }));
+        let center = (0.001 * SR) as usize;
+        assert!((out[center] - 1.0).abs() < 1e-3);
+        assert!(out
+            .iter()
+            .enumerate()
+            .all(|(i, x)| i == center || x.abs() < 1e-3));
+    }
+
+    #[test]
+    fn feedback_repeats() {
+        let mut buffer = [0.0; 1024];
+        let mut flanger = Flanger::init(SR);
+        flanger.set_buffer(&mut buffer);
+        flanger.set_smoothing_time(0.0);
+        flanger.set_depth(0.0);
+        flanger.set_mix(1.0);
+
+        flanger.set_feedback(1.0);
+        let repeats = (0..1000)
+            .map(|i| flanger.tick(if i == 0 { 1.0 } else { 0.0 }))
+            .filter(|x| x.abs() > 0.1)
+            .count();
+        assert!(repeats > 5);
+    }
+}
@@ -26,3 +26,9 @@ unsafe extern "C" fn freeverb_set_all_params(ptr: *mut Freeverb, params: *mut Fr
         .unwrap_unchecked()
         .set_all(params.as_ref().unwrap_unchecked());
 }
+
+/// Time in ms that parameter changes take
+#[no_mangle]
+unsafe extern "C" fn freeverb_set_smoothing_time(ptr: *mut Freeverb, ms: f32) {
+    ptr.as_mut().unwrap_unchecked().set_smoothing_time(ms);
+}
@@ -3,6 +3,7 @@ mod ffi;
 ///////////////
 // TODO: Calculate required buffer allocations for common sample rates!
 ///////////////
+use crate::smoothed_param::{SmoothedParam, SmoothingMode, DEFAULT_SMOOTHING_MS};
 use crate::tools::memory_access::{from_slice_mut, null_mut};
 use crate::tools::{AllPass, Comb};
 
@@ -42,10 +43,13 @@ pub struct Freeverb {
 
     params: FreeverbParams,
 
-    wet_gain_l: f32,
-    wet_gain_r: f32,
-    input_gain: f32,
-    dry: f32,
+    sr: f32,
+    feedback: SmoothedParam,
+    dampening: SmoothedParam,
+    wet_gain_l: SmoothedParam,
+    wet_gain_r: SmoothedParam,
+    input_gain: SmoothedParam,
+    dry: SmoothedParam,
     wet: f32,
 }
 
@@ -97,11 +101,14 @@ impl Freeverb {
             combs_r: [Comb::new(null_mut()); 8],
             allpasses_l: [AllPass::new(null_mut()); 4],
             allpasses_r: [AllPass::new(null_mut()); 4],
-            wet_gain_l: 0.0,
-            wet_gain_r: 0.0,
-            input_gain: 0.0,
+            sr: sr as f32,
+            feedback: SmoothedParam::new(0.0, SmoothingMode::OnePole),
+            dampening: SmoothedParam::new(0.0, SmoothingMode::OnePole),
+            wet_gain_l: SmoothedParam::new(0.0, SmoothingMode::Linear),
+            wet_gain_r: SmoothedParam::new(0.0, SmoothingMode::Linear),
+            input_gain: SmoothedParam::new(0.0, SmoothingMode::Linear),
+            dry: SmoothedParam::new(0.0, SmoothingMode::Linear),
             wet: 0.0,
-            dry: 0.0,
             params: FreeverbParams {
                 width: 0.0,
                 dampening: 0.0,
@@ -118,12 +125,20 @@ impl Freeverb {
         freeverb.set_dampening(0.5);
         freeverb.set_room_size(0.5);
         freeverb.set_frozen(false);
+        freeverb.set_smoothing_time(DEFAULT_SMOOTHING_MS);
 
         freeverb
     }
 
     pub fn tick(&mut self, input: (f32, f32)) -> (f32, f32) {
-        let input_mixed = (input.0 + input.1) * FIXED_GAIN * self.input_gain;
+        if self.feedback.is_settling() || self.dampening.is_settling() {
+            let (feedback, dampening) = (self.feedback.tick(), self.dampening.tick());
+            self.apply_combs(feedback, dampening);
+        }
+
+        let input_mixed = (input.0 + input.1) * FIXED_GAIN * self.input_gain.tick();
+        let (wet_gain_l, wet_gain_r) = (self.wet_gain_l.tick(), self.wet_gain_r.tick());
+        let dry = self.dry.tick();
 
         let mut out = (0.0, 0.0);
 
@@ -138,11 +153,26 @@ impl Freeverb {
         }
 
         (
-            out.0 * self.wet_gain_l + out.1 * self.wet_gain_r + input.0 * self.dry,
-            out.1 * self.wet_gain_l + out.0 * self.wet_gain_r + input.1 * self.dry,
+            out.0 * wet_gain_l + out.1 * wet_gain_r + input.0 * dry,
+            out.1 * wet_gain_l + out.0 * wet_gain_r + input.1 * dry,
         )
     }
 
+    /// Time in ms that continuous parameters take to reach a new value
+    pub fn set_smoothing_time(&mut self, ms: f32) {
+        let sr = self.sr;
+        for param in [
+            &mut self.feedback,
+            &mut self.dampening,
+            &mut self.wet_gain_l,
+            &mut self.wet_gain_r,
+            &mut self.input_gain,
+            &mut self.dry,
+        ] {
+            param.set_time_ms(ms, sr);
+        }
+    }
+
     fn align_buffers(&mut self, buffer: &mut [f32], tunings: [usize; 24]) {
         let mut offset = 0;
         // Give delay lines the approriate memory strips on buffer
@@ -183,19 +213,22 @@ impl Freeverb {
         self.update_wet_gains();
     }
 
+    /// Stereo width of the reverb, 1.0 keeps the channels apart and 0.0 blends them to mono
     pub fn set_width(&mut self, value: f32) {
         self.params.width = value;
         self.update_wet_gains();
     }
 
     fn update_wet_gains(&mut self) {
-        self.wet_gain_l = self.wet * (self.params.width / 2.0 + 0.5);
-        self.wet_gain_l = self.wet * ((1.0 - self.params.width) / 2.0);
+        self.wet_gain_l
+            .set_target(self.wet * (self.params.width / 2.0 + 0.5));
+        self.wet_gain_r
+            .set_target(self.wet * ((1.0 - self.params.width) / 2.0));
     }
 
     fn set_frozen(&mut self, frozen: bool) {
         self.params.frozen = frozen;
-        self.input_gain = if frozen { 0.0 } else { 1.0 };
+        self.input_gain.set_target(if frozen { 0.0 } else { 1.0 });
         self.update_combs();
     }
 
@@ -211,6 +244,12 @@ impl Freeverb {
             (self.params.room_size, self.params.dampening)
         };
 
+        self.feedback.set_target(feedback);
+        self.dampening.set_target(dampening);
+        self.apply_combs(self.feedback.value(), self.dampening.value());
+    }
+
+    fn apply_combs(&mut self, feedback: f32, dampening: f32) {
         for combs in core::iter::zip(self.combs_l.iter_mut(), self.combs_r.iter_mut()) {
             combs.0.set_feedback(feedback);
             combs.1.set_feedback(feedback);
@@ -221,7 +260,7 @@ impl Freeverb {
     }
 
     pub fn set_dry(&mut self, value: f32) {
-        self.dry = value;
+        self.dry.set_target(value);
     }
 
     pub fn set_all(&mut self, new: &FreeverbParams) {
@@ -231,8 +270,9 @@ impl Freeverb {
         self.params.frozen = new.frozen;
         self.params.mix = new.mix;
 
-        self.input_gain = if new.frozen { 0.0 } else { 1.0 };
-        self.dry = 1.0 - new.mix;
+        self.input_gain
+            .set_target(if new.frozen { 0.0 } else { 1.0 });
+        self.dry.set_target(1.0 - new.mix);
         self.wet = new.mix * SCALE_WET;
 
         self.update_combs();
@@ -256,4 +296,19 @@ mod tests {
         }
         assert_ne!(freeverb.tick((0.0, 0.0)), (0.0, 0.0));
     }
+
+    #[test]
+    fn zero_width_is_mono() {
+        let mut buffer = [0_f32; 48000];
+        let mut freeverb = super::Freeverb::new(48000, buffer.as_mut_slice());
+        freeverb.set_smoothing_time(0.0);
+        freeverb.set_width(0.0);
+        freeverb.set_dry(0.0);
+
+        freeverb.tick((1.0, -0.5));
+        for _ in 0..(1640 * 4) {
+            let out = freeverb.tick((0.0, 0.0));
+            assert!((out.0 - out.1).abs() < 1e-6);
+        }
+    }
 }
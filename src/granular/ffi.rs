@@ -0,0 +1,94 @@
+use super::Granular16;
+
+/// Initializes `Granular16` struct
+#[no_mangle]
+extern "C" fn granular_init(sr: f32) -> Granular16 {
+    Granular16::init(sr)
+}
+
+/// Initializes `Granular16` record buffer, which limits how far behind the input grains can start
+#[no_mangle]
+unsafe extern "C" fn granular_set_buffer(ptr: *mut Granular16, buffer: *mut f32, length: usize) {
+    ptr.as_mut()
+        .unwrap_unchecked()
+        .set_buffer(core::slice::from_raw_parts_mut(buffer, length));
+}
+
+/// Restarts the random grain placement, the same seed gives the same output
+#[no_mangle]
+unsafe extern "C" fn granular_set_seed(ptr: *mut Granular16, seed: u32) {
+    ptr.as_mut().unwrap_unchecked().set_seed(seed);
+}
+
+/// Returns next stereo samples. Raw pointer `stereo_samples` assumes to have exactly two elements!
+#[no_mangle]
+unsafe extern "C" fn granular_tick(ptr: *mut Granular16, stereo_samples: *mut f32) {
+    let samples = core::slice::from_raw_parts_mut(stereo_samples, 2);
+
+    (samples[0], samples[1]) = ptr
+        .as_mut()
+        .unwrap_unchecked()
+        .tick((samples[0], samples[1]));
+}
+
+/// Number of grains playing right now
+#[no_mangle]
+unsafe extern "C" fn granular_active_grains(ptr: *const Granular16) -> usize {
+    ptr.as_ref().unwrap_unchecked().active_grains()
+}
+
+/// Grain length between 10 and 500 ms, otherwise clamps
+#[no_mangle]
+unsafe extern "C" fn granular_set_grain_size(ptr: *mut Granular16, ms: f32) {
+    ptr.as_mut().unwrap_unchecked().set_grain_size(ms);
+}
+
+/// New grains per second between 0.5 and 200, otherwise clamps
+#[no_mangle]
+unsafe extern "C" fn granular_set_density(ptr: *mut Granular16, grains_per_second: f32) {
+    ptr.as_mut()
+        .unwrap_unchecked()
+        .set_density(grains_per_second);
+}
+
+/// Distance of new grains behind the input in ms
+#[no_mangle]
+unsafe extern "C" fn granular_set_position(ptr: *mut Granular16, ms: f32) {
+    ptr.as_mut().unwrap_unchecked().set_position(ms);
+}
+
+/// Only accepts values between 0.0 and 1.0, otherwise clamps
+#[no_mangle]
+unsafe extern "C" fn granular_set_jitter(ptr: *mut Granular16, val: f32) {
+    ptr.as_mut().unwrap_unchecked().set_jitter(val);
+}
+
+/// Transposition between -24 and 24 semitones, otherwise clamps
+#[no_mangle]
+unsafe extern "C" fn granular_set_pitch(ptr: *mut Granular16, semitones: f32) {
+    ptr.as_mut().unwrap_unchecked().set_pitch(semitones);
+}
+
+/// Only accepts values between 0.0 and 1.0, otherwise clamps
+#[no_mangle]
+unsafe extern "C" fn granular_set_spread(ptr: *mut Granular16, val: f32) {
+    ptr.as_mut().unwrap_unchecked().set_spread(val);
+}
+
+/// Only accepts values between 0.0 and 1.0, otherwise clamps
+#[no_mangle]
+unsafe extern "C" fn granular_set_feedback(ptr: *mut Granular16, val: f32) {
+    ptr.as_mut().unwrap_unchecked().set_feedback(val);
+}
+
+/// Dry/Wet mixing
+#[no_mangle]
+unsafe extern "C" fn granular_set_mix(ptr: *mut Granular16, val: f32) {
+    ptr.as_mut().unwrap_unchecked().set_mix(val);
+}
+
+/// Time in ms that feedback and mix changes take
+#[no_mangle]
+unsafe extern "C" fn granular_set_smoothing_time(ptr: *mut Granular16, ms: f32) {
+    ptr.as_mut().unwrap_unchecked().set_smoothing_time(ms);
+}
@@ -0,0 +1,337 @@
+mod ffi;
+
+use core::f32::consts::{FRAC_PI_4, PI};
+
+use libm::{cosf, exp2f, fabsf, sinf, sqrtf};
+
+use crate::noise::WhiteNoise;
+use crate::smoothed_param::{SmoothedParam, SmoothingMode, DEFAULT_SMOOTHING_MS};
+use crate::tools::{
+    memory_access::{from_slice_mut, null_mut},
+    DelayLine,
+};
+
+/// `Granular` with 16 grains, as used by the C API
+pub type Granular16 = Granular<16>;
+
+/// cbindgen:ignore
+const SHORTEST_GRAIN: f32 = 10.0; // ms
+
+/// cbindgen:ignore
+const LONGEST_GRAIN: f32 = 500.0; // ms
+
+/// cbindgen:ignore
+const MIN_DENSITY: f32 = 0.5; // grains per second
+
+/// cbindgen:ignore
+const MAX_DENSITY: f32 = 200.0; // grains per second
+
+/// cbindgen:ignore
+const MAX_PITCH: f32 = 24.0; // semitones
+
+/// cbindgen:ignore
+const MAX_FEEDBACK: f32 = 0.95;
+
+/// cbindgen:ignore
+const DEFAULT_SEED: u32 = 0x4752_414E;
+
+#[derive(Clone, Copy)]
+#[repr(C)]
+struct Grain {
+    active: bool,
+    age: f32,
+    length: f32,
+    delay: f32,
+    /// Change of the delay per sample, negative while reading faster than the input
+    drift: f32,
+    gain_l: f32,
+    gain_r: f32,
+}
+
+impl Grain {
+    const IDLE: Grain = Grain {
+        active: false,
+        age: 0.0,
+        length: 0.0,
+        delay: 0.0,
+        drift: 0.0,
+        gain_l: 0.0,
+        gain_r: 0.0,
+    };
+
+    #[inline]
+    fn tick(&mut self, delay_line: &DelayLine) -> (f32, f32) {
+        // Hann window
+        let window = 0.5 - 0.5 * cosf(2.0 * PI * self.age / self.length);
+        let sample = window * delay_line.read_lerp_wrapped_at(-self.delay);
+
+        self.age += 1.0;
+        self.delay += self.drift;
+        self.active = self.age < self.length;
+
+        (self.gain_l * sample, self.gain_r * sample)
+    }
+}
+
+/// Granular delay, records into a ring buffer and plays back overlapping windowed grains
+///
+/// Grains start `position` behind the input, randomly offset by the jitter and panned by the spread.
+/// New grains are skipped while all `GRAINS` are playing. The random choices are deterministic for a seed.
+#[repr(C)]
+pub struct Granular<const GRAINS: usize> {
+    delay_line: DelayLine,
+    grains: [Grain; GRAINS],
+    noise: WhiteNoise,
+
+    sr: f32,
+    smoothing_ms: f32,
+    spawn_countdown: f32,
+    grain_size: f32,
+    density: f32,
+    position: f32,
+    jitter: f32,
+    pitch_ratio: f32,
+    spread: f32,
+    feedback: SmoothedParam,
+    mix: SmoothedParam,
+}
+
+impl<const GRAINS: usize> Granular<GRAINS> {
+    pub fn init(sr: f32) -> Granular<GRAINS> {
+        let mut granular = Granular {
+            delay_line: DelayLine::new(null_mut()),
+            grains: [Grain::IDLE; GRAINS],
+            noise: WhiteNoise::new(DEFAULT_SEED),
+
+            sr,
+            smoothing_ms: DEFAULT_SMOOTHING_MS,
+            spawn_countdown: 0.0,
+            grain_size: 0.1 * sr,
+            density: 20.0,
+            position: 0.2 * sr,
+            jitter: 0.0,
+            pitch_ratio: 1.0,
+            spread: 0.5,
+            feedback: SmoothedParam::new(0.0, SmoothingMode::Linear),
+            mix: SmoothedParam::new(0.5, SmoothingMode::Linear),
+        };
+
+        granular.set_smoothing_time(DEFAULT_SMOOTHING_MS);
+        granular
+    }
+
+    /// Limits how far behind the input grains can start, e.g. `length = 72000` allows 1.5 s for `sr = 48000`
+    pub fn set_buffer(&mut self, buffer: &mut [f32]) {
+        self.delay_line.change_buffer(from_slice_mut(buffer));
+    }
+
+    /// Restarts the random grain placement, the same seed gives the same output
+    pub fn set_seed(&mut self, seed: u32) {
+        self.noise = WhiteNoise::new(seed);
+    }
+
+    /// Records the summed input and returns the stereo grains mixed with the dry signal
+    pub fn tick(&mut self, input: (f32, f32)) -> (f32, f32) {
+        self.spawn_countdown -= 1.0;
+        if self.spawn_countdown <= 0.0 {
+            self.spawn_countdown += self.sr / self.density;
+            self.spawn();
+        }
+
+        let mut wet = (0.0, 0.0);
+        for grain in self.grains.iter_mut().filter(|grain| grain.active) {
+            let (l, r) = grain.tick(&self.delay_line);
+            wet = (wet.0 + l, wet.1 + r);
+        }
+
+        // overlapping grains are uncorrelated, so they add up in power
+        let overlap = (self.density * self.grain_size / self.sr).max(1.0);
+        let norm = 1.0 / sqrtf(overlap);
+        wet = (norm * wet.0, norm * wet.1);
+
+        let feedback = self.feedback.tick();
+        self.delay_line
+            .write_and_advance(0.5 * (input.0 + input.1) + feedback * 0.5 * (wet.0 + wet.1));
+
+        let mix = self.mix.tick();
+        (
+            (1.0 - mix) * input.0 + mix * wet.0,
+            (1.0 - mix) * input.1 + mix * wet.1,
+        )
+    }
+
+    /// Number of grains playing right now
+    pub fn active_grains(&self) -> usize {
+        self.grains.iter().filter(|grain| grain.active).count()
+    }
+
+    /// Grain length between 10 and 500 ms, otherwise clamps
+    pub fn set_grain_size(&mut self, ms: f32) {
+        self.grain_size = ms.clamp(SHORTEST_GRAIN, LONGEST_GRAIN) * 0.001 * self.sr;
+    }
+
+    /// New grains per second between 0.5 and 200, otherwise clamps
+    pub fn set_density(&mut self, grains_per_second: f32) {
+        self.density = grains_per_second.clamp(MIN_DENSITY, MAX_DENSITY);
+    }
+
+    /// Distance of new grains behind the input in ms, limited by the buffer
+    pub fn set_position(&mut self, ms: f32) {
+        self.position = ms.max(0.0) * 0.001 * self.sr;
+    }
+
+    /// Random offset of the position, 1.0 scatters up to one position in each direction. Only accepts values between 0.0 and 1.0, otherwise clamps
+    pub fn set_jitter(&mut self, val: f32) {
+        self.jitter = val.clamp(0.0, 1.0);
+    }
+
+    /// Transposition of the grains between -24 and 24 semitones, otherwise clamps
+    pub fn set_pitch(&mut self, semitones: f32) {
+        self.pitch_ratio = exp2f(semitones.clamp(-MAX_PITCH, MAX_PITCH) / 12.0);
+    }
+
+    /// Random panning of the grains. Only accepts values between 0.0 and 1.0, otherwise clamps
+    pub fn set_spread(&mut self, val: f32) {
+        self.spread = val.clamp(0.0, 1.0);
+    }
+
+    /// Grains fed back into the buffer. Only accepts values between 0.0 and 1.0, otherwise clamps
+    pub fn set_feedback(&mut self, val: f32) {
+        self.feedback.set_target(val.clamp(0.0, 1.0) * MAX_FEEDBACK);
+    }
+
+    /// Dry/Wet mixing. Only accepts values between 0.0 and 1.0, otherwise clamps
+    pub fn set_mix(&mut self, val: f32) {
+        self.mix.set_target(val.clamp(0.0, 1.0));
+    }
+
+    /// Time in ms that feedback and mix changes take
+    pub fn set_smoothing_time(&mut self, ms: f32) {
+        self.smoothing_ms = ms;
+        self.feedback.set_time_ms(ms, self.sr);
+        self.mix.set_time_ms(ms, self.sr);
+    }
+
+    /// Rescales the time parameters, already playing grains finish unchanged
+    pub fn update_sr(&mut self, sr: f32) {
+        let scale = sr / self.sr;
+        self.grain_size *= scale;
+        self.position *= scale;
+        self.spawn_countdown *= scale;
+        self.sr = sr;
+        self.set_smoothing_time(self.smoothing_ms);
+    }
+
+    fn spawn(&mut self) {
+        let Some(grain) = self.grains.iter_mut().find(|grain| !grain.active) else {
+            return;
+        };
+
+        let drift = 1.0 - self.pitch_ratio;
+        let room = self.delay_line.len() as f32 - 3.0;
+        if room < 0.0 {
+            return;
+        }
+
+        // shorten the grain until its drift fits into the buffer
+        let length = if drift == 0.0 {
+            self.grain_size
+        } else {
+            self.grain_size.min(room / fabsf(drift))
+        };
+
+        // the read head must neither pass the write head nor fall off the end of the buffer,
+        // rounding can leave a grain that just fits a hair short of room
+        let shortest = 1.0 + (-drift * length).max(0.0);
+        let longest = self.delay_line.len().saturating_sub(2) as f32 - (drift * length).max(0.0);
+        let longest = longest.max(shortest);
+
+        let delay = self.position * (1.0 + self.jitter * self.noise.tick());
+        let angle = (self.spread * self.noise.tick() + 1.0) * FRAC_PI_4;
+
+        *grain = Grain {
+            active: true,
+            age: 0.0,
+            length,
+            delay: delay.clamp(shortest, longest),
+            drift,
+            gain_l: cosf(angle),
+            gain_r: sinf(angle),
+        };
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    const SR: f32 = 48000.0;
+
+    fn render<const GRAINS: usize>(granular: &mut Granular<GRAINS>) -> [(f32, f32); 4800] {
+        let mut noise = WhiteNoise::new(1);
+        core::array::from_fn(|_| {
+            let x = noise.tick();
+            granular.tick((x, x))
+        })
+    }
+
+    #[test]
+    fn deterministic_for_a_seed() {
+        let mut buffer = [[0.0; 12000]; 3];
+        let mut granulars: [Granular<8>; 3] = core::array::from_fn(|_| Granular::init(SR));
+        for ((granular, buffer), seed) in granulars.iter_mut().zip(buffer.iter_mut()).zip([7, 7, 8])
+        {
+            granular.set_buffer(buffer);
+            granular.set_seed(seed);
+            granular.set_position(50.0);
+            granular.set_jitter(1.0);
+            granular.set_spread(1.0);
+            granular.set_mix(1.0);
+        }
+
+        let [a, b, c] = granulars.each_mut().map(render);
+        assert_eq!(a, b);
+        assert_ne!(a, c);
+    }
+
+    #[test]
+    fn grain_count_is_fixed() {
+        let mut buffer = [0.0; 12000];
+        let mut granular: Granular<4> = Granular::init(SR);
+        granular.set_buffer(&mut buffer);
+        granular.set_density(MAX_DENSITY);
+        granular.set_grain_size(LONGEST_GRAIN);
+        granular.set_position(100.0);
+
+        render(&mut granular);
+        assert_eq!(granular.active_grains(), 4);
+    }
+
+    #[test]
+    fn long_grains_shorten_when_pitched_up() {
+        let mut buffer = [0.0; 12000];
+        let mut granular: Granular<4> = Granular::init(SR);
+        granular.set_buffer(&mut buffer);
+        granular.set_grain_size(LONGEST_GRAIN);
+        granular.set_pitch(MAX_PITCH);
+        granular.set_mix(1.0);
+
+        let out = render(&mut granular);
+        assert!(granular.active_grains() > 0);
+        assert!(out.iter().any(|&(l, r)| l != 0.0 || r != 0.0));
+    }
+
+    #[test]
+    fn pitched_down_grains_fit_a_short_buffer() {
+        for semitones in [-23.7, -19.3, -12.1, -7.9, -0.3] {
+            let mut buffer = [0.0; 100];
+            let mut granular: Granular<4> = Granular::init(SR);
+            granular.set_buffer(&mut buffer);
+            granular.set_density(MAX_DENSITY);
+            granular.set_grain_size(LONGEST_GRAIN);
+            granular.set_pitch(semitones);
+
+            render(&mut granular);
+        }
+    }
+}
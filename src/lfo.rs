@@ -0,0 +1,114 @@
+use libm::floorf;
+
+use crate::oscillator::waveform_at;
+use crate::tools::Waveform;
+
+/// Frequency of one period every `beats` beats at `bpm`, `None` for invalid tempos
+pub fn tempo_sync_freq(bpm: f32, beats: f32) -> Option<f32> {
+    if bpm > 0.0 && beats > 0.0 {
+        Some(bpm / (60.0 * beats))
+    } else {
+        None
+    }
+}
+
+/// Bipolar low frequency oscillator for modulation effects
+///
+/// Several taps with different phase offsets can be read per sample, e.g. for stereo spread.
+#[repr(C)]
+pub struct Lfo {
+    phase: f32,
+    freq: f32,
+    increment: f32,
+    sr: f32,
+    wave: Waveform,
+}
+
+impl Lfo {
+    pub fn new(freq: f32, sr: f32) -> Lfo {
+        Lfo {
+            phase: 0.0,
+            freq,
+            increment: freq / sr,
+            sr,
+            wave: Waveform::Sine,
+        }
+    }
+
+    /// Current value between -1.0 and 1.0, then advances by one sample
+    #[inline(always)]
+    pub fn tick(&mut self) -> f32 {
+        let value = self.value_at(0.0);
+        self.advance(1);
+        value
+    }
+
+    /// Current value with the phase shifted by `offset` periods, without advancing
+    #[inline(always)]
+    pub fn value_at(&self, offset: f32) -> f32 {
+        let phase = self.phase + offset;
+        waveform_at(&self.wave, phase - floorf(phase))
+    }
+
+    /// Advances by `samples`, e.g. once per block for control rate modulation
+    #[inline(always)]
+    pub fn advance(&mut self, samples: usize) {
+        self.phase += self.increment * samples as f32;
+        self.phase -= floorf(self.phase);
+    }
+
+    /// Frequency in Hz
+    pub fn set_freq(&mut self, freq: f32) {
+        self.freq = freq;
+        self.increment = freq / self.sr;
+    }
+
+    /// One period every `beats` beats at `bpm`, e.g. 0.25 for sixteenth notes in 4/4
+    pub fn set_tempo_sync(&mut self, bpm: f32, beats: f32) {
+        if let Some(freq) = tempo_sync_freq(bpm, beats) {
+            self.set_freq(freq);
+        }
+    }
+
+    pub fn set_sr(&mut self, sr: f32) {
+        self.sr = sr;
+        self.set_freq(self.freq);
+    }
+
+    pub fn set_waveform(&mut self, wave: Waveform) {
+        self.wave = wave;
+    }
+
+    /// Restarts at `phase` periods, between 0.0 and 1.0
+    pub fn reset_phase(&mut self, phase: f32) {
+        self.phase = phase - floorf(phase);
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn offsets_read_ahead() {
+        let mut lfo = Lfo::new(1.0, 4.0);
+
+        let ahead = lfo.value_at(0.25);
+        assert_eq!(lfo.tick(), 0.0);
+        assert_eq!(lfo.value_at(0.0), ahead);
+
+        lfo.advance(3);
+        assert!(lfo.value_at(0.0).abs() < 1e-6);
+    }
+
+    #[test]
+    fn tempo_sync() {
+        let mut lfo = Lfo::new(1.0, 48000.0);
+        lfo.set_tempo_sync(120.0, 0.25);
+        assert_eq!(lfo.freq, 8.0);
+
+        // invalid tempos keep the last frequency
+        lfo.set_tempo_sync(0.0, 1.0);
+        assert_eq!(lfo.freq, 8.0);
+    }
+}
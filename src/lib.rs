@@ -4,17 +4,75 @@ use embedded_audio_tools as tools;
 
 mod ffi;
 
+mod analog_filter;
+mod auto_pan;
+mod chorus;
+mod distortion;
+mod drum_voice;
+mod dynamics;
+mod flanger;
 mod freeverb;
+mod granular;
+mod lfo;
+mod looper;
 mod multi_filter;
+mod noise;
+mod oscillator;
+mod oversampler;
+mod parametric_eq;
+mod phaser;
+mod pitch_shifter;
+mod ring_modulator;
 mod simple_delay;
+mod smoothed_param;
+mod spectral;
+mod synth_clap;
+mod synth_hat;
 pub(crate) mod synth_kick;
+mod synth_snare;
+mod synth_tom;
+mod tremolo;
+mod waveshaper;
 
+pub use crate::analog_filter::{LadderFilter, SallenKeyFilter};
+pub use crate::auto_pan::AutoPan;
+pub use crate::chorus::Chorus;
+pub use crate::distortion::Distortion;
+pub use crate::drum_voice::{
+    trigger_in_choke_group, DrumKind, DrumVoice, DrumVoicePtr, ScheduledTrigger, TriggerSchedule,
+};
+pub use crate::dynamics::{Compressor, DetectorMode, Expander, Limiter, NoiseGate};
+pub use crate::flanger::Flanger;
 pub use crate::freeverb::Freeverb;
-pub use crate::multi_filter::MultiFilter;
+pub use crate::granular::{Granular, Granular16};
+pub use crate::lfo::Lfo;
+pub use crate::looper::{Looper, LooperEvent, LooperState};
+pub use crate::multi_filter::{
+    bandwidth_to_q, db_to_gain, gain_to_db, log_spaced_freq, q_to_bandwidth, shelf_slope_to_q,
+    MultiFilter,
+};
+pub use crate::noise::WhiteNoise;
+pub use crate::oscillator::{waveform_at, MorphOscillator};
+pub use crate::oversampler::Oversampler;
+pub use crate::parametric_eq::{ParametricEq, ParametricEq4};
+pub use crate::phaser::Phaser;
+pub use crate::pitch_shifter::PitchShifter;
+pub use crate::ring_modulator::RingModulator;
 pub use crate::simple_delay::SimpleDelay;
-pub use crate::synth_kick::SynthKick;
+pub use crate::smoothed_param::{SmoothedParam, SmoothingMode};
+pub use crate::spectral::{
+    fill_twiddles, irfft, rfft, SpectralGate, SpectrumAnalyzer, Stft, Window,
+};
+pub use crate::synth_clap::SynthClap;
+pub use crate::synth_hat::SynthHat;
+pub use crate::synth_kick::{RetriggerPolicy, SweepUnit, SynthKick, VelocityCurve};
+pub use crate::synth_snare::SynthSnare;
+pub use crate::synth_tom::SynthTom;
+pub use crate::tremolo::Tremolo;
+pub use crate::waveshaper::{Bitcrusher, Waveshaper};
 
 pub use tools::float::DSPUtility;
+pub use tools::Waveform;
 
 #[cfg(feature = "static")] // embedded targets
 #[panic_handler]
@@ -0,0 +1,61 @@
+use super::{LooperEvent, LooperState};
+use crate::Looper;
+
+/// Initializes `Looper` struct
+#[no_mangle]
+extern "C" fn looper_init(sr: f32) -> Looper {
+    Looper::init(sr)
+}
+
+/// Initializes `Looper` buffers, the longest loop is `length / 2` samples. Clears the loop.
+#[no_mangle]
+unsafe extern "C" fn looper_set_buffer(ptr: *mut Looper, buffer: *mut f32, length: usize) {
+    ptr.as_mut()
+        .unwrap_unchecked()
+        .set_buffer(core::slice::from_raw_parts_mut(buffer, length));
+}
+
+/// Returns the input with the loop on top
+#[no_mangle]
+unsafe extern "C" fn looper_tick(ptr: *mut Looper, sample: f32) -> f32 {
+    ptr.as_mut().unwrap_unchecked().tick(sample)
+}
+
+/// Advances the state machine, e.g. on a footswitch press
+#[no_mangle]
+unsafe extern "C" fn looper_handle_event(ptr: *mut Looper, event: LooperEvent) {
+    ptr.as_mut().unwrap_unchecked().handle_event(event);
+}
+
+#[no_mangle]
+unsafe extern "C" fn looper_get_state(ptr: *const Looper) -> LooperState {
+    ptr.as_ref().unwrap_unchecked().state()
+}
+
+/// Length of the recorded loop in samples, 0 while empty or recording
+#[no_mangle]
+unsafe extern "C" fn looper_loop_length(ptr: *const Looper) -> usize {
+    ptr.as_ref().unwrap_unchecked().loop_length()
+}
+
+#[no_mangle]
+unsafe extern "C" fn looper_set_reverse(ptr: *mut Looper, reverse: bool) {
+    ptr.as_mut().unwrap_unchecked().set_reverse(reverse);
+}
+
+#[no_mangle]
+unsafe extern "C" fn looper_set_half_speed(ptr: *mut Looper, half_speed: bool) {
+    ptr.as_mut().unwrap_unchecked().set_half_speed(half_speed);
+}
+
+/// Only accepts values between 0.0 and 1.0, otherwise clamps
+#[no_mangle]
+unsafe extern "C" fn looper_set_level(ptr: *mut Looper, val: f32) {
+    ptr.as_mut().unwrap_unchecked().set_level(val);
+}
+
+/// Time in ms of the overdub fades and level changes
+#[no_mangle]
+unsafe extern "C" fn looper_set_smoothing_time(ptr: *mut Looper, ms: f32) {
+    ptr.as_mut().unwrap_unchecked().set_smoothing_time(ms);
+}
@@ -0,0 +1,428 @@
+mod ffi;
+
+use core::ptr::null_mut;
+
+use crate::smoothed_param::{SmoothedParam, SmoothingMode, DEFAULT_SMOOTHING_MS};
+
+/// Crossfade between the end and the start of a freshly recorded loop
+/// cbindgen:ignore
+const LOOP_FADE: f32 = 0.010; // s
+
+/// Samples the undo copy catches up per tick, faster than any play speed
+/// cbindgen:ignore
+const SYNC_PER_TICK: usize = 2;
+
+#[derive(Clone, Copy, PartialEq, Debug)]
+#[repr(C)]
+pub enum LooperState {
+    Empty,
+    Recording,
+    Playing,
+    Overdubbing,
+    Stopped,
+}
+
+/// Footswitch events driving the `Looper` state machine
+#[derive(Clone, Copy, PartialEq, Debug)]
+#[repr(C)]
+pub enum LooperEvent {
+    /// Record, then cycles between play and overdub. Restarts a stopped loop.
+    RecordPlay,
+    /// Stops playback, closes a running recording first
+    Stop,
+    /// Erases the loop
+    Clear,
+    /// Reverts the last overdub, pressed again it redoes it
+    Undo,
+    /// Toggles backwards playback
+    Reverse,
+    /// Toggles playback at half speed, an octave down
+    HalfSpeed,
+}
+
+/// Looper with overdub, one level of undo, reverse and half speed
+///
+/// The caller-provided buffer is split into the loop and its undo copy. Undo swaps both halves, so
+/// the undo copy has to match the loop before an overdub starts. It catches up a few samples per
+/// tick ahead of the play head, which avoids copying the whole loop at once.
+/// Speed changes during an overdub take effect once it ends.
+#[repr(C)]
+pub struct Looper {
+    current: *mut f32,
+    backup: *mut f32,
+    capacity: usize,
+
+    sr: f32,
+    smoothing_ms: f32,
+    state: LooperState,
+    length: usize,
+    position: f32,
+    speed: f32,
+    reverse: bool,
+    half_speed: bool,
+
+    fade_length: usize,
+    fade_left: usize,
+
+    has_undo: bool,
+    sync_cursor: usize,
+    sync_left: usize,
+    sync_backwards: bool,
+
+    dub_gain: SmoothedParam,
+    level: SmoothedParam,
+}
+
+impl Looper {
+    pub fn init(sr: f32) -> Looper {
+        let mut looper = Looper {
+            current: null_mut(),
+            backup: null_mut(),
+            capacity: 0,
+
+            sr,
+            smoothing_ms: DEFAULT_SMOOTHING_MS,
+            state: LooperState::Empty,
+            length: 0,
+            position: 0.0,
+            speed: 1.0,
+            reverse: false,
+            half_speed: false,
+
+            fade_length: 0,
+            fade_left: 0,
+
+            has_undo: false,
+            sync_cursor: 0,
+            sync_left: 0,
+            sync_backwards: false,
+
+            dub_gain: SmoothedParam::new(0.0, SmoothingMode::Linear),
+            level: SmoothedParam::new(1.0, SmoothingMode::Linear),
+        };
+
+        looper.set_smoothing_time(DEFAULT_SMOOTHING_MS);
+        looper
+    }
+
+    /// Split into loop and undo copy, the longest loop is `length / 2` samples. Clears the loop.
+    pub fn set_buffer(&mut self, buffer: &mut [f32]) {
+        let (current, backup) = buffer.split_at_mut(buffer.len() / 2);
+        self.current = current.as_mut_ptr();
+        self.backup = backup.as_mut_ptr();
+        self.capacity = current.len();
+        self.clear();
+    }
+
+    /// Returns the input with the loop on top
+    pub fn tick(&mut self, input: f32) -> f32 {
+        self.advance_sync();
+        let dub_gain = self.dub_gain.tick();
+        let level = self.level.tick();
+
+        match self.state {
+            LooperState::Empty => input,
+            LooperState::Recording => {
+                let index = self.length;
+                let (current, backup) = self.buffers();
+                current[index] = input;
+                backup[index] = input;
+
+                self.length += 1;
+                if self.length == self.capacity {
+                    self.close_recording(LooperState::Playing);
+                }
+                input
+            }
+            LooperState::Stopped => {
+                self.apply_loop_fade(input);
+                input
+            }
+            LooperState::Playing | LooperState::Overdubbing => {
+                self.apply_loop_fade(input);
+
+                let index = self.position as usize;
+                let next = (index + 1) % self.length;
+                let frac = self.position - index as f32;
+                let speed = self.speed;
+                let current = self.buffers().0;
+                let output = current[index] + frac * (current[next] - current[index]);
+
+                // slower speeds visit every sample more often, so each visit adds less
+                if dub_gain > 0.0 {
+                    current[index] += dub_gain * speed.abs() * input;
+                }
+
+                self.position += speed;
+                if self.position >= self.length as f32 {
+                    self.position -= self.length as f32;
+                } else if self.position < 0.0 {
+                    self.position += self.length as f32;
+                }
+
+                input + level * output
+            }
+        }
+    }
+
+    pub fn handle_event(&mut self, event: LooperEvent) {
+        match event {
+            LooperEvent::RecordPlay => match self.state {
+                LooperState::Empty if self.capacity > 0 => {
+                    self.state = LooperState::Recording;
+                }
+                LooperState::Empty => {}
+                LooperState::Recording => self.close_recording(LooperState::Playing),
+                LooperState::Playing => self.start_overdub(),
+                LooperState::Overdubbing => self.stop_overdub(LooperState::Playing),
+                LooperState::Stopped => {
+                    self.position = 0.0;
+                    self.state = LooperState::Playing;
+                }
+            },
+            LooperEvent::Stop => match self.state {
+                LooperState::Recording => self.close_recording(LooperState::Stopped),
+                LooperState::Playing => self.state = LooperState::Stopped,
+                LooperState::Overdubbing => self.stop_overdub(LooperState::Stopped),
+                LooperState::Empty | LooperState::Stopped => {}
+            },
+            LooperEvent::Clear => self.clear(),
+            LooperEvent::Undo => self.undo(),
+            LooperEvent::Reverse => self.set_reverse(!self.reverse),
+            LooperEvent::HalfSpeed => self.set_half_speed(!self.half_speed),
+        }
+    }
+
+    pub fn state(&self) -> LooperState {
+        self.state
+    }
+
+    /// Length of the recorded loop in samples, 0 while empty or recording
+    pub fn loop_length(&self) -> usize {
+        match self.state {
+            LooperState::Empty | LooperState::Recording => 0,
+            _ => self.length,
+        }
+    }
+
+    pub fn set_reverse(&mut self, reverse: bool) {
+        self.reverse = reverse;
+        self.update_speed();
+    }
+
+    pub fn set_half_speed(&mut self, half_speed: bool) {
+        self.half_speed = half_speed;
+        self.update_speed();
+    }
+
+    /// Level of the loop. Only accepts values between 0.0 and 1.0, otherwise clamps
+    pub fn set_level(&mut self, val: f32) {
+        self.level.set_target(val.clamp(0.0, 1.0));
+    }
+
+    /// Time in ms of the overdub fades and level changes
+    pub fn set_smoothing_time(&mut self, ms: f32) {
+        self.smoothing_ms = ms;
+        self.dub_gain.set_time_ms(ms, self.sr);
+        self.level.set_time_ms(ms, self.sr);
+    }
+
+    pub fn update_sr(&mut self, sr: f32) {
+        self.sr = sr;
+        self.set_smoothing_time(self.smoothing_ms);
+    }
+
+    fn buffers(&mut self) -> (&mut [f32], &mut [f32]) {
+        if self.capacity == 0 {
+            return (&mut [], &mut []);
+        }
+
+        // both halves of the caller's buffer, which never overlap
+        unsafe {
+            (
+                core::slice::from_raw_parts_mut(self.current, self.capacity),
+                core::slice::from_raw_parts_mut(self.backup, self.capacity),
+            )
+        }
+    }
+
+    fn clear(&mut self) {
+        self.state = LooperState::Empty;
+        self.length = 0;
+        self.position = 0.0;
+        self.fade_left = 0;
+        self.has_undo = false;
+        self.sync_left = 0;
+        self.dub_gain.set_immediate(0.0);
+    }
+
+    fn close_recording(&mut self, next: LooperState) {
+        if self.length == 0 {
+            self.state = LooperState::Empty;
+            return;
+        }
+
+        self.fade_length = ((LOOP_FADE * self.sr) as usize).min(self.length / 2);
+        self.fade_left = self.fade_length;
+        self.position = 0.0;
+        self.state = next;
+        self.update_speed();
+    }
+
+    /// Blends the input that follows the recording into the loop start, so the loop point is seamless
+    fn apply_loop_fade(&mut self, input: f32) {
+        if self.fade_left == 0 {
+            return;
+        }
+
+        let index = self.fade_length - self.fade_left;
+        let fade_in = index as f32 / self.fade_length as f32;
+        let (current, backup) = self.buffers();
+        current[index] = fade_in * current[index] + (1.0 - fade_in) * input;
+        backup[index] = current[index];
+
+        self.fade_left -= 1;
+    }
+
+    fn start_overdub(&mut self) {
+        // the previous undo copy is replaced by the loop as it is now
+        if self.has_undo {
+            self.sync_cursor = self.position as usize;
+            self.sync_left = self.length;
+            self.sync_backwards = self.speed < 0.0;
+        }
+
+        self.has_undo = true;
+        self.dub_gain.set_target(1.0);
+        self.state = LooperState::Overdubbing;
+    }
+
+    fn stop_overdub(&mut self, next: LooperState) {
+        self.dub_gain.set_target(0.0);
+        self.state = next;
+        self.update_speed();
+    }
+
+    fn undo(&mut self) {
+        match self.state {
+            LooperState::Empty | LooperState::Recording => return,
+            LooperState::Overdubbing => self.stop_overdub(LooperState::Playing),
+            LooperState::Playing | LooperState::Stopped => {}
+        }
+
+        // rare, only when undo follows right after a short overdub
+        while self.sync_left > 0 {
+            self.advance_sync();
+        }
+
+        if self.has_undo {
+            self.dub_gain.set_immediate(0.0);
+            core::mem::swap(&mut self.current, &mut self.backup);
+        }
+    }
+
+    /// Copies the loop into the undo copy ahead of the play head
+    fn advance_sync(&mut self) {
+        let steps = self.sync_left.min(SYNC_PER_TICK);
+        let length = self.length;
+        let (mut cursor, backwards) = (self.sync_cursor, self.sync_backwards);
+        let (current, backup) = self.buffers();
+
+        for _ in 0..steps {
+            backup[cursor] = current[cursor];
+            cursor = if backwards {
+                (cursor + length - 1) % length
+            } else {
+                (cursor + 1) % length
+            };
+        }
+
+        self.sync_cursor = cursor;
+        self.sync_left -= steps;
+    }
+
+    fn update_speed(&mut self) {
+        if self.state == LooperState::Overdubbing {
+            return;
+        }
+
+        let speed = if self.half_speed { 0.5 } else { 1.0 };
+        self.speed = if self.reverse { -speed } else { speed };
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    const SR: f32 = 1000.0;
+
+    /// Records `length` samples of a ramp and starts playing
+    fn recorded(buffer: &mut [f32], length: usize) -> Looper {
+        let mut looper = Looper::init(SR);
+        looper.set_buffer(buffer);
+        looper.set_smoothing_time(0.0);
+
+        looper.handle_event(LooperEvent::RecordPlay);
+        for i in 0..length {
+            looper.tick(i as f32);
+        }
+        looper.handle_event(LooperEvent::RecordPlay);
+        looper
+    }
+
+    #[test]
+    fn records_and_plays() {
+        let mut buffer = [0.0; 256];
+        let mut looper = recorded(&mut buffer, 100);
+        assert_eq!(looper.state(), LooperState::Playing);
+        assert_eq!(looper.loop_length(), 100);
+
+        // the first 10 ms fade from the continuation of the recording into the loop
+        let first: [f32; 100] = core::array::from_fn(|_| looper.tick(100.0) - 100.0);
+        assert_eq!(first[0], 100.0);
+        assert_eq!(
+            &first[10..],
+            core::array::from_fn::<f32, 90, _>(|i| (i + 10) as f32)
+        );
+
+        looper.handle_event(LooperEvent::Reverse);
+        looper.tick(0.0);
+        assert_eq!(looper.tick(0.0), 99.0);
+
+        looper.handle_event(LooperEvent::Stop);
+        assert_eq!(looper.tick(0.5), 0.5);
+    }
+
+    #[test]
+    fn overdub_undo_redo() {
+        let mut buffer = [0.0; 256];
+        let mut looper = recorded(&mut buffer, 100);
+        for _ in 0..100 {
+            looper.tick(0.0);
+        }
+
+        let play =
+            |looper: &mut Looper| -> [f32; 100] { core::array::from_fn(|_| looper.tick(0.0)) };
+        let original = play(&mut looper);
+
+        // two layers, only the last one gets undone
+        for layer in [1.0, 1000.0] {
+            looper.handle_event(LooperEvent::RecordPlay);
+            assert_eq!(looper.state(), LooperState::Overdubbing);
+            for _ in 0..100 {
+                looper.tick(layer);
+            }
+            looper.handle_event(LooperEvent::RecordPlay);
+        }
+
+        let dubbed = play(&mut looper);
+        assert_eq!(dubbed[50], original[50] + 1001.0);
+
+        looper.handle_event(LooperEvent::Undo);
+        assert_eq!(play(&mut looper)[50], original[50] + 1.0);
+
+        looper.handle_event(LooperEvent::Undo);
+        assert_eq!(play(&mut looper), dubbed);
+    }
+}
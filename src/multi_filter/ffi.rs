@@ -1,4 +1,4 @@
-use crate::multi_filter::ButterworthType;
+use crate::multi_filter::{log_spaced_freq, ButterworthType};
 use crate::MultiFilter;
 
 /// Initializes `MultiFilter` struct.
@@ -13,7 +13,7 @@ unsafe extern "C" fn multifilter_tick(ptr: *mut MultiFilter, sample: f32) -> f32
     ptr.as_mut().unwrap_unchecked().next(sample)
 }
 
-/// Sample rate depending calculations should be performed earlier!
+/// Sample rate depending calculations should be performed earlier! `gain` is linear.
 #[no_mangle]
 unsafe extern "C" fn multifilter_set_all_params(
     ptr: *mut MultiFilter,
@@ -26,3 +26,66 @@ unsafe extern "C" fn multifilter_set_all_params(
         .unwrap_unchecked()
         .set_all(filter, freq, q, gain);
 }
+
+/// Gain in dB, only used by bell and shelf filters
+#[no_mangle]
+unsafe extern "C" fn multifilter_set_gain_db(ptr: *mut MultiFilter, db: f32) {
+    ptr.as_mut().unwrap_unchecked().set_gain_db(db);
+}
+
+/// Bandwidth in octaves, replaces Q
+#[no_mangle]
+unsafe extern "C" fn multifilter_set_bandwidth(ptr: *mut MultiFilter, octaves: f32) {
+    ptr.as_mut().unwrap_unchecked().set_bandwidth(octaves);
+}
+
+/// Shelf slope between 0.01 and 1.0, replaces Q. Otherwise clamps.
+#[no_mangle]
+unsafe extern "C" fn multifilter_set_shelf_slope(ptr: *mut MultiFilter, slope: f32) {
+    ptr.as_mut().unwrap_unchecked().set_shelf_slope(slope);
+}
+
+/// Time in ms that cutoff, Q and gain take to reach a new value
+#[no_mangle]
+unsafe extern "C" fn multifilter_set_smoothing_time(ptr: *mut MultiFilter, ms: f32) {
+    ptr.as_mut().unwrap_unchecked().set_smoothing_time(ms);
+}
+
+/// Linear magnitude response at `freq` in Hz
+#[no_mangle]
+unsafe extern "C" fn multifilter_magnitude_at(ptr: *const MultiFilter, freq: f32) -> f32 {
+    ptr.as_ref().unwrap_unchecked().magnitude_at(freq)
+}
+
+/// Phase response at `freq` in Hz, in radians
+#[no_mangle]
+unsafe extern "C" fn multifilter_phase_at(ptr: *const MultiFilter, freq: f32) -> f32 {
+    ptr.as_ref().unwrap_unchecked().phase_at(freq)
+}
+
+/// Fills `out` with dB values on a log-spaced grid from `f_min` to `f_max`. `out` needs `length` elements!
+#[no_mangle]
+unsafe extern "C" fn multifilter_fill_response_db(
+    ptr: *const MultiFilter,
+    f_min: f32,
+    f_max: f32,
+    out: *mut f32,
+    length: usize,
+) {
+    ptr.as_ref().unwrap_unchecked().fill_response_db(
+        f_min,
+        f_max,
+        core::slice::from_raw_parts_mut(out, length),
+    );
+}
+
+/// Frequency of point `index` on a log-spaced grid with `length` points, e.g. for axis labels
+#[no_mangle]
+extern "C" fn multifilter_log_spaced_freq(
+    index: usize,
+    length: usize,
+    f_min: f32,
+    f_max: f32,
+) -> f32 {
+    log_spaced_freq(index, length, f_min, f_max)
+}
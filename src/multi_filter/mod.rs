@@ -1,35 +1,117 @@
 mod ffi;
 
+use core::f32::consts::PI;
+
 use embedded_audio_tools::filter::{
     butterworth::ButterworthType, Biquad, BiquadCoeffs, Butterworth,
 };
+use libm::{asinhf, atan2f, cosf, log10f, powf, sinf, sqrtf};
+
+use crate::smoothed_param::{SmoothedParam, SmoothingMode, DEFAULT_SMOOTHING_MS};
+
+/// cbindgen:ignore
+const MIN_RESPONSE_DB: f32 = -120.0;
+
+/// cbindgen:ignore
+const MIN_SHELF_SLOPE: f32 = 0.01;
+
+/// Steepest shelf slope without overshoot
+/// cbindgen:ignore
+const MAX_SHELF_SLOPE: f32 = 1.0;
+
+/// Samples between coefficient updates while parameters are smoothed
+/// cbindgen:ignore
+const COEFF_UPDATE_INTERVAL: usize = 16;
 
 #[repr(C)]
 pub struct MultiFilter {
     biquad: Biquad<Butterworth>,
+    /// Coefficients of the settled parameters, used by the response functions
+    target_coeffs: BiquadCoeffs<Butterworth>,
     filter: ButterworthType,
     sr: f32,
     fc: f32,
     q: f32,
+    /// Linear amplitude gain of the bell and shelf filters
     gain: f32,
+    /// Shelf slope that `q` is derived from, `0.0` when `q` was set directly
+    shelf_slope: f32,
+
+    smooth_fc: SmoothedParam,
+    smooth_q: SmoothedParam,
+    smooth_gain: SmoothedParam,
+    coeff_countdown: usize,
 }
 
 impl MultiFilter {
     pub fn init(sr: usize) -> MultiFilter {
-        MultiFilter {
+        let mut filter = MultiFilter {
             biquad: Biquad::new(BiquadCoeffs::new()),
+            target_coeffs: BiquadCoeffs::new(),
             filter: ButterworthType::Lowpass,
             sr: sr as f32,
             fc: 100.0,
             q: 1.0,
             gain: 1.0,
-        }
+            shelf_slope: 0.0,
+
+            smooth_fc: SmoothedParam::new(100.0, SmoothingMode::OnePole),
+            smooth_q: SmoothedParam::new(1.0, SmoothingMode::OnePole),
+            smooth_gain: SmoothedParam::new(1.0, SmoothingMode::OnePole),
+            coeff_countdown: 0,
+        };
+
+        filter.set_smoothing_time(DEFAULT_SMOOTHING_MS);
+        filter
     }
 
     pub fn next(&mut self, input: f32) -> f32 {
+        if self.is_settling() {
+            let (fc, q, gain) = (
+                self.smooth_fc.tick(),
+                self.smooth_q.tick(),
+                self.smooth_gain.tick(),
+            );
+
+            // recalculating at control rate is plenty and saves a lot of trigonometry
+            if self.coeff_countdown == 0 || !self.is_settling() {
+                self.coeff_countdown = COEFF_UPDATE_INTERVAL;
+                calculate_coeffs(&mut self.biquad.coeffs, &self.filter, fc, q, gain, self.sr);
+            }
+            self.coeff_countdown -= 1;
+        }
+
         self.biquad.process(input)
     }
 
+    /// Time in ms that cutoff, Q and gain take to reach a new value
+    pub fn set_smoothing_time(&mut self, ms: f32) {
+        self.smooth_fc.set_time_ms(ms, self.sr);
+        self.smooth_q.set_time_ms(ms, self.sr);
+        self.smooth_gain.set_time_ms(ms, self.sr);
+    }
+
+    /// Clears the filter memory, e.g. before a bypassed filter is switched back in
+    pub fn reset(&mut self) {
+        let coeffs = core::mem::replace(&mut self.biquad.coeffs, BiquadCoeffs::new());
+        self.biquad = Biquad::new(coeffs);
+    }
+
+    /// Jumps to the current settings without smoothing, e.g. when loading a preset
+    pub fn skip_smoothing(&mut self) {
+        self.smooth_fc.set_immediate(self.fc);
+        self.smooth_q.set_immediate(self.q);
+        self.smooth_gain.set_immediate(self.gain);
+        calculate_coeffs(
+            &mut self.biquad.coeffs,
+            &self.filter,
+            self.fc,
+            self.q,
+            self.gain,
+            self.sr,
+        );
+    }
+
     pub fn set_filter(&mut self, filter: ButterworthType) {
         self.filter = filter;
         self.update_coeffs();
@@ -42,11 +124,33 @@ impl MultiFilter {
 
     pub fn set_q(&mut self, q: f32) {
         self.q = q;
+        self.shelf_slope = 0.0;
         self.update_coeffs();
     }
 
+    /// Linear amplitude gain, only used by bell and shelf filters
     pub fn set_gain(&mut self, gain: f32) {
         self.gain = gain;
+        self.update_shelf_q();
+        self.update_coeffs();
+    }
+
+    /// Gain in dB, only used by bell and shelf filters
+    pub fn set_gain_db(&mut self, db: f32) {
+        self.set_gain(db_to_gain(db));
+    }
+
+    /// Bandwidth in octaves between the -3 dB points (or half gain points for a bell)
+    pub fn set_bandwidth(&mut self, octaves: f32) {
+        self.set_q(bandwidth_to_q(octaves));
+    }
+
+    /// Shelf slope between 0.01 and 1.0, otherwise clamps
+    ///
+    /// 1.0 is the steepest slope without overshoot. The resulting Q follows gain changes.
+    pub fn set_shelf_slope(&mut self, slope: f32) {
+        self.shelf_slope = slope.clamp(MIN_SHELF_SLOPE, MAX_SHELF_SLOPE);
+        self.update_shelf_q();
         self.update_coeffs();
     }
 
@@ -55,20 +159,285 @@ impl MultiFilter {
         self.fc = freq;
         self.q = q;
         self.gain = gain;
+        self.shelf_slope = 0.0;
         self.update_coeffs();
     }
 
+    /// Linear magnitude response of the current settings at `freq` in Hz, ignoring smoothing
+    pub fn magnitude_at(&self, freq: f32) -> f32 {
+        let (re, im) = self.response_at(freq);
+        sqrtf(re * re + im * im)
+    }
+
+    /// Magnitude response of the current settings at `freq` in Hz, in dB
+    ///
+    /// Clamped to -120 dB, so notches stay drawable.
+    pub fn magnitude_db_at(&self, freq: f32) -> f32 {
+        gain_to_db(self.magnitude_at(freq))
+    }
+
+    /// Phase response of the current settings at `freq` in Hz, in radians between -π and π
+    pub fn phase_at(&self, freq: f32) -> f32 {
+        let (re, im) = self.response_at(freq);
+        atan2f(im, re)
+    }
+
+    /// Fills `out` with the dB magnitude response on a log-spaced grid from `f_min` to `f_max`
+    ///
+    /// The first element is evaluated at `f_min`, the last one at `f_max`.
+    pub fn fill_response_db(&self, f_min: f32, f_max: f32, out: &mut [f32]) {
+        let len = out.len();
+
+        for (i, db) in out.iter_mut().enumerate() {
+            *db = self.magnitude_db_at(log_spaced_freq(i, len, f_min, f_max));
+        }
+    }
+
+    /// Complex frequency response `H(e^jw)` as `(re, im)`
+    ///
+    /// Assumes normalized coefficients, i.e.
+    /// `H(z) = (b0 + b1 z^-1 + b2 z^-2) / (1 + a1 z^-1 + a2 z^-2)`
+    pub(crate) fn response_at(&self, freq: f32) -> (f32, f32) {
+        let c = &self.target_coeffs;
+        let w = 2.0 * PI * freq / self.sr;
+        let (cos_w, sin_w) = (cosf(w), sinf(w));
+        let (cos_2w, sin_2w) = (cosf(2.0 * w), sinf(2.0 * w));
+
+        let num = (
+            c.b0 + c.b1 * cos_w + c.b2 * cos_2w,
+            -(c.b1 * sin_w + c.b2 * sin_2w),
+        );
+        let den = (
+            1.0 + c.a1 * cos_w + c.a2 * cos_2w,
+            -(c.a1 * sin_w + c.a2 * sin_2w),
+        );
+
+        let den_norm = den.0 * den.0 + den.1 * den.1;
+
+        (
+            (num.0 * den.0 + num.1 * den.1) / den_norm,
+            (num.1 * den.0 - num.0 * den.1) / den_norm,
+        )
+    }
+
+    fn update_shelf_q(&mut self) {
+        if self.shelf_slope > 0.0 {
+            self.q = shelf_slope_to_q(self.shelf_slope, self.gain);
+        }
+    }
+
+    fn is_settling(&self) -> bool {
+        self.smooth_fc.is_settling()
+            || self.smooth_q.is_settling()
+            || self.smooth_gain.is_settling()
+    }
+
     fn update_coeffs(&mut self) {
-        match self.filter {
-            ButterworthType::Lowpass => self.biquad.coeffs.lowpass(self.fc, self.q, self.sr),
-            ButterworthType::Highpass => self.biquad.coeffs.highpass(self.fc, self.q, self.sr),
-            ButterworthType::Allpass => self.biquad.coeffs.allpass(self.fc, self.q, self.sr),
-            ButterworthType::Notch => self.biquad.coeffs.notch(self.fc, self.q, self.sr),
-            ButterworthType::Bell => self.biquad.coeffs.bell(self.fc, self.q, self.gain, self.sr),
-            ButterworthType::LowShelf => self
-                .biquad
-                .coeffs
-                .low_shelf(self.fc, self.q, self.gain, self.sr),
+        calculate_coeffs(
+            &mut self.target_coeffs,
+            &self.filter,
+            self.fc,
+            self.q,
+            self.gain,
+            self.sr,
+        );
+
+        self.smooth_fc.set_target(self.fc);
+        self.smooth_q.set_target(self.q);
+        self.smooth_gain.set_target(self.gain);
+
+        // the filter type can't glide, so it switches right away with the current smoothed values
+        calculate_coeffs(
+            &mut self.biquad.coeffs,
+            &self.filter,
+            self.smooth_fc.value(),
+            self.smooth_q.value(),
+            self.smooth_gain.value(),
+            self.sr,
+        );
+        self.coeff_countdown = COEFF_UPDATE_INTERVAL;
+    }
+}
+
+fn calculate_coeffs(
+    coeffs: &mut BiquadCoeffs<Butterworth>,
+    filter: &ButterworthType,
+    fc: f32,
+    q: f32,
+    gain: f32,
+    sr: f32,
+) {
+    match filter {
+        ButterworthType::Lowpass => coeffs.lowpass(fc, q, sr),
+        ButterworthType::Highpass => coeffs.highpass(fc, q, sr),
+        ButterworthType::Allpass => coeffs.allpass(fc, q, sr),
+        ButterworthType::Notch => coeffs.notch(fc, q, sr),
+        ButterworthType::Bell => coeffs.bell(fc, q, gain, sr),
+        ButterworthType::LowShelf => coeffs.low_shelf(fc, q, gain, sr),
+    }
+}
+
+/// Frequency of point `index` on a log-spaced grid of `len` points from `f_min` to `f_max`
+pub fn log_spaced_freq(index: usize, len: usize, f_min: f32, f_max: f32) -> f32 {
+    if len < 2 {
+        return f_min;
+    }
+
+    f_min * powf(f_max / f_min, index as f32 / (len - 1) as f32)
+}
+
+/// Converts dB into a linear amplitude gain
+pub fn db_to_gain(db: f32) -> f32 {
+    powf(10.0, db / 20.0)
+}
+
+/// Converts a linear amplitude gain into dB, clamped to -120 dB
+pub fn gain_to_db(gain: f32) -> f32 {
+    if gain > 0.0 {
+        (20.0 * log10f(gain)).max(MIN_RESPONSE_DB)
+    } else {
+        MIN_RESPONSE_DB
+    }
+}
+
+/// Q of a second order section spanning `octaves` between its -3 dB points
+///
+/// `Q = sqrt(2^N) / (2^N - 1)`, e.g. one octave equals a Q of `sqrt(2)`
+pub fn bandwidth_to_q(octaves: f32) -> f32 {
+    let ratio = powf(2.0, octaves.max(f32::EPSILON));
+    sqrtf(ratio) / (ratio - 1.0)
+}
+
+/// Inverse of `bandwidth_to_q`
+pub fn q_to_bandwidth(q: f32) -> f32 {
+    2.0 / core::f32::consts::LN_2 * asinhf(1.0 / (2.0 * q))
+}
+
+/// Q of a shelf with the given `slope` and linear `gain`, after the RBJ cookbook
+///
+/// `1/Q = sqrt((A + 1/A) * (1/S - 1) + 2)` with `A = sqrt(gain)`. A slope of 1.0 always yields
+/// `Q = 1/sqrt(2)`.
+pub fn shelf_slope_to_q(slope: f32, gain: f32) -> f32 {
+    let a = sqrtf(gain);
+    let slope = slope.clamp(MIN_SHELF_SLOPE, MAX_SHELF_SLOPE);
+    1.0 / sqrtf((a + 1.0 / a) * (1.0 / slope - 1.0) + 2.0)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    const SR: usize = 48000;
+
+    fn lowpass(fc: f32) -> MultiFilter {
+        let mut filter = MultiFilter::init(SR);
+        filter.set_all(
+            ButterworthType::Lowpass,
+            fc,
+            core::f32::consts::FRAC_1_SQRT_2,
+            1.0,
+        );
+        filter
+    }
+
+    #[test]
+    fn lowpass_response() {
+        let filter = lowpass(1000.0);
+
+        assert!((filter.magnitude_at(1.0) - 1.0).abs() < 1e-3);
+        assert!((filter.magnitude_db_at(1000.0) + 3.01).abs() < 0.05);
+        assert!(filter.magnitude_db_at(10000.0) < -35.0);
+        assert!((filter.phase_at(1000.0) + PI / 2.0).abs() < 1e-2);
+    }
+
+    #[test]
+    fn response_matches_processing() {
+        let mut filter = lowpass(2000.0);
+        let freq = 4000.0;
+
+        // let the filter settle, then measure the peak of a sine
+        let mut peak = 0.0_f32;
+        for i in 0..SR / 10 {
+            let out = filter.next(sinf(2.0 * PI * freq * i as f32 / SR as f32));
+            if i > SR / 20 {
+                peak = peak.max(out.abs());
+            }
         }
+
+        assert!((peak - filter.magnitude_at(freq)).abs() < 1e-2);
+    }
+
+    #[test]
+    fn cutoff_glides() {
+        let mut filter = lowpass(1000.0);
+        filter.skip_smoothing();
+        filter.set_cutoff(5000.0);
+
+        filter.next(0.0);
+        assert!(filter.biquad.coeffs.b0 != filter.target_coeffs.b0);
+
+        for _ in 0..SR / 20 {
+            filter.next(0.0);
+        }
+        assert_eq!(filter.biquad.coeffs.b0, filter.target_coeffs.b0);
+        assert_eq!(filter.biquad.coeffs.a1, filter.target_coeffs.a1);
+    }
+
+    #[test]
+    fn gain_conversion() {
+        assert_eq!(db_to_gain(0.0), 1.0);
+        assert!((db_to_gain(6.0) - 1.9953).abs() < 1e-4);
+        assert!((db_to_gain(-20.0) - 0.1).abs() < 1e-6);
+        assert!((gain_to_db(db_to_gain(-7.5)) + 7.5).abs() < 1e-4);
+        assert_eq!(gain_to_db(0.0), MIN_RESPONSE_DB);
+    }
+
+    #[test]
+    fn bandwidth_conversion() {
+        assert!((bandwidth_to_q(1.0) - core::f32::consts::SQRT_2).abs() < 1e-4);
+        assert!((bandwidth_to_q(2.0) - 2.0 / 3.0).abs() < 1e-4);
+        assert!((q_to_bandwidth(core::f32::consts::SQRT_2) - 1.0).abs() < 1e-4);
+
+        for octaves in [0.1, 0.5, 1.0, 3.0] {
+            assert!((q_to_bandwidth(bandwidth_to_q(octaves)) - octaves).abs() < 1e-3);
+        }
+    }
+
+    #[test]
+    fn shelf_slope_conversion() {
+        for db in [-12.0, 0.0, 6.0, 18.0] {
+            let q = shelf_slope_to_q(1.0, db_to_gain(db));
+            assert!((q - core::f32::consts::FRAC_1_SQRT_2).abs() < 1e-5);
+        }
+
+        // gentler slopes mean lower Q, out of range slopes clamp
+        assert!(shelf_slope_to_q(0.5, db_to_gain(6.0)) < core::f32::consts::FRAC_1_SQRT_2);
+        assert_eq!(shelf_slope_to_q(2.0, 4.0), shelf_slope_to_q(1.0, 4.0));
+    }
+
+    #[test]
+    fn shelf_q_follows_gain() {
+        let mut filter = MultiFilter::init(SR);
+        filter.set_filter(ButterworthType::LowShelf);
+        filter.set_shelf_slope(0.5);
+        filter.set_gain_db(12.0);
+        assert_eq!(filter.q, shelf_slope_to_q(0.5, db_to_gain(12.0)));
+
+        filter.set_bandwidth(1.0);
+        filter.set_gain_db(-12.0);
+        assert_eq!(filter.q, bandwidth_to_q(1.0));
+    }
+
+    #[test]
+    fn log_grid() {
+        let filter = lowpass(500.0);
+        let mut response = [0.0; 64];
+        filter.fill_response_db(20.0, 20000.0, &mut response);
+
+        assert_eq!(log_spaced_freq(0, 64, 20.0, 20000.0), 20.0);
+        assert!((log_spaced_freq(63, 64, 20.0, 20000.0) - 20000.0).abs() < 0.1);
+        assert_eq!(response[0], filter.magnitude_db_at(20.0));
+        assert!(response.windows(2).all(|w| w[1] <= w[0] + 1e-3));
+        assert!(response.iter().all(|db| *db >= MIN_RESPONSE_DB));
     }
 }
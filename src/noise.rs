@@ -0,0 +1,44 @@
+/// cbindgen:ignore
+const DEFAULT_SEED: u32 = 0x9E37_79B9;
+
+/// Xorshift white noise, cheap and deterministic for a given seed
+#[derive(Clone, Copy)]
+#[repr(C)]
+pub struct WhiteNoise {
+    state: u32,
+}
+
+impl WhiteNoise {
+    /// A seed of zero falls back to a default seed, since xorshift would get stuck on zero
+    pub fn new(seed: u32) -> WhiteNoise {
+        WhiteNoise {
+            state: if seed == 0 { DEFAULT_SEED } else { seed },
+        }
+    }
+
+    #[inline(always)]
+    pub fn next_u32(&mut self) -> u32 {
+        self.state ^= self.state << 13;
+        self.state ^= self.state >> 17;
+        self.state ^= self.state << 5;
+        self.state
+    }
+
+    /// Uniformly distributed between 0.0 and 1.0
+    #[inline(always)]
+    pub fn tick_unipolar(&mut self) -> f32 {
+        (self.next_u32() >> 8) as f32 / (1 << 24) as f32
+    }
+
+    /// Uniformly distributed between -1.0 and 1.0
+    #[inline(always)]
+    pub fn tick(&mut self) -> f32 {
+        self.tick_unipolar() * 2.0 - 1.0
+    }
+}
+
+impl Default for WhiteNoise {
+    fn default() -> Self {
+        WhiteNoise::new(DEFAULT_SEED)
+    }
+}
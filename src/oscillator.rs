@@ -0,0 +1,228 @@
+use core::f32::consts::PI;
+
+use libm::sinf;
+
+use crate::tools::Waveform;
+
+/// Naive (not bandlimited) shape of `wave` at `phase` between 0.0 and 1.0
+///
+/// All shapes start at zero or their rising edge, like the sine.
+#[inline(always)]
+pub fn waveform_at(wave: &Waveform, phase: f32) -> f32 {
+    match wave {
+        Waveform::Sine => sinf(2.0 * PI * phase),
+        Waveform::Rectangle => {
+            if phase < 0.5 {
+                1.0
+            } else {
+                -1.0
+            }
+        }
+        Waveform::Sawtooth => {
+            // wraps around at half the period to start from zero
+            let shifted = phase + 0.5;
+            2.0 * (shifted - (shifted as usize) as f32) - 1.0
+        }
+        Waveform::Triangle => {
+            let shifted = phase + 0.75;
+            4.0 * (shifted - (shifted as usize) as f32 - 0.5).abs() - 1.0
+        }
+    }
+}
+
+/// Polynomial approximation of a band-limited step, subtracted around a discontinuity at `t = 0`
+///
+/// `t` is the phase since the step between 0.0 and 1.0, `dt` the phase increment per sample.
+#[inline(always)]
+fn poly_blep(t: f32, dt: f32) -> f32 {
+    if t < dt {
+        let x = t / dt;
+        2.0 * x - x * x - 1.0
+    } else if t > 1.0 - dt {
+        let x = (t - 1.0) / dt;
+        x * x + 2.0 * x + 1.0
+    } else {
+        0.0
+    }
+}
+
+/// Wraps `phase` into 0.0..1.0
+#[inline(always)]
+fn wrap(phase: f32) -> f32 {
+    phase - libm::floorf(phase)
+}
+
+/// Phase accumulator that crossfades from a sine towards one of the `Waveform` shapes
+///
+/// The steps of the sawtooth and the rectangle are smoothed by polyBLEP, which keeps the aliasing of
+/// swept kick bodies low.
+#[repr(C)]
+pub struct MorphOscillator {
+    phase: f32,
+    increment: f32,
+    sr: f32,
+    wave: Waveform,
+    morph: f32,
+}
+
+impl MorphOscillator {
+    pub fn new(freq: f32, sr: f32) -> MorphOscillator {
+        MorphOscillator {
+            phase: 0.0,
+            increment: freq / sr,
+            sr,
+            wave: Waveform::Sine,
+            morph: 1.0,
+        }
+    }
+
+    /// Next sample with the phase shifted by `offset` periods, e.g. for phase modulation
+    #[inline(always)]
+    pub fn tick_with_offset(&mut self, offset: f32) -> f32 {
+        let phase = wrap(self.phase + offset);
+
+        self.phase += self.increment;
+        if self.phase >= 1.0 {
+            self.phase -= 1.0;
+        }
+
+        let shape = self.band_limited(phase);
+        if self.morph < 1.0 {
+            let sine = waveform_at(&Waveform::Sine, phase);
+            sine + self.morph * (shape - sine)
+        } else {
+            shape
+        }
+    }
+
+    /// The waveform with its steps smoothed over one sample on either side
+    #[inline(always)]
+    fn band_limited(&self, phase: f32) -> f32 {
+        let dt = self.increment.min(0.5);
+        let naive = waveform_at(&self.wave, phase);
+
+        match self.wave {
+            // falls from 1.0 to -1.0 at half the period
+            Waveform::Sawtooth => naive - poly_blep(wrap(phase + 0.5), dt),
+            // rises at the start, falls at half the period
+            Waveform::Rectangle => naive + poly_blep(phase, dt) - poly_blep(wrap(phase + 0.5), dt),
+            // continuous, the kinks alias far less
+            Waveform::Sine | Waveform::Triangle => naive,
+        }
+    }
+
+    #[inline(always)]
+    pub fn tick(&mut self) -> f32 {
+        self.tick_with_offset(0.0)
+    }
+
+    pub fn set_freq(&mut self, freq: f32) {
+        self.increment = freq / self.sr;
+    }
+
+    pub fn set_sr(&mut self, sr: f32) {
+        self.increment *= self.sr / sr;
+        self.sr = sr;
+    }
+
+    pub fn reset_phase(&mut self) {
+        self.phase = 0.0;
+    }
+
+    pub fn set_waveform(&mut self, wave: Waveform) {
+        self.wave = wave;
+    }
+
+    /// 0.0 is a pure sine, 1.0 the pure waveform. Only accepts values between 0.0 and 1.0, otherwise clamps
+    pub fn set_morph(&mut self, val: f32) {
+        self.morph = val.clamp(0.0, 1.0);
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn shapes_line_up() {
+        for wave in [
+            Waveform::Sine,
+            Waveform::Rectangle,
+            Waveform::Sawtooth,
+            Waveform::Triangle,
+        ] {
+            assert!(waveform_at(&wave, 0.25) > 0.0);
+            assert!(waveform_at(&wave, 0.75) < 0.0);
+        }
+
+        assert_eq!(waveform_at(&Waveform::Sawtooth, 0.0), 0.0);
+        assert_eq!(waveform_at(&Waveform::Triangle, 0.0), 0.0);
+        assert_eq!(waveform_at(&Waveform::Triangle, 0.25), 1.0);
+        assert_eq!(waveform_at(&Waveform::Triangle, 0.75), -1.0);
+    }
+
+    #[test]
+    fn morph_blends_from_sine() {
+        let mut sine = MorphOscillator::new(100.0, 48000.0);
+        let mut morphed = MorphOscillator::new(100.0, 48000.0);
+        morphed.set_waveform(Waveform::Rectangle);
+        morphed.set_morph(0.0);
+
+        // a quarter period, away from the steps
+        for _ in 0..120 {
+            assert_eq!(sine.tick(), morphed.tick());
+        }
+
+        morphed.set_morph(0.5);
+        let (s, m) = (sine.tick(), morphed.tick());
+        assert!((m - (0.5 * s + 0.5)).abs() < 1e-6);
+    }
+
+    /// Energy between the harmonics of a 1100 Hz tone relative to the total, which is aliasing
+    fn alias_ratio(mut render: impl FnMut() -> f32) -> f32 {
+        const N: usize = 960;
+        let out: [f32; N] = core::array::from_fn(|_| render());
+
+        // 22 periods in the window, the harmonics land on every 22nd bin
+        let (mut alias, mut total) = (0.0, 0.0);
+        for k in 1..N / 2 {
+            let (re, im) = out.iter().enumerate().fold((0.0, 0.0), |(re, im), (i, x)| {
+                let w = 2.0 * PI * (k * i) as f32 / N as f32;
+                (re + x * libm::cosf(w), im + x * sinf(w))
+            });
+            let energy = re * re + im * im;
+            total += energy;
+            if k % 22 != 0 {
+                alias += energy;
+            }
+        }
+        alias / total
+    }
+
+    #[test]
+    fn steps_are_band_limited() {
+        // `Waveform` is not `Clone`
+        let shape = |saw: bool| {
+            if saw {
+                Waveform::Sawtooth
+            } else {
+                Waveform::Rectangle
+            }
+        };
+
+        for saw in [true, false] {
+            let mut phase = 0.0;
+            let naive = alias_ratio(|| {
+                let x = waveform_at(&shape(saw), phase);
+                phase = wrap(phase + 1100.0 / 48000.0);
+                x
+            });
+
+            let mut osc = MorphOscillator::new(1100.0, 48000.0);
+            osc.set_waveform(shape(saw));
+            let blep = alias_ratio(|| osc.tick());
+
+            assert!(blep < 0.1 * naive);
+        }
+    }
+}
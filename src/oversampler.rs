@@ -0,0 +1,228 @@
+/// Non-zero taps of one side of a 47 tap Kaiser windowed halfband FIR, every other tap is zero
+/// and the center tap is 0.5. Flat up to 0.4 times the lower sample rate.
+/// cbindgen:ignore
+const HALFBAND: [f32; HALFBAND_TAPS] = [
+    0.316_060_03,
+    -0.099_533_67,
+    0.053_239_11,
+    -0.031_905_92,
+    0.019_511_503,
+    -0.011_685_277,
+    0.006_670_786,
+    -0.003_539_435_3,
+    0.001_690_635_5,
+    -6.899_972e-4,
+    2.146_022_8e-4,
+    -3.236_779e-5,
+];
+
+/// cbindgen:ignore
+const HALFBAND_TAPS: usize = 12;
+
+/// History length of one polyphase branch
+/// cbindgen:ignore
+const HISTORY: usize = 2 * HALFBAND_TAPS;
+
+/// Delay of one halfband filter at its higher sample rate
+/// cbindgen:ignore
+const HALFBAND_LATENCY: f32 = (HISTORY - 1) as f32;
+
+/// Delay line of the dry compensation, longer than the latency of all stages
+/// cbindgen:ignore
+const DRY_LENGTH: usize = 64;
+
+/// One 2x up- and downsampling stage with polyphase halfband filters
+#[repr(C)]
+struct HalfbandStage {
+    up: [f32; 24],
+    down_even: [f32; 24],
+    down_odd: [f32; 24],
+}
+
+impl HalfbandStage {
+    const fn new() -> HalfbandStage {
+        HalfbandStage {
+            up: [0.0; HISTORY],
+            down_even: [0.0; HISTORY],
+            down_odd: [0.0; HISTORY],
+        }
+    }
+
+    /// Two samples at the doubled rate
+    #[inline(always)]
+    fn upsample(&mut self, input: f32) -> (f32, f32) {
+        push(&mut self.up, input);
+
+        // the zero stuffing halves the level, the factor 2 restores it
+        (2.0 * symmetric_sum(&self.up), self.up[HALFBAND_TAPS - 1])
+    }
+
+    /// One sample at the halved rate
+    #[inline(always)]
+    fn downsample(&mut self, even: f32, odd: f32) -> f32 {
+        push(&mut self.down_even, even);
+        push(&mut self.down_odd, odd);
+
+        0.5 * self.down_even[HALFBAND_TAPS - 1] + symmetric_sum(&self.down_odd)
+    }
+}
+
+#[inline(always)]
+fn push(history: &mut [f32; HISTORY], input: f32) {
+    history.copy_within(0..HISTORY - 1, 1);
+    history[0] = input;
+}
+
+/// Applies the halfband taps to the branch, which is symmetric around its center
+#[inline(always)]
+fn symmetric_sum(history: &[f32; HISTORY]) -> f32 {
+    HALFBAND
+        .iter()
+        .enumerate()
+        .map(|(m, tap)| tap * (history[HALFBAND_TAPS - 1 - m] + history[HALFBAND_TAPS + m]))
+        .sum()
+}
+
+/// Runs a nonlinear mono processor at 2, 4 or 8 times the sample rate to reduce aliasing
+///
+/// Each doubling adds a cascaded halfband stage with fixed-size state, so no buffer is needed.
+#[repr(C)]
+pub struct Oversampler {
+    stages: [HalfbandStage; 3],
+    active: usize,
+
+    dry: [f32; 64],
+    dry_index: usize,
+}
+
+impl Oversampler {
+    pub fn new() -> Oversampler {
+        Oversampler {
+            stages: [
+                HalfbandStage::new(),
+                HalfbandStage::new(),
+                HalfbandStage::new(),
+            ],
+            active: 0,
+
+            dry: [0.0; DRY_LENGTH],
+            dry_index: 0,
+        }
+    }
+
+    /// Upsamples `input`, calls `f` once per oversampled sample and returns the downsampled result
+    #[inline]
+    pub fn process(&mut self, input: f32, mut f: impl FnMut(f32) -> f32) -> f32 {
+        process_stages(&mut self.stages[..self.active], input, &mut f)
+    }
+
+    /// Delays `input` by the latency, e.g. to keep a parallel dry signal aligned
+    #[inline]
+    pub fn delay(&mut self, input: f32) -> f32 {
+        self.dry[self.dry_index] = input;
+
+        let latency = self.latency_samples();
+        let whole = latency as usize;
+        let frac = latency - whole as f32;
+        let at = |delay: usize| self.dry[(self.dry_index + DRY_LENGTH - delay) % DRY_LENGTH];
+        let delayed = at(whole) + frac * (at(whole + 1) - at(whole));
+
+        self.dry_index = (self.dry_index + 1) % DRY_LENGTH;
+        delayed
+    }
+
+    /// Oversampling factor 1, 2, 4 or 8. Other values round down, 0 counts as 1.
+    pub fn set_factor(&mut self, factor: usize) {
+        let active = match factor {
+            0..=1 => 0,
+            2..=3 => 1,
+            4..=7 => 2,
+            _ => 3,
+        };
+
+        // stages that come back in must not replay old signal
+        for stage in &mut self.stages[self.active.min(active)..] {
+            *stage = HalfbandStage::new();
+        }
+        self.active = active;
+    }
+
+    pub fn factor(&self) -> usize {
+        1 << self.active
+    }
+
+    /// Delay of the output in samples at the original sample rate, can be fractional
+    pub fn latency_samples(&self) -> f32 {
+        // up- and downsampling filter of every stage at twice the rate of the stage before,
+        // the decimation keeps the odd sample which is one sample earlier
+        (1..=self.active)
+            .map(|stage| (2.0 * HALFBAND_LATENCY - 1.0) / (1 << stage) as f32)
+            .sum()
+    }
+}
+
+impl Default for Oversampler {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+fn process_stages(stages: &mut [HalfbandStage], input: f32, f: &mut impl FnMut(f32) -> f32) -> f32 {
+    match stages.split_first_mut() {
+        None => f(input),
+        Some((stage, inner)) => {
+            let (even, odd) = stage.upsample(input);
+            let even = process_stages(inner, even, f);
+            let odd = process_stages(inner, odd, f);
+            stage.downsample(even, odd)
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    const SR: f32 = 48000.0;
+
+    fn sine(freq: f32, t: f32) -> f32 {
+        libm::sinf(2.0 * core::f32::consts::PI * freq * t / SR)
+    }
+
+    #[test]
+    fn linear_path_is_delayed_by_latency() {
+        for factor in [1, 2, 4, 8] {
+            let mut oversampler = Oversampler::new();
+            oversampler.set_factor(factor);
+            assert_eq!(oversampler.factor(), factor);
+
+            let latency = oversampler.latency_samples();
+            for i in 0..2000 {
+                let out = oversampler.process(sine(1000.0, i as f32), |x| x);
+                let dry = oversampler.delay(sine(1000.0, i as f32));
+                if i > 100 {
+                    let expected = sine(1000.0, i as f32 - latency);
+                    assert!((out - expected).abs() < 1e-3, "{}x: {}", factor, i);
+                    assert!((dry - expected).abs() < 1e-2, "{}x: {}", factor, i);
+                }
+            }
+        }
+    }
+
+    #[test]
+    fn reduces_aliasing() {
+        // hard clipping a 7 kHz sine folds harmonics back onto 1 kHz among others
+        let alias = |factor: usize| {
+            let mut oversampler = Oversampler::new();
+            oversampler.set_factor(factor);
+            let (re, im) = (0..4800).fold((0.0, 0.0), |(re, im), i| {
+                let y = oversampler.process(4.0 * sine(7000.0, i as f32), |x| x.clamp(-1.0, 1.0));
+                let w = 2.0 * core::f32::consts::PI * 1000.0 * i as f32 / SR;
+                (re + y * libm::cosf(w), im + y * libm::sinf(w))
+            });
+            libm::sqrtf(re * re + im * im)
+        };
+
+        assert!(alias(4) < 0.1 * alias(1));
+    }
+}
@@ -0,0 +1,82 @@
+use embedded_audio_tools::filter::butterworth::ButterworthType;
+
+use super::ParametricEq4;
+
+/// Initializes `ParametricEq4` struct. All bands are bypassed.
+#[no_mangle]
+extern "C" fn parametric_eq_init(sr: f32) -> ParametricEq4 {
+    ParametricEq4::init(sr as usize)
+}
+
+/// Returns next sample
+#[no_mangle]
+unsafe extern "C" fn parametric_eq_tick(ptr: *mut ParametricEq4, sample: f32) -> f32 {
+    ptr.as_mut().unwrap_unchecked().tick(sample)
+}
+
+/// Configures and enables band `index`. Out of range indices are ignored.
+#[no_mangle]
+unsafe extern "C" fn parametric_eq_set_band(
+    ptr: *mut ParametricEq4,
+    index: usize,
+    filter: ButterworthType,
+    freq: f32,
+    q: f32,
+    gain: f32,
+) {
+    ptr.as_mut()
+        .unwrap_unchecked()
+        .set_band(index, filter, freq, q, gain);
+}
+
+/// Out of range indices are ignored
+#[no_mangle]
+unsafe extern "C" fn parametric_eq_set_band_bypass(
+    ptr: *mut ParametricEq4,
+    index: usize,
+    bypass: bool,
+) {
+    ptr.as_mut()
+        .unwrap_unchecked()
+        .set_band_bypass(index, bypass);
+}
+
+/// Linear gain after the last band
+#[no_mangle]
+unsafe extern "C" fn parametric_eq_set_output_gain(ptr: *mut ParametricEq4, gain: f32) {
+    ptr.as_mut().unwrap_unchecked().set_output_gain(gain);
+}
+
+/// Time in ms that band settings and the output gain take to reach a new value
+#[no_mangle]
+unsafe extern "C" fn parametric_eq_set_smoothing_time(ptr: *mut ParametricEq4, ms: f32) {
+    ptr.as_mut().unwrap_unchecked().set_smoothing_time(ms);
+}
+
+/// Linear magnitude response of all active bands at `freq` in Hz
+#[no_mangle]
+unsafe extern "C" fn parametric_eq_magnitude_at(ptr: *const ParametricEq4, freq: f32) -> f32 {
+    ptr.as_ref().unwrap_unchecked().magnitude_at(freq)
+}
+
+/// Phase response of all active bands at `freq` in Hz, in radians
+#[no_mangle]
+unsafe extern "C" fn parametric_eq_phase_at(ptr: *const ParametricEq4, freq: f32) -> f32 {
+    ptr.as_ref().unwrap_unchecked().phase_at(freq)
+}
+
+/// Fills `out` with dB values on a log-spaced grid from `f_min` to `f_max`. `out` needs `length` elements!
+#[no_mangle]
+unsafe extern "C" fn parametric_eq_fill_response_db(
+    ptr: *const ParametricEq4,
+    f_min: f32,
+    f_max: f32,
+    out: *mut f32,
+    length: usize,
+) {
+    ptr.as_ref().unwrap_unchecked().fill_response_db(
+        f_min,
+        f_max,
+        core::slice::from_raw_parts_mut(out, length),
+    );
+}
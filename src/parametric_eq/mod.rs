@@ -0,0 +1,197 @@
+mod ffi;
+
+use embedded_audio_tools::filter::butterworth::ButterworthType;
+use libm::atan2f;
+
+use crate::multi_filter::{gain_to_db, log_spaced_freq};
+use crate::smoothed_param::{SmoothedParam, SmoothingMode, DEFAULT_SMOOTHING_MS};
+use crate::MultiFilter;
+
+/// Parametric EQ with four bands, as exposed through the C API
+pub type ParametricEq4 = ParametricEq<4>;
+
+#[repr(C)]
+pub struct EqBand {
+    filter: MultiFilter,
+    bypass: bool,
+}
+
+/// Chain of `MultiFilter` bands, processed in ascending order
+#[repr(C)]
+pub struct ParametricEq<const BANDS: usize> {
+    bands: [EqBand; BANDS],
+    sr: f32,
+    output_gain: SmoothedParam,
+}
+
+impl<const BANDS: usize> ParametricEq<BANDS> {
+    /// All bands start out bypassed
+    pub fn init(sr: usize) -> ParametricEq<BANDS> {
+        let mut eq = ParametricEq {
+            bands: core::array::from_fn(|_| EqBand {
+                filter: MultiFilter::init(sr),
+                bypass: true,
+            }),
+            sr: sr as f32,
+            output_gain: SmoothedParam::new(1.0, SmoothingMode::Linear),
+        };
+
+        eq.output_gain.set_time_ms(DEFAULT_SMOOTHING_MS, eq.sr);
+        eq
+    }
+
+    pub fn tick(&mut self, input: f32) -> f32 {
+        let mut out = input;
+
+        for band in self.bands.iter_mut().filter(|band| !band.bypass) {
+            out = band.filter.next(out);
+        }
+
+        out * self.output_gain.tick()
+    }
+
+    /// Time in ms that band settings and the output gain take to reach a new value
+    pub fn set_smoothing_time(&mut self, ms: f32) {
+        self.output_gain.set_time_ms(ms, self.sr);
+        self.bands
+            .iter_mut()
+            .for_each(|band| band.filter.set_smoothing_time(ms));
+    }
+
+    /// Configures band `index` and enables it. Out of range indices are ignored.
+    ///
+    /// A bypassed band jumps to its new settings, an active one glides.
+    pub fn set_band(
+        &mut self,
+        index: usize,
+        filter: ButterworthType,
+        freq: f32,
+        q: f32,
+        gain: f32,
+    ) {
+        if let Some(band) = self.bands.get_mut(index) {
+            band.filter.set_all(filter, freq, q, gain);
+            if band.bypass {
+                band.filter.skip_smoothing();
+                band.filter.reset();
+            }
+            band.bypass = false;
+        }
+    }
+
+    /// Out of range indices are ignored. A band switched back in starts from a cleared state.
+    pub fn set_band_bypass(&mut self, index: usize, bypass: bool) {
+        if let Some(band) = self.bands.get_mut(index) {
+            if band.bypass && !bypass {
+                band.filter.reset();
+            }
+            band.bypass = bypass;
+        }
+    }
+
+    /// Access to a single band, e.g. to change only its cutoff
+    pub fn band_mut(&mut self, index: usize) -> Option<&mut MultiFilter> {
+        self.bands.get_mut(index).map(|band| &mut band.filter)
+    }
+
+    /// Linear gain applied after the last band
+    pub fn set_output_gain(&mut self, gain: f32) {
+        self.output_gain.set_target(gain);
+    }
+
+    /// Linear magnitude response of all active bands and the output gain at `freq` in Hz
+    pub fn magnitude_at(&self, freq: f32) -> f32 {
+        self.active_bands()
+            .fold(self.output_gain.target().abs(), |mag, filter| {
+                mag * filter.magnitude_at(freq)
+            })
+    }
+
+    /// Magnitude response at `freq` in Hz, in dB
+    pub fn magnitude_db_at(&self, freq: f32) -> f32 {
+        gain_to_db(self.magnitude_at(freq))
+    }
+
+    /// Phase response at `freq` in Hz, in radians between -π and π
+    pub fn phase_at(&self, freq: f32) -> f32 {
+        // multiply the complex responses, so the phase wraps correctly
+        let (re, im) =
+            self.active_bands()
+                .fold((self.output_gain.target(), 0.0), |(re, im), filter| {
+                    let (band_re, band_im) = filter.response_at(freq);
+                    (re * band_re - im * band_im, re * band_im + im * band_re)
+                });
+
+        atan2f(im, re)
+    }
+
+    /// Fills `out` with the combined dB magnitude response on a log-spaced grid from `f_min` to `f_max`
+    pub fn fill_response_db(&self, f_min: f32, f_max: f32, out: &mut [f32]) {
+        let len = out.len();
+
+        for (i, db) in out.iter_mut().enumerate() {
+            *db = self.magnitude_db_at(log_spaced_freq(i, len, f_min, f_max));
+        }
+    }
+
+    fn active_bands(&self) -> impl Iterator<Item = &MultiFilter> {
+        self.bands
+            .iter()
+            .filter(|band| !band.bypass)
+            .map(|band| &band.filter)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    const SR: usize = 48000;
+
+    #[test]
+    fn bypassed_is_transparent() {
+        let mut eq = ParametricEq4::init(SR);
+        eq.set_band(0, ButterworthType::Lowpass, 100.0, 0.7, 1.0);
+        eq.set_band_bypass(0, true);
+
+        for i in 0..64 {
+            let input = i as f32 / 64.0;
+            assert_eq!(eq.tick(input), input);
+        }
+        assert_eq!(eq.magnitude_at(10000.0), 1.0);
+    }
+
+    #[test]
+    fn band_switched_back_in_starts_clean() {
+        let mut eq = ParametricEq4::init(SR);
+        eq.set_band(0, ButterworthType::Lowpass, 100.0, 0.7, 1.0);
+        for _ in 0..64 {
+            eq.tick(1.0);
+        }
+
+        eq.set_band_bypass(0, true);
+        eq.set_band_bypass(0, false);
+        for _ in 0..64 {
+            assert_eq!(eq.tick(0.0), 0.0);
+        }
+    }
+
+    #[test]
+    fn bands_are_chained() {
+        let mut single = MultiFilter::init(SR);
+        single.set_all(ButterworthType::Lowpass, 1000.0, 0.7, 1.0);
+
+        let mut eq = ParametricEq::<2>::init(SR);
+        eq.set_band(0, ButterworthType::Lowpass, 1000.0, 0.7, 1.0);
+        eq.set_band(1, ButterworthType::Lowpass, 1000.0, 0.7, 1.0);
+        eq.set_band(2, ButterworthType::Highpass, 1000.0, 0.7, 1.0); // ignored
+        eq.set_output_gain(0.5);
+
+        for freq in [100.0, 1000.0, 5000.0] {
+            let expected = 0.5 * single.magnitude_at(freq) * single.magnitude_at(freq);
+            assert!((eq.magnitude_at(freq) - expected).abs() < 1e-5);
+        }
+
+        assert!((eq.phase_at(1000.0).abs() - core::f32::consts::PI).abs() < 1e-2);
+    }
+}
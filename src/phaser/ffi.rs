@@ -0,0 +1,66 @@
+use crate::Phaser;
+
+/// Initializes `Phaser` struct
+#[no_mangle]
+extern "C" fn phaser_init(sr: f32) -> Phaser {
+    Phaser::init(sr)
+}
+
+/// Returns next stereo samples. Raw pointer `stereo_samples` assumes to have exactly two elements!
+#[no_mangle]
+unsafe extern "C" fn phaser_tick(ptr: *mut Phaser, stereo_samples: *mut f32) {
+    let samples = core::slice::from_raw_parts_mut(stereo_samples, 2);
+
+    (samples[0], samples[1]) = ptr
+        .as_mut()
+        .unwrap_unchecked()
+        .tick((samples[0], samples[1]));
+}
+
+/// Number of allpass stages between 2 and 12, otherwise clamps
+#[no_mangle]
+unsafe extern "C" fn phaser_set_stages(ptr: *mut Phaser, stages: usize) {
+    ptr.as_mut().unwrap_unchecked().set_stages(stages);
+}
+
+/// Sweep speed between 0.01 and 10 Hz, otherwise clamps
+#[no_mangle]
+unsafe extern "C" fn phaser_set_rate(ptr: *mut Phaser, hz: f32) {
+    ptr.as_mut().unwrap_unchecked().set_rate(hz);
+}
+
+/// Center frequency of the sweep between 50 Hz and 5 kHz, otherwise clamps
+#[no_mangle]
+unsafe extern "C" fn phaser_set_center(ptr: *mut Phaser, freq: f32) {
+    ptr.as_mut().unwrap_unchecked().set_center(freq);
+}
+
+/// Only accepts values between 0.0 and 1.0, otherwise clamps
+#[no_mangle]
+unsafe extern "C" fn phaser_set_depth(ptr: *mut Phaser, val: f32) {
+    ptr.as_mut().unwrap_unchecked().set_depth(val);
+}
+
+/// Only accepts values between -1.0 and 1.0, otherwise clamps
+#[no_mangle]
+unsafe extern "C" fn phaser_set_feedback(ptr: *mut Phaser, val: f32) {
+    ptr.as_mut().unwrap_unchecked().set_feedback(val);
+}
+
+/// Only accepts values between 0.0 and 1.0, otherwise clamps
+#[no_mangle]
+unsafe extern "C" fn phaser_set_stereo_phase(ptr: *mut Phaser, val: f32) {
+    ptr.as_mut().unwrap_unchecked().set_stereo_phase(val);
+}
+
+/// Dry/Wet mixing
+#[no_mangle]
+unsafe extern "C" fn phaser_set_mix(ptr: *mut Phaser, val: f32) {
+    ptr.as_mut().unwrap_unchecked().set_mix(val);
+}
+
+/// Time in ms that center, depth, feedback and mix changes take
+#[no_mangle]
+unsafe extern "C" fn phaser_set_smoothing_time(ptr: *mut Phaser, ms: f32) {
+    ptr.as_mut().unwrap_unchecked().set_smoothing_time(ms);
+}
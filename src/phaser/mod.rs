@@ -0,0 +1,286 @@
+mod ffi;
+
+use embedded_audio_tools::filter::{Biquad, BiquadCoeffs, Butterworth};
+use libm::exp2f;
+
+use crate::lfo::Lfo;
+use crate::smoothed_param::{SmoothedParam, SmoothingMode, DEFAULT_SMOOTHING_MS};
+
+/// cbindgen:ignore
+const MIN_PHASER_STAGES: usize = 2;
+
+/// cbindgen:ignore
+const MAX_PHASER_STAGES: usize = 12;
+
+/// Samples between coefficient updates, the sweep is slow enough for control rate
+/// cbindgen:ignore
+const PHASER_BLOCK: usize = 32;
+
+/// cbindgen:ignore
+const PHASER_Q: f32 = 0.5;
+
+/// cbindgen:ignore
+const MAX_SWEEP: f32 = 6.0; // octaves, peak to peak
+
+/// cbindgen:ignore
+const MIN_CENTER: f32 = 50.0; // Hz
+
+/// cbindgen:ignore
+const MAX_CENTER: f32 = 5000.0; // Hz
+
+/// cbindgen:ignore
+const MAX_FEEDBACK: f32 = 0.95;
+
+/// cbindgen:ignore
+const MIN_RATE: f32 = 0.01; // Hz
+
+/// cbindgen:ignore
+const MAX_RATE: f32 = 10.0; // Hz
+
+/// Stereo phaser from a chain of swept allpass biquads
+///
+/// All stages of a channel share one set of coefficients, recalculated every 32 samples.
+#[repr(C)]
+pub struct Phaser {
+    stages_l: [Biquad<Butterworth>; 12],
+    stages_r: [Biquad<Butterworth>; 12],
+    lfo: Lfo,
+
+    sr: f32,
+    smoothing_ms: f32,
+    block_countdown: usize,
+    feedback_l: f32,
+    feedback_r: f32,
+
+    stages: usize,
+    stereo_phase: f32,
+    center: SmoothedParam,
+    depth: SmoothedParam,
+    feedback: SmoothedParam,
+    mix: SmoothedParam,
+}
+
+impl Phaser {
+    pub fn init(sr: f32) -> Phaser {
+        let mut phaser = Phaser {
+            stages_l: core::array::from_fn(|_| Biquad::new(BiquadCoeffs::new())),
+            stages_r: core::array::from_fn(|_| Biquad::new(BiquadCoeffs::new())),
+            lfo: Lfo::new(0.3, sr),
+
+            sr,
+            smoothing_ms: DEFAULT_SMOOTHING_MS,
+            block_countdown: 0,
+            feedback_l: 0.0,
+            feedback_r: 0.0,
+
+            stages: 4,
+            stereo_phase: 0.25,
+            center: SmoothedParam::new(800.0, SmoothingMode::OnePole),
+            depth: SmoothedParam::new(0.5, SmoothingMode::OnePole),
+            feedback: SmoothedParam::new(0.0, SmoothingMode::Linear),
+            mix: SmoothedParam::new(0.5, SmoothingMode::Linear),
+        };
+
+        phaser.set_smoothing_time(DEFAULT_SMOOTHING_MS);
+        phaser
+    }
+
+    pub fn tick(&mut self, input: (f32, f32)) -> (f32, f32) {
+        let (center, depth) = (self.center.tick(), self.depth.tick());
+
+        if self.block_countdown == 0 {
+            self.block_countdown = PHASER_BLOCK;
+            self.update_coeffs(center, depth);
+            self.lfo.advance(PHASER_BLOCK);
+        }
+        self.block_countdown -= 1;
+
+        let feedback = self.feedback.tick();
+        let stages = self.stages;
+
+        let wet_l = self.stages_l[..stages]
+            .iter_mut()
+            .fold(input.0 + feedback * self.feedback_l, |signal, stage| {
+                stage.process(signal)
+            });
+        let wet_r = self.stages_r[..stages]
+            .iter_mut()
+            .fold(input.1 + feedback * self.feedback_r, |signal, stage| {
+                stage.process(signal)
+            });
+        (self.feedback_l, self.feedback_r) = (wet_l, wet_r);
+
+        let mix = self.mix.tick();
+        (
+            (1.0 - mix) * input.0 + mix * wet_l,
+            (1.0 - mix) * input.1 + mix * wet_r,
+        )
+    }
+
+    /// Number of allpass stages between 2 and 12, otherwise clamps
+    pub fn set_stages(&mut self, stages: usize) {
+        let stages = stages.clamp(MIN_PHASER_STAGES, MAX_PHASER_STAGES);
+
+        // stages switched back in start from silence instead of their stale state
+        if stages > self.stages {
+            for stage in self.stages_l[self.stages..stages]
+                .iter_mut()
+                .chain(self.stages_r[self.stages..stages].iter_mut())
+            {
+                *stage = Biquad::new(BiquadCoeffs::new());
+            }
+            self.block_countdown = 0;
+        }
+
+        self.stages = stages;
+    }
+
+    /// Sweep speed between 0.01 and 10 Hz, otherwise clamps
+    pub fn set_rate(&mut self, hz: f32) {
+        self.lfo.set_freq(hz.clamp(MIN_RATE, MAX_RATE));
+    }
+
+    /// Center frequency of the sweep between 50 Hz and 5 kHz, otherwise clamps
+    pub fn set_center(&mut self, freq: f32) {
+        self.center.set_target(freq.clamp(MIN_CENTER, MAX_CENTER));
+    }
+
+    /// Sweep range up to six octaves. Only accepts values between 0.0 and 1.0, otherwise clamps
+    pub fn set_depth(&mut self, val: f32) {
+        self.depth.set_target(val.clamp(0.0, 1.0));
+    }
+
+    /// Negative feedback moves the notches. Only accepts values between -1.0 and 1.0, otherwise clamps
+    pub fn set_feedback(&mut self, val: f32) {
+        self.feedback
+            .set_target(val.clamp(-1.0, 1.0) * MAX_FEEDBACK);
+    }
+
+    /// LFO phase offset of the right channel, 1.0 is half a period. Only accepts values between 0.0 and 1.0, otherwise clamps
+    pub fn set_stereo_phase(&mut self, val: f32) {
+        self.stereo_phase = val.clamp(0.0, 1.0);
+    }
+
+    /// Dry/Wet mixing, 0.5 gives the deepest notches. Only accepts values between 0.0 and 1.0, otherwise clamps
+    pub fn set_mix(&mut self, val: f32) {
+        self.mix.set_target(val.clamp(0.0, 1.0));
+    }
+
+    /// Time in ms that center, depth, feedback and mix changes take
+    pub fn set_smoothing_time(&mut self, ms: f32) {
+        self.smoothing_ms = ms;
+        for param in [
+            &mut self.center,
+            &mut self.depth,
+            &mut self.feedback,
+            &mut self.mix,
+        ] {
+            param.set_time_ms(ms, self.sr);
+        }
+    }
+
+    pub fn update_sr(&mut self, sr: f32) {
+        self.sr = sr;
+        self.set_smoothing_time(self.smoothing_ms);
+        self.lfo.set_sr(sr);
+        self.block_countdown = 0;
+    }
+
+    fn update_coeffs(&mut self, center: f32, depth: f32) {
+        let half_sweep = 0.5 * MAX_SWEEP * depth;
+        let max_fc = 0.45 * self.sr;
+
+        let fc_l = (center * exp2f(half_sweep * self.lfo.value_at(0.0))).min(max_fc);
+        let fc_r =
+            (center * exp2f(half_sweep * self.lfo.value_at(0.5 * self.stereo_phase))).min(max_fc);
+
+        share_allpass(&mut self.stages_l, fc_l, self.sr);
+        share_allpass(&mut self.stages_r, fc_r, self.sr);
+    }
+}
+
+/// Calculates the allpass coefficients once and copies them into every stage
+fn share_allpass(stages: &mut [Biquad<Butterworth>], fc: f32, sr: f32) {
+    let mut coeffs = BiquadCoeffs::<Butterworth>::new();
+    coeffs.allpass(fc, PHASER_Q, sr);
+
+    for stage in stages.iter_mut() {
+        stage.coeffs.b0 = coeffs.b0;
+        stage.coeffs.b1 = coeffs.b1;
+        stage.coeffs.b2 = coeffs.b2;
+        stage.coeffs.a1 = coeffs.a1;
+        stage.coeffs.a2 = coeffs.a2;
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    use core::f32::consts::PI;
+    use libm::sinf;
+
+    use crate::log_spaced_freq;
+
+    const SR: f32 = 48000.0;
+
+    /// Peak of the left output for a settled sine at `freq`
+    fn sine_peak(phaser: &mut Phaser, freq: f32) -> f32 {
+        let mut peak = 0.0_f32;
+        for i in 0..SR as usize / 10 {
+            let x = sinf(2.0 * PI * freq * i as f32 / SR);
+            let out = phaser.tick((x, x));
+            if i > SR as usize / 20 {
+                peak = peak.max(out.0.abs());
+            }
+        }
+        peak
+    }
+
+    #[test]
+    fn cancels_some_frequencies() {
+        let levels: [f32; 48] = core::array::from_fn(|i| {
+            let mut phaser = Phaser::init(SR);
+            phaser.set_smoothing_time(0.0);
+            phaser.set_depth(0.0);
+            phaser.set_stages(2);
+            phaser.set_mix(0.5);
+            sine_peak(&mut phaser, log_spaced_freq(i, 48, 50.0, 20000.0))
+        });
+
+        // the allpasses never boost, but mixed with the dry signal they notch
+        assert!(levels.iter().all(|x| *x <= 1.0 + 1e-3));
+        assert!(levels.iter().any(|x| *x < 0.2));
+        assert!(levels.iter().any(|x| *x > 0.9));
+    }
+
+    #[test]
+    fn notch_follows_the_lfo() {
+        let mut phaser = Phaser::init(SR);
+        phaser.set_smoothing_time(0.0);
+        phaser.set_stages(2);
+        phaser.set_depth(1.0);
+        phaser.set_rate(2.0);
+        phaser.set_stereo_phase(0.5);
+
+        // levels of a sine in 10 ms windows over one LFO period
+        let mut levels = [(0.0_f32, 0.0_f32); 50];
+        for i in 0..SR as usize {
+            let x = sinf(2.0 * PI * 800.0 * i as f32 / SR);
+            let out = phaser.tick((x, x));
+            if i >= SR as usize / 2 {
+                let level = &mut levels[(i / 480) % 50];
+                level.0 = level.0.max(out.0.abs());
+                level.1 = level.1.max(out.1.abs());
+            }
+        }
+
+        let (min_l, max_l) = levels.iter().fold((f32::MAX, 0.0_f32), |(min, max), x| {
+            (min.min(x.0), max.max(x.0))
+        });
+        assert!(max_l > 4.0 * min_l);
+
+        // the right channel sweeps a quarter period apart
+        assert!(levels.iter().any(|x| x.1 > 4.0 * x.0 || x.0 > 4.0 * x.1));
+    }
+}
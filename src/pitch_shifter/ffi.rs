@@ -0,0 +1,80 @@
+use crate::PitchShifter;
+
+/// Initializes `PitchShifter` struct
+#[no_mangle]
+extern "C" fn pitch_shifter_init(sr: f32) -> PitchShifter {
+    PitchShifter::init(sr)
+}
+
+/// Initializes `PitchShifter` delay buffer. `length` needs to be `>= 4803` for `sr = 48000`.
+#[no_mangle]
+unsafe extern "C" fn pitch_shifter_set_buffer(
+    ptr: *mut PitchShifter,
+    buffer: *mut f32,
+    length: usize,
+) {
+    ptr.as_mut()
+        .unwrap_unchecked()
+        .set_buffer(core::slice::from_raw_parts_mut(buffer, length));
+}
+
+/// Returns next sample
+#[no_mangle]
+unsafe extern "C" fn pitch_shifter_tick(ptr: *mut PitchShifter, sample: f32) -> f32 {
+    ptr.as_mut().unwrap_unchecked().tick(sample)
+}
+
+/// Shift of the first voice, between -24 and 24 semitones in total, otherwise clamps
+#[no_mangle]
+unsafe extern "C" fn pitch_shifter_set_shift(ptr: *mut PitchShifter, semitones: f32, cents: f32) {
+    ptr.as_mut().unwrap_unchecked().set_shift(semitones, cents);
+}
+
+/// Number of harmonizer voices between 1 and 4, otherwise clamps
+#[no_mangle]
+unsafe extern "C" fn pitch_shifter_set_voices(ptr: *mut PitchShifter, voices: usize) {
+    ptr.as_mut().unwrap_unchecked().set_voices(voices);
+}
+
+/// Shift of the voice at `index`, between -24 and 24 semitones in total, otherwise clamps
+#[no_mangle]
+unsafe extern "C" fn pitch_shifter_set_voice_shift(
+    ptr: *mut PitchShifter,
+    index: usize,
+    semitones: f32,
+    cents: f32,
+) {
+    ptr.as_mut()
+        .unwrap_unchecked()
+        .set_voice_shift(index, semitones, cents);
+}
+
+/// Level of the voice at `index`. Only accepts values between 0.0 and 1.0, otherwise clamps
+#[no_mangle]
+unsafe extern "C" fn pitch_shifter_set_voice_gain(ptr: *mut PitchShifter, index: usize, val: f32) {
+    ptr.as_mut().unwrap_unchecked().set_voice_gain(index, val);
+}
+
+/// Length of the read head sweep between 10 and 100 ms, otherwise clamps
+#[no_mangle]
+unsafe extern "C" fn pitch_shifter_set_window(ptr: *mut PitchShifter, ms: f32) {
+    ptr.as_mut().unwrap_unchecked().set_window(ms);
+}
+
+/// Average delay of the shifted voices in samples
+#[no_mangle]
+unsafe extern "C" fn pitch_shifter_latency_samples(ptr: *const PitchShifter) -> f32 {
+    ptr.as_ref().unwrap_unchecked().latency_samples()
+}
+
+/// Dry/Wet mixing
+#[no_mangle]
+unsafe extern "C" fn pitch_shifter_set_mix(ptr: *mut PitchShifter, val: f32) {
+    ptr.as_mut().unwrap_unchecked().set_mix(val);
+}
+
+/// Time in ms that voice gain and mix changes take
+#[no_mangle]
+unsafe extern "C" fn pitch_shifter_set_smoothing_time(ptr: *mut PitchShifter, ms: f32) {
+    ptr.as_mut().unwrap_unchecked().set_smoothing_time(ms);
+}
@@ -0,0 +1,233 @@
+mod ffi;
+
+use libm::{exp2f, floorf};
+
+use crate::smoothed_param::{SmoothedParam, SmoothingMode, DEFAULT_SMOOTHING_MS};
+use crate::tools::{
+    memory_access::{from_slice_mut, null_mut},
+    stereo::crossfade_correlated_unchecked,
+    DelayLine,
+};
+
+/// cbindgen:ignore
+const MAX_VOICES: usize = 4;
+
+/// cbindgen:ignore
+const MAX_SHIFT: f32 = 24.0; // semitones
+
+/// cbindgen:ignore
+const SHORTEST_WINDOW: f32 = 10.0; // ms
+
+/// cbindgen:ignore
+const LONGEST_WINDOW: f32 = 100.0; // ms
+
+/// Both read heads stay behind the write head by at least this
+/// cbindgen:ignore
+const MIN_DELAY_SAMPLES: f32 = 1.0;
+
+/// Two read heads half a window apart, sweeping through the delay line at the pitch ratio
+#[repr(C)]
+struct ShiftVoice {
+    phase: f32,
+    ratio: f32,
+    gain: SmoothedParam,
+}
+
+impl ShiftVoice {
+    fn new() -> ShiftVoice {
+        ShiftVoice {
+            phase: 0.0,
+            ratio: 1.0,
+            gain: SmoothedParam::new(1.0, SmoothingMode::Linear),
+        }
+    }
+
+    #[inline]
+    fn tick(&mut self, delay_line: &DelayLine, window: f32) -> f32 {
+        let phase_b = if self.phase < 0.5 {
+            self.phase + 0.5
+        } else {
+            self.phase - 0.5
+        };
+
+        let read =
+            |phase: f32| delay_line.read_lerp_wrapped_at(-(MIN_DELAY_SAMPLES + phase * window));
+        let (head_a, head_b) = (read(self.phase), read(phase_b));
+
+        // each head fades in and out with a triangle over its sweep, the jump back happens when it is silent
+        let gain_a = 1.0 - (2.0 * self.phase - 1.0).abs();
+        let shifted = crossfade_correlated_unchecked(1.0 - 2.0 * gain_a, (head_a, head_b));
+
+        // a higher pitch reads faster than the write head, so the delay shrinks
+        self.phase += (1.0 - self.ratio) / window;
+        self.phase -= floorf(self.phase);
+
+        shifted * self.gain.tick()
+    }
+}
+
+/// Delay line based pitch shifter with up to four harmonizer voices
+///
+/// Every voice has its own pair of crossfaded read heads on the shared delay line.
+#[repr(C)]
+pub struct PitchShifter {
+    delay_line: DelayLine,
+    voices: [ShiftVoice; 4],
+
+    sr: f32,
+    smoothing_ms: f32,
+    active_voices: usize,
+    window_ms: f32,
+    window: f32,
+    mix: SmoothedParam,
+}
+
+impl PitchShifter {
+    pub fn init(sr: f32) -> PitchShifter {
+        let mut pitch_shifter = PitchShifter {
+            delay_line: DelayLine::new(null_mut()),
+            voices: core::array::from_fn(|_| ShiftVoice::new()),
+
+            sr,
+            smoothing_ms: DEFAULT_SMOOTHING_MS,
+            active_voices: 1,
+            window_ms: 50.0,
+            window: 0.0,
+            mix: SmoothedParam::new(1.0, SmoothingMode::Linear),
+        };
+
+        pitch_shifter.set_smoothing_time(DEFAULT_SMOOTHING_MS);
+        pitch_shifter
+    }
+
+    /// Needs `length >= 0.1 * sr + 3` for the longest window, e.g. 4803 for `sr = 48000`
+    pub fn set_buffer(&mut self, buffer: &mut [f32]) {
+        self.delay_line.change_buffer(from_slice_mut(buffer));
+        self.set_window(self.window_ms);
+    }
+
+    pub fn tick(&mut self, input: f32) -> f32 {
+        self.delay_line.write_and_advance(input);
+
+        let window = self.window;
+        let wet: f32 = self.voices[..self.active_voices]
+            .iter_mut()
+            .map(|voice| voice.tick(&self.delay_line, window))
+            .sum();
+
+        let mix = self.mix.tick();
+        (1.0 - mix) * input + mix * wet
+    }
+
+    /// Shift of the first voice, between -24 and 24 semitones in total, otherwise clamps
+    pub fn set_shift(&mut self, semitones: f32, cents: f32) {
+        self.set_voice_shift(0, semitones, cents);
+    }
+
+    /// Number of harmonizer voices between 1 and 4, otherwise clamps
+    pub fn set_voices(&mut self, voices: usize) {
+        self.active_voices = voices.clamp(1, MAX_VOICES);
+    }
+
+    /// Shift of the voice at `index`, between -24 and 24 semitones in total, otherwise clamps. Ignores invalid indices.
+    pub fn set_voice_shift(&mut self, index: usize, semitones: f32, cents: f32) {
+        if let Some(voice) = self.voices.get_mut(index) {
+            let shift = (semitones + 0.01 * cents).clamp(-MAX_SHIFT, MAX_SHIFT);
+            voice.ratio = exp2f(shift / 12.0);
+        }
+    }
+
+    /// Level of the voice at `index`. Only accepts values between 0.0 and 1.0, otherwise clamps. Ignores invalid indices.
+    pub fn set_voice_gain(&mut self, index: usize, val: f32) {
+        if let Some(voice) = self.voices.get_mut(index) {
+            voice.gain.set_target(val.clamp(0.0, 1.0));
+        }
+    }
+
+    /// Length of the read head sweep between 10 and 100 ms, otherwise clamps
+    ///
+    /// Longer windows suit low voices, shorter ones smear transients less. Limited by the buffer length.
+    pub fn set_window(&mut self, ms: f32) {
+        self.window_ms = ms.clamp(SHORTEST_WINDOW, LONGEST_WINDOW);
+
+        let max_window = self.delay_line.len().saturating_sub(3) as f32;
+        self.window = (self.window_ms * 0.001 * self.sr).min(max_window).max(1.0);
+    }
+
+    /// Average delay of the shifted voices in samples
+    pub fn latency_samples(&self) -> f32 {
+        MIN_DELAY_SAMPLES + 0.5 * self.window
+    }
+
+    /// Dry/Wet mixing. Only accepts values between 0.0 and 1.0, otherwise clamps
+    pub fn set_mix(&mut self, val: f32) {
+        self.mix.set_target(val.clamp(0.0, 1.0));
+    }
+
+    /// Time in ms that voice gain and mix changes take
+    pub fn set_smoothing_time(&mut self, ms: f32) {
+        self.smoothing_ms = ms;
+        self.mix.set_time_ms(ms, self.sr);
+        for voice in &mut self.voices {
+            voice.gain.set_time_ms(ms, self.sr);
+        }
+    }
+
+    pub fn update_sr(&mut self, sr: f32) {
+        self.sr = sr;
+        self.set_smoothing_time(self.smoothing_ms);
+        self.set_window(self.window_ms);
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    const SR: f32 = 48000.0;
+
+    /// Magnitude of `freq` in the output of a shifted 200 Hz sine
+    fn magnitude(pitch_shifter: &mut PitchShifter, freq: f32) -> f32 {
+        let output: [f32; SR as usize / 5] = core::array::from_fn(|i| {
+            pitch_shifter.tick(libm::sinf(
+                2.0 * core::f32::consts::PI * 200.0 * i as f32 / SR,
+            ))
+        });
+
+        let (re, im) = output
+            .iter()
+            .enumerate()
+            .skip(4800)
+            .fold((0.0, 0.0), |(re, im), (i, x)| {
+                let w = 2.0 * core::f32::consts::PI * freq * i as f32 / SR;
+                (re + x * libm::cosf(w), im + x * libm::sinf(w))
+            });
+        libm::sqrtf(re * re + im * im)
+    }
+
+    #[test]
+    fn octave_up() {
+        let mut buffer = [0.0; 4803];
+        let mut pitch_shifter = PitchShifter::init(SR);
+        pitch_shifter.set_buffer(&mut buffer);
+        pitch_shifter.set_shift(12.0, 0.0);
+
+        let octave = magnitude(&mut pitch_shifter, 400.0);
+        assert!(octave > 10.0 * magnitude(&mut pitch_shifter, 200.0));
+    }
+
+    #[test]
+    fn harmonizer_adds_voices() {
+        let mut buffer = [0.0; 4803];
+        let mut pitch_shifter = PitchShifter::init(SR);
+        pitch_shifter.set_buffer(&mut buffer);
+        pitch_shifter.set_shift(0.0, 0.0);
+        pitch_shifter.set_voices(2);
+        pitch_shifter.set_voice_shift(1, 7.0, 2.0);
+
+        // a fifth above 200 Hz
+        let fifth = magnitude(&mut pitch_shifter, 200.0 * exp2f(7.02 / 12.0));
+        assert!(fifth > 0.2 * magnitude(&mut pitch_shifter, 200.0));
+        assert!(magnitude(&mut pitch_shifter, 200.0) > 1.0);
+    }
+}
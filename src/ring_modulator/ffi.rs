@@ -0,0 +1,62 @@
+use crate::tools::Waveform;
+use crate::RingModulator;
+
+/// Initializes `RingModulator` struct
+#[no_mangle]
+extern "C" fn ring_modulator_init(sr: f32) -> RingModulator {
+    RingModulator::init(sr)
+}
+
+/// Returns next sample
+#[no_mangle]
+unsafe extern "C" fn ring_modulator_tick(ptr: *mut RingModulator, sample: f32) -> f32 {
+    ptr.as_mut().unwrap_unchecked().tick(sample)
+}
+
+/// Returns next sample modulated by `carrier` instead of the internal oscillator
+#[no_mangle]
+unsafe extern "C" fn ring_modulator_tick_with_sidechain(
+    ptr: *mut RingModulator,
+    sample: f32,
+    carrier: f32,
+) -> f32 {
+    ptr.as_mut()
+        .unwrap_unchecked()
+        .tick_with_sidechain(sample, carrier)
+}
+
+/// Carrier frequency between 0.1 Hz and 5 kHz, otherwise clamps
+#[no_mangle]
+unsafe extern "C" fn ring_modulator_set_freq(ptr: *mut RingModulator, hz: f32) {
+    ptr.as_mut().unwrap_unchecked().set_freq(hz);
+}
+
+/// One carrier period every `beats` beats at `bpm`
+#[no_mangle]
+unsafe extern "C" fn ring_modulator_set_tempo_sync(ptr: *mut RingModulator, bpm: f32, beats: f32) {
+    ptr.as_mut().unwrap_unchecked().set_tempo_sync(bpm, beats);
+}
+
+/// Restarts the carrier
+#[no_mangle]
+unsafe extern "C" fn ring_modulator_reset_phase(ptr: *mut RingModulator) {
+    ptr.as_mut().unwrap_unchecked().reset_phase();
+}
+
+/// Shape of the carrier
+#[no_mangle]
+unsafe extern "C" fn ring_modulator_set_waveform(ptr: *mut RingModulator, wave: Waveform) {
+    ptr.as_mut().unwrap_unchecked().set_waveform(wave);
+}
+
+/// Dry/Wet mixing
+#[no_mangle]
+unsafe extern "C" fn ring_modulator_set_mix(ptr: *mut RingModulator, val: f32) {
+    ptr.as_mut().unwrap_unchecked().set_mix(val);
+}
+
+/// Time in ms that mix changes take
+#[no_mangle]
+unsafe extern "C" fn ring_modulator_set_smoothing_time(ptr: *mut RingModulator, ms: f32) {
+    ptr.as_mut().unwrap_unchecked().set_smoothing_time(ms);
+}
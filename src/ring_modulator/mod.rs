@@ -0,0 +1,104 @@
+mod ffi;
+
+use crate::lfo::{tempo_sync_freq, Lfo};
+use crate::smoothed_param::{SmoothedParam, SmoothingMode, DEFAULT_SMOOTHING_MS};
+use crate::tools::Waveform;
+
+/// cbindgen:ignore
+const MIN_CARRIER: f32 = 0.1; // Hz
+
+/// cbindgen:ignore
+const MAX_CARRIER: f32 = 5000.0; // Hz
+
+/// Multiplies the input with an internal carrier or with a sidechain signal
+///
+/// Slow carriers also work as a tremolo through zero, with the same tempo sync.
+#[repr(C)]
+pub struct RingModulator {
+    carrier: Lfo,
+
+    sr: f32,
+    smoothing_ms: f32,
+    mix: SmoothedParam,
+}
+
+impl RingModulator {
+    pub fn init(sr: f32) -> RingModulator {
+        let mut ring_modulator = RingModulator {
+            carrier: Lfo::new(440.0, sr),
+
+            sr,
+            smoothing_ms: DEFAULT_SMOOTHING_MS,
+            mix: SmoothedParam::new(1.0, SmoothingMode::Linear),
+        };
+
+        ring_modulator.set_smoothing_time(DEFAULT_SMOOTHING_MS);
+        ring_modulator
+    }
+
+    pub fn tick(&mut self, input: f32) -> f32 {
+        let carrier = self.carrier.tick();
+        self.tick_with_sidechain(input, carrier)
+    }
+
+    /// Modulates `input` with `carrier` instead of the internal oscillator
+    pub fn tick_with_sidechain(&mut self, input: f32, carrier: f32) -> f32 {
+        let mix = self.mix.tick();
+        (1.0 - mix) * input + mix * input * carrier
+    }
+
+    /// Carrier frequency between 0.1 Hz and 5 kHz, otherwise clamps
+    pub fn set_freq(&mut self, hz: f32) {
+        self.carrier.set_freq(hz.clamp(MIN_CARRIER, MAX_CARRIER));
+    }
+
+    /// One carrier period every `beats` beats at `bpm`, within the same limits as the frequency
+    pub fn set_tempo_sync(&mut self, bpm: f32, beats: f32) {
+        if let Some(hz) = tempo_sync_freq(bpm, beats) {
+            self.set_freq(hz);
+        }
+    }
+
+    /// Restarts the carrier
+    pub fn reset_phase(&mut self) {
+        self.carrier.reset_phase(0.0);
+    }
+
+    /// Shape of the carrier, without band limiting
+    pub fn set_waveform(&mut self, wave: Waveform) {
+        self.carrier.set_waveform(wave);
+    }
+
+    /// Dry/Wet mixing. Only accepts values between 0.0 and 1.0, otherwise clamps
+    pub fn set_mix(&mut self, val: f32) {
+        self.mix.set_target(val.clamp(0.0, 1.0));
+    }
+
+    /// Time in ms that mix changes take
+    pub fn set_smoothing_time(&mut self, ms: f32) {
+        self.smoothing_ms = ms;
+        self.mix.set_time_ms(ms, self.sr);
+    }
+
+    pub fn update_sr(&mut self, sr: f32) {
+        self.sr = sr;
+        self.set_smoothing_time(self.smoothing_ms);
+        self.carrier.set_sr(sr);
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn sidechain_carrier() {
+        let mut ring_modulator = RingModulator::init(48000.0);
+        ring_modulator.set_smoothing_time(0.0);
+
+        assert_eq!(ring_modulator.tick_with_sidechain(0.5, -0.5), -0.25);
+
+        ring_modulator.set_mix(0.5);
+        assert_eq!(ring_modulator.tick_with_sidechain(0.5, 0.0), 0.25);
+    }
+}
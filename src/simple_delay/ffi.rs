@@ -1,4 +1,4 @@
-use crate::SimpleDelay;
+use crate::{SimpleDelay, Waveshaper};
 
 /// Initializes `SimpleDelay` struct
 #[no_mangle]
@@ -44,3 +44,27 @@ unsafe extern "C" fn simple_delay_set_mix(ptr: *mut SimpleDelay, mix: f32) {
         .set_dry(1.0 - mix.clamp(0.0, 1.0));
     ptr.as_mut().unwrap_unchecked().set_wet(mix);
 }
+
+/// Time in ms that feedback and mix changes take
+#[no_mangle]
+unsafe extern "C" fn simple_delay_set_smoothing_time(ptr: *mut SimpleDelay, ms: f32) {
+    ptr.as_mut().unwrap_unchecked().set_smoothing_time(ms);
+}
+
+/// Sample rate the smoothing time refers to, 48 kHz by default
+#[no_mangle]
+unsafe extern "C" fn simple_delay_update_sr(ptr: *mut SimpleDelay, sr: f32) {
+    ptr.as_mut().unwrap_unchecked().update_sr(sr);
+}
+
+/// Linear drive of the saturation inside the feedback loop. Values below 1.0 are clamped, 1.0 bypasses it
+#[no_mangle]
+unsafe extern "C" fn simple_delay_set_feedback_drive(ptr: *mut SimpleDelay, drive: f32) {
+    ptr.as_mut().unwrap_unchecked().set_feedback_drive(drive);
+}
+
+/// Curve of the saturation inside the feedback loop
+#[no_mangle]
+unsafe extern "C" fn simple_delay_set_feedback_shape(ptr: *mut SimpleDelay, shape: Waveshaper) {
+    ptr.as_mut().unwrap_unchecked().set_feedback_shape(shape);
+}
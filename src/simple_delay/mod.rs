@@ -2,45 +2,67 @@ mod ffi;
 
 use core::ops::Neg;
 
+use crate::smoothed_param::{SmoothedParam, SmoothingMode, DEFAULT_SMOOTHING_MS};
 use crate::tools::{
     memory_access::{from_slice_mut, null_mut},
     stereo::crossfade_correlated_unchecked,
     DelayLine,
 };
+use crate::waveshaper::Waveshaper;
 
 /// cbindgen:ignore
 const MIN_DELAY_SAMPLES: f32 = 4.0;
 
+/// Sample rate the smoothing time refers to until `update_sr` is called
+/// cbindgen:ignore
+const DEFAULT_SR: f32 = 48000.0;
+
+/// cbindgen:ignore
+const LOWEST_FEEDBACK_DRIVE: f32 = 1.0;
+
 #[repr(C)]
 pub struct SimpleDelay {
     delay_line: crate::tools::DelayLine,
 
     delay_samples: f32,
-    feedback: f32,
-    dry_gain: f32,
-    wet_gain: f32,
+    feedback: SmoothedParam,
+    dry_gain: SmoothedParam,
+    wet_gain: SmoothedParam,
+    feedback_drive: f32,
+    feedback_shape: Waveshaper,
 
     delay_time_changed: bool,
     last_delay_samples: f32,
     crossfade_counter: usize,
     crossfade_samples: usize,
+
+    sr: f32,
+    smoothing_ms: f32,
 }
 
 impl SimpleDelay {
     pub fn init() -> SimpleDelay {
-        SimpleDelay {
+        let mut delay = SimpleDelay {
             delay_line: (DelayLine::new(null_mut())),
 
             delay_samples: MIN_DELAY_SAMPLES,
-            feedback: 0.5,
-            dry_gain: 0.0,
-            wet_gain: 1.0,
+            feedback: SmoothedParam::new(0.5, SmoothingMode::Linear),
+            dry_gain: SmoothedParam::new(0.0, SmoothingMode::Linear),
+            wet_gain: SmoothedParam::new(1.0, SmoothingMode::Linear),
+            feedback_drive: LOWEST_FEEDBACK_DRIVE,
+            feedback_shape: Waveshaper::Tanh,
 
             delay_time_changed: false,
             last_delay_samples: 0.0,
             crossfade_counter: 0,
             crossfade_samples: 480,
-        }
+
+            sr: DEFAULT_SR,
+            smoothing_ms: DEFAULT_SMOOTHING_MS,
+        };
+
+        delay.set_smoothing_time(DEFAULT_SMOOTHING_MS);
+        delay
     }
 
     ///////////////////////////////////////////////////////////////////////////////
@@ -48,11 +70,12 @@ impl SimpleDelay {
     ///////////////////////////////////////////////////////////////////////////////
 
     pub fn tick(&mut self, input: f32) -> f32 {
-        let output = self.get_delayed_sample() * self.feedback;
+        let output = self.get_delayed_sample() * self.feedback.tick();
 
-        self.delay_line.write_and_advance(input + output);
+        self.delay_line
+            .write_and_advance(self.saturate(input + output));
 
-        self.dry_gain * input + self.wet_gain * output
+        self.dry_gain.tick() * input + self.wet_gain.tick() * output
     }
 
     pub fn set_buffer(&mut self, buffer: &mut [f32]) {
@@ -74,15 +97,25 @@ impl SimpleDelay {
     }
 
     pub fn set_feedback(&mut self, feedback: f32) {
-        self.feedback = feedback.clamp(0.0, 1.0);
+        self.feedback.set_target(feedback.clamp(0.0, 1.0));
     }
 
     pub fn set_dry(&mut self, dry_gain: f32) {
-        self.dry_gain = dry_gain.clamp(0.0, 1.0);
+        self.dry_gain.set_target(dry_gain.clamp(0.0, 1.0));
     }
 
     pub fn set_wet(&mut self, wet_gain: f32) {
-        self.wet_gain = wet_gain.clamp(0.0, 1.0);
+        self.wet_gain.set_target(wet_gain.clamp(0.0, 1.0));
+    }
+
+    /// Linear drive of the saturation inside the feedback loop. Values below 1.0 are clamped, 1.0 bypasses it.
+    pub fn set_feedback_drive(&mut self, drive: f32) {
+        self.feedback_drive = drive.max(LOWEST_FEEDBACK_DRIVE);
+    }
+
+    /// Curve of the saturation inside the feedback loop
+    pub fn set_feedback_shape(&mut self, shape: Waveshaper) {
+        self.feedback_shape = shape;
     }
 
     /// Sets the crossfade time
@@ -92,6 +125,20 @@ impl SimpleDelay {
         self.crossfade_samples = fade_samples;
     }
 
+    /// Time in ms that feedback and mix changes take
+    pub fn set_smoothing_time(&mut self, ms: f32) {
+        self.smoothing_ms = ms;
+        self.feedback.set_time_ms(ms, self.sr);
+        self.dry_gain.set_time_ms(ms, self.sr);
+        self.wet_gain.set_time_ms(ms, self.sr);
+    }
+
+    /// Only the smoothing depends on the sample rate, the delay and crossfade lengths are in samples
+    pub fn update_sr(&mut self, sr: f32) {
+        self.sr = sr;
+        self.set_smoothing_time(self.smoothing_ms);
+    }
+
     ///////////////////////////////////////////////////////////////////////////////
     /// Private Functions
     ///////////////////////////////////////////////////////////////////////////////
@@ -126,6 +173,16 @@ impl SimpleDelay {
         }
     }
 
+    /// Keeps unity gain for small signals, so only loud repeats get compressed
+    #[inline(always)]
+    fn saturate(&self, input: f32) -> f32 {
+        if self.feedback_drive <= LOWEST_FEEDBACK_DRIVE {
+            return input;
+        }
+
+        self.feedback_shape.process(input * self.feedback_drive) / self.feedback_drive
+    }
+
     #[inline(always)]
     fn get_normalized_bipolar_crossfade(&self) -> f32 {
         (self.crossfade_counter as f32 / self.crossfade_samples as f32) * 2.0 - 1.0
@@ -192,4 +249,20 @@ mod tests {
         delay.crossfade_counter += 1;
         assert_eq!(delay.get_normalized_bipolar_crossfade(), 1.0);
     }
+
+    #[test]
+    fn saturated_feedback_stays_bounded() {
+        let mut buffer = [0_f32; 64];
+        let mut delay = SimpleDelay::init();
+        delay.set_buffer(buffer.as_mut_slice());
+        delay.set_smoothing_time(0.0);
+        delay.set_feedback(1.0);
+        delay.set_delay(32.0);
+        delay.set_feedback_shape(Waveshaper::HardClip);
+        delay.set_feedback_drive(4.0);
+
+        for _ in 0..1000 {
+            assert!(delay.tick(1.0).abs() <= 0.25 + 1e-6);
+        }
+    }
 }
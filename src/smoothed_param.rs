@@ -0,0 +1,185 @@
+use libm::expf;
+
+/// Smoothing time used by the effects until it gets changed
+/// cbindgen:ignore
+pub(crate) const DEFAULT_SMOOTHING_MS: f32 = 10.0;
+
+/// A one-pole filter counts as settled after this many time constants (99 %)
+/// cbindgen:ignore
+const ONE_POLE_TIME_CONSTANTS: f32 = 4.6;
+
+/// Remaining distance to the target, relative to the target, at which a one-pole snaps onto it
+/// cbindgen:ignore
+const ONE_POLE_SNAP: f32 = 1e-5;
+
+#[derive(Clone, Copy, PartialEq)]
+#[repr(C)]
+pub enum SmoothingMode {
+    /// Ramps to the target in exactly the smoothing time
+    Linear,
+    /// Approaches the target exponentially, settling to 99 % within the smoothing time
+    OnePole,
+}
+
+/// Parameter that glides to new values instead of jumping, which avoids zipper noise
+#[derive(Clone, Copy)]
+#[repr(C)]
+pub struct SmoothedParam {
+    current: f32,
+    target: f32,
+    mode: SmoothingMode,
+    time_samples: f32,
+
+    // Linear
+    step: f32,
+    steps_left: usize,
+
+    // One-pole
+    coeff: f32,
+}
+
+impl SmoothedParam {
+    /// Starts settled at `value`, without smoothing until a time is set
+    pub fn new(value: f32, mode: SmoothingMode) -> SmoothedParam {
+        SmoothedParam {
+            current: value,
+            target: value,
+            mode,
+            time_samples: 0.0,
+
+            step: 0.0,
+            steps_left: 0,
+
+            coeff: 1.0,
+        }
+    }
+
+    #[inline]
+    pub fn tick(&mut self) -> f32 {
+        if self.current != self.target {
+            match self.mode {
+                SmoothingMode::Linear => {
+                    self.current += self.step;
+                    self.steps_left = self.steps_left.saturating_sub(1);
+
+                    if self.steps_left == 0 {
+                        self.current = self.target;
+                    }
+                }
+                SmoothingMode::OnePole => {
+                    self.current += self.coeff * (self.target - self.current);
+
+                    if (self.target - self.current).abs()
+                        <= ONE_POLE_SNAP * self.target.abs().max(1.0)
+                    {
+                        self.current = self.target;
+                    }
+                }
+            }
+        }
+
+        self.current
+    }
+
+    /// Current value, without advancing
+    pub fn value(&self) -> f32 {
+        self.current
+    }
+
+    /// Value the parameter is heading to
+    pub fn target(&self) -> f32 {
+        self.target
+    }
+
+    pub fn is_settling(&self) -> bool {
+        self.current != self.target
+    }
+
+    pub fn set_target(&mut self, target: f32) {
+        self.target = target;
+
+        if self.time_samples < 1.0 {
+            self.current = target;
+            return;
+        }
+
+        self.steps_left = self.time_samples as usize;
+        self.step = (target - self.current) / self.steps_left as f32;
+    }
+
+    /// Jumps to `value` without smoothing, e.g. when loading a preset
+    pub fn set_immediate(&mut self, value: f32) {
+        self.current = value;
+        self.target = value;
+        self.steps_left = 0;
+    }
+
+    pub fn set_time_ms(&mut self, ms: f32, sr: f32) {
+        self.set_time_samples(ms.max(0.0) * 0.001 * sr);
+    }
+
+    /// Sample rate depending calculations should be performed earlier!
+    pub fn set_time_samples(&mut self, samples: f32) {
+        self.time_samples = samples.max(0.0);
+        self.coeff = if self.time_samples < 1.0 {
+            1.0
+        } else {
+            1.0 - expf(-ONE_POLE_TIME_CONSTANTS / self.time_samples)
+        };
+
+        // restart a running linear ramp with the new time
+        if self.is_settling() {
+            self.set_target(self.target);
+        }
+    }
+
+    pub fn set_mode(&mut self, mode: SmoothingMode) {
+        self.mode = mode;
+
+        if self.is_settling() {
+            self.set_target(self.target);
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn linear_ramp() {
+        let mut param = SmoothedParam::new(0.0, SmoothingMode::Linear);
+        param.set_time_samples(4.0);
+        param.set_target(1.0);
+
+        let ramp: [f32; 5] = core::array::from_fn(|_| param.tick());
+        assert_eq!(ramp, [0.25, 0.5, 0.75, 1.0, 1.0]);
+        assert!(!param.is_settling());
+    }
+
+    #[test]
+    fn one_pole_settles() {
+        let mut param = SmoothedParam::new(0.0, SmoothingMode::OnePole);
+        param.set_time_ms(10.0, 48000.0);
+        param.set_target(1.0);
+
+        // 99 % within the smoothing time, exactly on target shortly after
+        for _ in 0..480 {
+            param.tick();
+        }
+        assert!(param.value() > 0.98 && param.value() < 1.0);
+
+        for _ in 0..960 {
+            param.tick();
+        }
+        assert_eq!(param.value(), 1.0);
+    }
+
+    #[test]
+    fn zero_time_jumps() {
+        let mut param = SmoothedParam::new(0.0, SmoothingMode::OnePole);
+        param.set_target(0.5);
+        assert_eq!(param.value(), 0.5);
+        assert!(!param.is_settling());
+    }
+}
@@ -0,0 +1,206 @@
+use core::ptr::null_mut;
+
+use super::{amplitude_scale, bin_magnitude, fft_size_for, fill_twiddles, rfft, Window};
+use crate::multi_filter::gain_to_db;
+
+/// Lowest level shown
+/// cbindgen:ignore
+const FLOOR_DB: f32 = -120.0;
+
+/// Frames overlap by 50%
+/// cbindgen:ignore
+const OVERLAP: usize = 2;
+
+/// Input, frame, window and twiddles take the FFT size each, the bins half of it
+/// cbindgen:ignore
+const BUFFER_PARTS: f32 = 4.5;
+
+/// Magnitude spectrum in dB for displays, with a falloff like a peak meter
+///
+/// A sine at full scale shows up at 0 dB in its bin.
+#[repr(C)]
+pub struct SpectrumAnalyzer {
+    input: *mut f32,
+    frame: *mut f32,
+    window_table: *mut f32,
+    twiddles: *mut f32,
+    bins: *mut f32,
+    size: usize,
+
+    sr: f32,
+    window: Window,
+    amplitude_scale: f32,
+    position: usize,
+    hop_countdown: usize,
+    falloff: f32,
+}
+
+/// The parts of the caller's buffer
+struct AnalyzerBuffers<'a> {
+    input: &'a mut [f32],
+    frame: &'a mut [f32],
+    window: &'a mut [f32],
+    twiddles: &'a mut [f32],
+    bins: &'a mut [f32],
+}
+
+impl SpectrumAnalyzer {
+    pub fn init(sr: f32) -> SpectrumAnalyzer {
+        SpectrumAnalyzer {
+            input: null_mut(),
+            frame: null_mut(),
+            window_table: null_mut(),
+            twiddles: null_mut(),
+            bins: null_mut(),
+            size: 0,
+
+            sr,
+            window: Window::Blackman,
+            amplitude_scale: 0.0,
+            position: 0,
+            hop_countdown: 0,
+            falloff: 60.0,
+        }
+    }
+
+    /// Split into input, frame, tables and bins, the FFT size is `length / 4.5` rounded down to a power of two, e.g. 1024 for `length = 4608`
+    pub fn set_buffer(&mut self, buffer: &mut [f32]) {
+        let size = fft_size_for(buffer.len(), BUFFER_PARTS);
+        buffer.fill(0.0);
+
+        let (input, rest) = buffer.split_at_mut(size);
+        let (frame, rest) = rest.split_at_mut(size);
+        let (window_table, rest) = rest.split_at_mut(size);
+        let (twiddles, rest) = rest.split_at_mut(size);
+        let bins = &mut rest[..size / 2];
+        bins.fill(FLOOR_DB);
+        if size > 0 {
+            fill_twiddles(twiddles);
+        }
+
+        self.input = input.as_mut_ptr();
+        self.frame = frame.as_mut_ptr();
+        self.window_table = window_table.as_mut_ptr();
+        self.twiddles = twiddles.as_mut_ptr();
+        self.bins = bins.as_mut_ptr();
+        self.size = size;
+        self.position = 0;
+        self.hop_countdown = size / OVERLAP;
+        self.set_window(self.window);
+    }
+
+    /// Records a sample and updates the bins every half frame
+    pub fn push(&mut self, sample: f32) {
+        if self.size == 0 {
+            return;
+        }
+
+        let (size, scale) = (self.size, self.amplitude_scale);
+        let falloff = self.falloff * (size / OVERLAP) as f32 / self.sr;
+        let position = self.position;
+        let oldest = (position + 1) % size;
+        self.position = oldest;
+
+        self.hop_countdown -= 1;
+        let frame_due = self.hop_countdown == 0;
+        if frame_due {
+            self.hop_countdown = size / OVERLAP;
+        }
+
+        let buffers = self.buffers();
+        buffers.input[position] = sample;
+
+        if frame_due {
+            for (n, x) in buffers.frame.iter_mut().enumerate() {
+                *x = buffers.input[(oldest + n) % size] * buffers.window[n];
+            }
+            rfft(buffers.frame, buffers.twiddles);
+
+            for (k, bin) in buffers.bins.iter_mut().enumerate() {
+                let db = gain_to_db(bin_magnitude(buffers.frame, k) * scale).max(FLOOR_DB);
+                *bin = db.max(*bin - falloff);
+            }
+        }
+    }
+
+    /// Levels in dB of the bins from DC up to just below Nyquist
+    pub fn bins(&self) -> &[f32] {
+        if self.size == 0 {
+            return &[];
+        }
+
+        unsafe { core::slice::from_raw_parts(self.bins, self.size / 2) }
+    }
+
+    /// Center frequency of bin `index` in Hz
+    pub fn bin_freq(&self, index: usize) -> f32 {
+        if self.size == 0 {
+            return 0.0;
+        }
+
+        index as f32 * self.sr / self.size as f32
+    }
+
+    /// Hann resolves close frequencies better, Blackman shows a wider dynamic range
+    pub fn set_window(&mut self, window: Window) {
+        self.window = window;
+        self.amplitude_scale = amplitude_scale(window, self.size);
+
+        if self.size == 0 {
+            return;
+        }
+
+        let size = self.size;
+        for (n, x) in self.buffers().window.iter_mut().enumerate() {
+            *x = window.value(n, size);
+        }
+    }
+
+    /// Speed in dB per second at which the bins fall after a peak
+    pub fn set_falloff(&mut self, db_per_second: f32) {
+        self.falloff = db_per_second.max(0.0);
+    }
+
+    pub fn update_sr(&mut self, sr: f32) {
+        self.sr = sr;
+    }
+
+    fn buffers(&mut self) -> AnalyzerBuffers<'_> {
+        // parts of the caller's buffer, which never overlap
+        unsafe {
+            AnalyzerBuffers {
+                input: core::slice::from_raw_parts_mut(self.input, self.size),
+                frame: core::slice::from_raw_parts_mut(self.frame, self.size),
+                window: core::slice::from_raw_parts_mut(self.window_table, self.size),
+                twiddles: core::slice::from_raw_parts_mut(self.twiddles, self.size),
+                bins: core::slice::from_raw_parts_mut(self.bins, self.size / 2),
+            }
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn sine_peaks_in_its_bin() {
+        let sr = 48000.0;
+        let mut buffer = [0.0; 1152];
+        let mut analyzer = SpectrumAnalyzer::init(sr);
+        analyzer.set_buffer(&mut buffer);
+        assert_eq!(analyzer.bins().len(), 128);
+
+        // the onset of the sine is broadband, it must not linger
+        analyzer.set_falloff(1.0e6);
+
+        let freq = analyzer.bin_freq(20);
+        for i in 0..2048 {
+            analyzer.push(0.5 * libm::sinf(2.0 * core::f32::consts::PI * freq * i as f32 / sr));
+        }
+
+        let bins = analyzer.bins();
+        assert!((bins[20] - gain_to_db(0.5)).abs() < 0.1);
+        assert!(bins[40] < -60.0);
+    }
+}
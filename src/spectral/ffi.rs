@@ -0,0 +1,111 @@
+use super::{SpectralGate, SpectrumAnalyzer, Window};
+
+///////////////////////////////////////////////////////////////////////////////
+// Spectral Gate
+///////////////////////////////////////////////////////////////////////////////
+
+/// Initializes `SpectralGate` struct
+#[no_mangle]
+extern "C" fn spectral_gate_init() -> SpectralGate {
+    SpectralGate::init()
+}
+
+/// Initializes `SpectralGate` buffers. The FFT size is `length / 5.25` rounded down to a power of two.
+#[no_mangle]
+unsafe extern "C" fn spectral_gate_set_buffer(
+    ptr: *mut SpectralGate,
+    buffer: *mut f32,
+    length: usize,
+) {
+    ptr.as_mut()
+        .unwrap_unchecked()
+        .set_buffer(core::slice::from_raw_parts_mut(buffer, length));
+}
+
+/// Returns next sample
+#[no_mangle]
+unsafe extern "C" fn spectral_gate_tick(ptr: *mut SpectralGate, sample: f32) -> f32 {
+    ptr.as_mut().unwrap_unchecked().tick(sample)
+}
+
+/// Level in dB a sine needs in its bin to pass unchanged
+#[no_mangle]
+unsafe extern "C" fn spectral_gate_set_threshold(ptr: *mut SpectralGate, db: f32) {
+    ptr.as_mut().unwrap_unchecked().set_threshold(db);
+}
+
+/// Largest attenuation in dB between 0 and 80, otherwise clamps
+#[no_mangle]
+unsafe extern "C" fn spectral_gate_set_reduction(ptr: *mut SpectralGate, db: f32) {
+    ptr.as_mut().unwrap_unchecked().set_reduction(db);
+}
+
+#[no_mangle]
+unsafe extern "C" fn spectral_gate_set_window(ptr: *mut SpectralGate, window: Window) {
+    ptr.as_mut().unwrap_unchecked().set_window(window);
+}
+
+/// Delay of the output in samples, the FFT size
+#[no_mangle]
+unsafe extern "C" fn spectral_gate_latency_samples(ptr: *const SpectralGate) -> usize {
+    ptr.as_ref().unwrap_unchecked().latency_samples()
+}
+
+///////////////////////////////////////////////////////////////////////////////
+// Spectrum Analyzer
+///////////////////////////////////////////////////////////////////////////////
+
+/// Initializes `SpectrumAnalyzer` struct
+#[no_mangle]
+extern "C" fn spectrum_analyzer_init(sr: f32) -> SpectrumAnalyzer {
+    SpectrumAnalyzer::init(sr)
+}
+
+/// Initializes `SpectrumAnalyzer` buffers. The FFT size is `length / 4.5` rounded down to a power of two.
+#[no_mangle]
+unsafe extern "C" fn spectrum_analyzer_set_buffer(
+    ptr: *mut SpectrumAnalyzer,
+    buffer: *mut f32,
+    length: usize,
+) {
+    ptr.as_mut()
+        .unwrap_unchecked()
+        .set_buffer(core::slice::from_raw_parts_mut(buffer, length));
+}
+
+/// Records a sample
+#[no_mangle]
+unsafe extern "C" fn spectrum_analyzer_push(ptr: *mut SpectrumAnalyzer, sample: f32) {
+    ptr.as_mut().unwrap_unchecked().push(sample);
+}
+
+/// Copies up to `length` bin levels in dB into `bins`, returns the number copied
+#[no_mangle]
+unsafe extern "C" fn spectrum_analyzer_get_bins(
+    ptr: *const SpectrumAnalyzer,
+    bins: *mut f32,
+    length: usize,
+) -> usize {
+    let source = ptr.as_ref().unwrap_unchecked().bins();
+    let count = source.len().min(length);
+
+    core::slice::from_raw_parts_mut(bins, count).copy_from_slice(&source[..count]);
+    count
+}
+
+/// Center frequency of bin `index` in Hz
+#[no_mangle]
+unsafe extern "C" fn spectrum_analyzer_bin_freq(ptr: *const SpectrumAnalyzer, index: usize) -> f32 {
+    ptr.as_ref().unwrap_unchecked().bin_freq(index)
+}
+
+#[no_mangle]
+unsafe extern "C" fn spectrum_analyzer_set_window(ptr: *mut SpectrumAnalyzer, window: Window) {
+    ptr.as_mut().unwrap_unchecked().set_window(window);
+}
+
+/// Speed in dB per second at which the bins fall after a peak
+#[no_mangle]
+unsafe extern "C" fn spectrum_analyzer_set_falloff(ptr: *mut SpectrumAnalyzer, db_per_second: f32) {
+    ptr.as_mut().unwrap_unchecked().set_falloff(db_per_second);
+}
@@ -0,0 +1,197 @@
+use core::f32::consts::PI;
+
+use libm::{cosf, sinf};
+
+/// Fills the table of twiddle factors `rfft` and `irfft` need for buffers of the same length
+///
+/// Holds cos and sin of 2πk / n interleaved for k up to n / 2, so no frame has to compute them.
+pub fn fill_twiddles(twiddles: &mut [f32]) {
+    let n = twiddles.len();
+    for (k, w) in twiddles.chunks_exact_mut(2).enumerate() {
+        let angle = 2.0 * PI * k as f32 / n as f32;
+        w[0] = cosf(angle);
+        w[1] = sinf(angle);
+    }
+}
+
+/// In-place real FFT, the length of `buffer` has to be a power of two and at least 4
+///
+/// The spectrum is packed into the same buffer: `buffer[0]` is the DC bin, `buffer[1]` the Nyquist bin
+/// and `buffer[2 * k]`, `buffer[2 * k + 1]` are the real and imaginary part of bin `k`. Not normalized.
+/// `twiddles` has the same length as `buffer` and is filled by `fill_twiddles`.
+pub fn rfft(buffer: &mut [f32], twiddles: &[f32]) {
+    let n = buffer.len();
+    debug_assert!(n >= 4 && n.is_power_of_two());
+    debug_assert_eq!(twiddles.len(), n);
+
+    // the even and odd samples form a complex signal of half the length
+    fft_complex(buffer, twiddles, false);
+
+    let (dc, nyquist) = (buffer[0] + buffer[1], buffer[0] - buffer[1]);
+    buffer[0] = dc;
+    buffer[1] = nyquist;
+
+    for k in 1..=n / 4 {
+        let j = n / 2 - k;
+        let z_k = (buffer[2 * k], buffer[2 * k + 1]);
+        let z_j = (buffer[2 * j], buffer[2 * j + 1]);
+
+        // split into the spectra of the even and odd samples
+        let even = (0.5 * (z_k.0 + z_j.0), 0.5 * (z_k.1 - z_j.1));
+        let odd = (0.5 * (z_k.1 + z_j.1), -0.5 * (z_k.0 - z_j.0));
+        let odd = mul(odd, twiddle(twiddles, k, false));
+
+        buffer[2 * k] = even.0 + odd.0;
+        buffer[2 * k + 1] = even.1 + odd.1;
+        // bin n / 2 - k is the mirrored combination
+        buffer[2 * j] = even.0 - odd.0;
+        buffer[2 * j + 1] = -(even.1 - odd.1);
+    }
+}
+
+/// Inverse of `rfft`, including the normalization
+pub fn irfft(buffer: &mut [f32], twiddles: &[f32]) {
+    let n = buffer.len();
+    debug_assert!(n >= 4 && n.is_power_of_two());
+    debug_assert_eq!(twiddles.len(), n);
+
+    let (dc, nyquist) = (buffer[0], buffer[1]);
+    buffer[0] = 0.5 * (dc + nyquist);
+    buffer[1] = 0.5 * (dc - nyquist);
+
+    for k in 1..=n / 4 {
+        let j = n / 2 - k;
+        let x_k = (buffer[2 * k], buffer[2 * k + 1]);
+        let x_j = (buffer[2 * j], buffer[2 * j + 1]);
+
+        let even = (0.5 * (x_k.0 + x_j.0), 0.5 * (x_k.1 - x_j.1));
+        let odd = mul(
+            (0.5 * (x_k.0 - x_j.0), 0.5 * (x_k.1 + x_j.1)),
+            twiddle(twiddles, k, true),
+        );
+
+        // z = even + i * odd
+        buffer[2 * k] = even.0 - odd.1;
+        buffer[2 * k + 1] = even.1 + odd.0;
+        buffer[2 * j] = even.0 + odd.1;
+        buffer[2 * j + 1] = odd.0 - even.1;
+    }
+
+    fft_complex(buffer, twiddles, true);
+
+    let scale = 2.0 / n as f32;
+    buffer.iter_mut().for_each(|x| *x *= scale);
+}
+
+/// exp(∓2πi k / n) from the table of a buffer of length n
+#[inline(always)]
+fn twiddle(twiddles: &[f32], k: usize, inverse: bool) -> (f32, f32) {
+    let sin = twiddles[2 * k + 1];
+    (twiddles[2 * k], if inverse { sin } else { -sin })
+}
+
+#[inline(always)]
+fn mul(a: (f32, f32), b: (f32, f32)) -> (f32, f32) {
+    (a.0 * b.0 - a.1 * b.1, a.0 * b.1 + a.1 * b.0)
+}
+
+/// Iterative radix-2 FFT on interleaved complex values, not normalized
+fn fft_complex(buffer: &mut [f32], twiddles: &[f32], inverse: bool) {
+    let n = buffer.len() / 2;
+
+    // bit reversal permutation
+    let mut j = 0;
+    for i in 1..n {
+        let mut bit = n >> 1;
+        while j & bit != 0 {
+            j ^= bit;
+            bit >>= 1;
+        }
+        j |= bit;
+
+        if i < j {
+            buffer.swap(2 * i, 2 * j);
+            buffer.swap(2 * i + 1, 2 * j + 1);
+        }
+    }
+
+    let mut length = 2;
+    while length <= n {
+        let half = length / 2;
+        // the table holds the angles of the whole buffer, 2 * n per turn
+        let stride = 2 * n / length;
+        for k in 0..half {
+            let w = twiddle(twiddles, k * stride, inverse);
+            for start in (0..n).step_by(length) {
+                let (a, b) = (start + k, start + k + half);
+                let t = mul((buffer[2 * b], buffer[2 * b + 1]), w);
+                let u = (buffer[2 * a], buffer[2 * a + 1]);
+
+                buffer[2 * a] = u.0 + t.0;
+                buffer[2 * a + 1] = u.1 + t.1;
+                buffer[2 * b] = u.0 - t.0;
+                buffer[2 * b + 1] = u.1 - t.1;
+            }
+        }
+        length *= 2;
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn sine_lands_in_its_bin() {
+        const N: usize = 64;
+        let mut buffer: [f32; N] =
+            core::array::from_fn(|i| cosf(2.0 * PI * 5.0 * i as f32 / N as f32) + 0.5);
+        let original = buffer;
+        let mut twiddles = [0.0; N];
+        fill_twiddles(&mut twiddles);
+
+        rfft(&mut buffer, &twiddles);
+        assert!((buffer[0] - 0.5 * N as f32).abs() < 1e-4);
+        assert!((buffer[10] - 0.5 * N as f32).abs() < 1e-4);
+        for (k, bin) in buffer.chunks(2).enumerate().skip(1) {
+            if k != 5 {
+                assert!(bin[0].abs() < 1e-3 && bin[1].abs() < 1e-3, "bin {}", k);
+            }
+        }
+
+        irfft(&mut buffer, &twiddles);
+        for (x, y) in buffer.iter().zip(original) {
+            assert!((x - y).abs() < 1e-5);
+        }
+    }
+
+    #[test]
+    fn matches_dft() {
+        const N: usize = 16;
+        let input: [f32; N] = core::array::from_fn(|i| ((i * 7 + 3) % 11) as f32 - 5.0);
+        let mut buffer = input;
+        let mut twiddles = [0.0; N];
+        fill_twiddles(&mut twiddles);
+        rfft(&mut buffer, &twiddles);
+
+        for k in 0..=N / 2 {
+            let (re, im) = input
+                .iter()
+                .enumerate()
+                .fold((0.0, 0.0), |(re, im), (i, x)| {
+                    let w = -2.0 * PI * (k * i) as f32 / N as f32;
+                    (re + x * cosf(w), im + x * sinf(w))
+                });
+            let (bin_re, bin_im) = match k {
+                0 => (buffer[0], 0.0),
+                k if k == N / 2 => (buffer[1], 0.0),
+                k => (buffer[2 * k], buffer[2 * k + 1]),
+            };
+            assert!(
+                (re - bin_re).abs() < 1e-3 && (im - bin_im).abs() < 1e-3,
+                "bin {}",
+                k
+            );
+        }
+    }
+}
@@ -0,0 +1,113 @@
+use super::{amplitude_scale, bin_magnitude, scale_bin, Stft, Window};
+use crate::multi_filter::db_to_gain;
+
+/// cbindgen:ignore
+const MAX_REDUCTION: f32 = 80.0; // dB
+
+/// Noise reduction that attenuates every frequency bin below the threshold on its own
+///
+/// Bins fade out quadratically below the threshold instead of switching hard, which keeps
+/// the musical noise of isolated bins down.
+#[repr(C)]
+pub struct SpectralGate {
+    stft: Stft,
+
+    threshold: f32,
+    floor: f32,
+    amplitude_scale: f32,
+}
+
+impl SpectralGate {
+    pub fn init() -> SpectralGate {
+        SpectralGate {
+            stft: Stft::new(Window::Hann),
+
+            threshold: db_to_gain(-60.0),
+            floor: db_to_gain(-40.0),
+            amplitude_scale: 0.0,
+        }
+    }
+
+    /// Split into the STFT parts, the FFT size is `length / 5.25` rounded down to a power of two, e.g. 1024 for `length = 5376`
+    pub fn set_buffer(&mut self, buffer: &mut [f32]) {
+        self.stft.set_buffer(buffer);
+        self.update_scale();
+    }
+
+    pub fn tick(&mut self, input: f32) -> f32 {
+        let (threshold, floor, scale) = (self.threshold, self.floor, self.amplitude_scale);
+
+        self.stft.tick(input, |spectrum| {
+            for k in 0..=spectrum.len() / 2 {
+                let ratio = bin_magnitude(spectrum, k) * scale / threshold;
+                if ratio < 1.0 {
+                    scale_bin(spectrum, k, (ratio * ratio).max(floor));
+                }
+            }
+        })
+    }
+
+    /// Level in dB a sine needs in its bin to pass unchanged
+    pub fn set_threshold(&mut self, db: f32) {
+        self.threshold = db_to_gain(db);
+    }
+
+    /// Largest attenuation in dB between 0 and 80, otherwise clamps
+    pub fn set_reduction(&mut self, db: f32) {
+        self.floor = db_to_gain(-db.clamp(0.0, MAX_REDUCTION));
+    }
+
+    pub fn set_window(&mut self, window: Window) {
+        self.stft.set_window(window);
+        self.update_scale();
+    }
+
+    pub fn latency_samples(&self) -> usize {
+        self.stft.latency_samples()
+    }
+
+    fn update_scale(&mut self) {
+        self.amplitude_scale = amplitude_scale(self.stft.window(), self.stft.size());
+    }
+}
+
+impl Default for SpectralGate {
+    fn default() -> Self {
+        Self::init()
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn removes_quiet_noise_keeps_tone() {
+        let mut buffer = [0.0; 1344];
+        let mut gate = SpectralGate::init();
+        gate.set_buffer(&mut buffer);
+        gate.set_threshold(-30.0);
+
+        let mut noise = crate::noise::WhiteNoise::new(3);
+        let (mut noise_in, mut noise_out) = (0.0, 0.0);
+        for i in 0..8192 {
+            let x = 0.003 * noise.tick();
+            let out = gate.tick(x);
+            if i > 1024 {
+                noise_in += x * x;
+                noise_out += out * out;
+            }
+        }
+        assert!(noise_out < 0.01 * noise_in);
+
+        // a tone centered on bin 16 passes
+        let tone =
+            |i: usize| 0.5 * libm::sinf(2.0 * core::f32::consts::PI * 16.0 * i as f32 / 256.0);
+        for i in 0..4096 {
+            let out = gate.tick(tone(i));
+            if i > 1024 {
+                assert!((out - tone(i - 256)).abs() < 1e-3);
+            }
+        }
+    }
+}
@@ -0,0 +1,99 @@
+mod analyzer;
+mod ffi;
+mod fft;
+mod gate;
+mod stft;
+
+pub use analyzer::SpectrumAnalyzer;
+pub use fft::{fill_twiddles, irfft, rfft};
+pub use gate::SpectralGate;
+pub use stft::Stft;
+
+use core::f32::consts::PI;
+
+use libm::cosf;
+
+/// Smallest supported FFT size
+/// cbindgen:ignore
+const MIN_FFT_SIZE: usize = 16;
+
+#[derive(Clone, Copy, PartialEq, Debug)]
+#[repr(C)]
+pub enum Window {
+    /// Good frequency resolution, sidelobes at -31 dB
+    Hann,
+    /// Wider main lobe, sidelobes at -58 dB
+    Blackman,
+}
+
+impl Window {
+    /// Periodic window value at sample `n` of `size`, which overlaps evenly
+    ///
+    /// Meant for filling tables, the STFT and the analyzer don't call it per sample.
+    pub fn value(&self, n: usize, size: usize) -> f32 {
+        let phase = 2.0 * PI * n as f32 / size as f32;
+        match self {
+            Window::Hann => 0.5 - 0.5 * cosf(phase),
+            Window::Blackman => 0.42 - 0.5 * cosf(phase) + 0.08 * cosf(2.0 * phase),
+        }
+    }
+}
+
+/// Largest power of two FFT size for which `parts` buffers of that size fit into `length`, 0 if none
+fn fft_size_for(length: usize, parts: f32) -> usize {
+    let size = (length as f32 / parts) as usize;
+    if size < MIN_FFT_SIZE {
+        0
+    } else {
+        1 << (usize::BITS - 1 - size.leading_zeros())
+    }
+}
+
+/// Turns bin magnitudes of a windowed frame into the amplitude of a sine in that bin
+fn amplitude_scale(window: Window, size: usize) -> f32 {
+    let sum: f32 = (0..size).map(|n| window.value(n, size)).sum();
+    if sum > 0.0 {
+        2.0 / sum
+    } else {
+        0.0
+    }
+}
+
+/// Magnitude of bin `k` of a spectrum packed by `rfft`
+#[inline(always)]
+fn bin_magnitude(spectrum: &[f32], k: usize) -> f32 {
+    match k {
+        0 => spectrum[0].abs(),
+        k if k == spectrum.len() / 2 => spectrum[1].abs(),
+        k => libm::sqrtf(
+            spectrum[2 * k] * spectrum[2 * k] + spectrum[2 * k + 1] * spectrum[2 * k + 1],
+        ),
+    }
+}
+
+/// Scales bin `k` of a spectrum packed by `rfft`
+#[inline(always)]
+fn scale_bin(spectrum: &mut [f32], k: usize, gain: f32) {
+    match k {
+        0 => spectrum[0] *= gain,
+        k if k == spectrum.len() / 2 => spectrum[1] *= gain,
+        k => {
+            spectrum[2 * k] *= gain;
+            spectrum[2 * k + 1] *= gain;
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn sizes_fit_the_buffer() {
+        assert_eq!(fft_size_for(3 * 1024, 3.0), 1024);
+        assert_eq!(fft_size_for(3 * 1024 - 1, 3.0), 512);
+        assert_eq!(fft_size_for(40, 3.0), 0);
+        assert_eq!(Window::Hann.value(0, 64), 0.0);
+        assert!((Window::Blackman.value(32, 64) - 1.0).abs() < 1e-6);
+    }
+}
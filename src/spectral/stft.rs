@@ -0,0 +1,199 @@
+use core::ptr::null_mut;
+
+use super::{fft_size_for, fill_twiddles, irfft, rfft, Window};
+
+/// Frames overlap by 75%
+/// cbindgen:ignore
+const OVERLAP: usize = 4;
+
+/// Rings, frame, window and twiddles take the FFT size each, the normalization a quarter of it
+/// cbindgen:ignore
+const BUFFER_PARTS: f32 = 5.25;
+
+/// Overlap-add short-time Fourier transform in a caller-provided buffer
+///
+/// Every `size / 4` samples a windowed frame is transformed, handed to the processing closure as a
+/// spectrum packed by `rfft`, transformed back, windowed again and added to the output.
+/// The overlapping windows are normalized per sample, so without processing the output is the input
+/// delayed by `size` samples for every window.
+#[repr(C)]
+pub struct Stft {
+    input: *mut f32,
+    output: *mut f32,
+    frame: *mut f32,
+    window_table: *mut f32,
+    twiddles: *mut f32,
+    norm: *mut f32,
+    size: usize,
+
+    window: Window,
+    position: usize,
+    hop_countdown: usize,
+}
+
+/// The parts of the caller's buffer
+struct StftBuffers<'a> {
+    input: &'a mut [f32],
+    output: &'a mut [f32],
+    frame: &'a mut [f32],
+    window: &'a mut [f32],
+    twiddles: &'a mut [f32],
+    norm: &'a mut [f32],
+}
+
+impl Stft {
+    pub fn new(window: Window) -> Stft {
+        Stft {
+            input: null_mut(),
+            output: null_mut(),
+            frame: null_mut(),
+            window_table: null_mut(),
+            twiddles: null_mut(),
+            norm: null_mut(),
+            size: 0,
+
+            window,
+            position: 0,
+            hop_countdown: 0,
+        }
+    }
+
+    /// Split into the ring buffers, the frame and the tables, the FFT size is `length / 5.25` rounded down to a power of two
+    ///
+    /// Buffers shorter than 84 samples leave the STFT silent.
+    pub fn set_buffer(&mut self, buffer: &mut [f32]) {
+        let size = fft_size_for(buffer.len(), BUFFER_PARTS);
+        buffer.fill(0.0);
+
+        let (input, rest) = buffer.split_at_mut(size);
+        let (output, rest) = rest.split_at_mut(size);
+        let (frame, rest) = rest.split_at_mut(size);
+        let (window_table, rest) = rest.split_at_mut(size);
+        let (twiddles, rest) = rest.split_at_mut(size);
+        self.input = input.as_mut_ptr();
+        self.output = output.as_mut_ptr();
+        self.frame = frame.as_mut_ptr();
+        self.window_table = window_table.as_mut_ptr();
+        self.twiddles = twiddles.as_mut_ptr();
+        self.norm = rest.as_mut_ptr();
+        self.size = size;
+        self.position = 0;
+        self.hop_countdown = size / OVERLAP;
+
+        if size > 0 {
+            fill_twiddles(self.buffers().twiddles);
+        }
+        self.set_window(self.window);
+    }
+
+    /// `process` is called once per hop with the spectrum of the latest frame
+    pub fn tick(&mut self, input: f32, mut process: impl FnMut(&mut [f32])) -> f32 {
+        if self.size == 0 {
+            return 0.0;
+        }
+
+        let (size, hop) = (self.size, self.size / OVERLAP);
+        let position = self.position;
+        // the oldest sample sits right after the newest one
+        let oldest = (position + 1) % size;
+        self.position = oldest;
+
+        self.hop_countdown -= 1;
+        let frame_due = self.hop_countdown == 0;
+        if frame_due {
+            self.hop_countdown = hop;
+        }
+
+        let buffers = self.buffers();
+        buffers.input[position] = input;
+        let output = buffers.output[position];
+        buffers.output[position] = 0.0;
+
+        if frame_due {
+            for (n, x) in buffers.frame.iter_mut().enumerate() {
+                *x = buffers.input[(oldest + n) % size] * buffers.window[n];
+            }
+
+            rfft(buffers.frame, buffers.twiddles);
+            process(buffers.frame);
+            irfft(buffers.frame, buffers.twiddles);
+
+            for (n, x) in buffers.frame.iter().enumerate() {
+                buffers.output[(oldest + n) % size] +=
+                    x * buffers.window[n] * buffers.norm[n % hop];
+            }
+        }
+
+        output
+    }
+
+    pub fn set_window(&mut self, window: Window) {
+        self.window = window;
+        if self.size == 0 {
+            return;
+        }
+
+        let (size, hop) = (self.size, self.size / OVERLAP);
+        let buffers = self.buffers();
+        for (n, x) in buffers.window.iter_mut().enumerate() {
+            *x = window.value(n, size);
+        }
+
+        // every output sample gets the squared window of OVERLAP frames, at the same offset within a hop
+        for (m, norm) in buffers.norm.iter_mut().enumerate() {
+            let sum: f32 = (0..OVERLAP)
+                .map(|j| buffers.window[m + j * hop] * buffers.window[m + j * hop])
+                .sum();
+            *norm = if sum > 0.0 { 1.0 / sum } else { 0.0 };
+        }
+    }
+
+    pub fn window(&self) -> Window {
+        self.window
+    }
+
+    pub fn size(&self) -> usize {
+        self.size
+    }
+
+    pub fn latency_samples(&self) -> usize {
+        self.size
+    }
+
+    fn buffers(&mut self) -> StftBuffers<'_> {
+        // parts of the caller's buffer, which never overlap
+        unsafe {
+            StftBuffers {
+                input: core::slice::from_raw_parts_mut(self.input, self.size),
+                output: core::slice::from_raw_parts_mut(self.output, self.size),
+                frame: core::slice::from_raw_parts_mut(self.frame, self.size),
+                window: core::slice::from_raw_parts_mut(self.window_table, self.size),
+                twiddles: core::slice::from_raw_parts_mut(self.twiddles, self.size),
+                norm: core::slice::from_raw_parts_mut(self.norm, self.size / OVERLAP),
+            }
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn reconstructs_delayed_input() {
+        for window in [Window::Hann, Window::Blackman] {
+            let mut buffer = [0.0; 336];
+            let mut stft = Stft::new(window);
+            stft.set_buffer(&mut buffer);
+            assert_eq!(stft.size(), 64);
+
+            let input = |i: usize| libm::sinf(0.05 * i as f32) + 0.3 * libm::sinf(0.31 * i as f32);
+            for i in 0..1000 {
+                let out = stft.tick(input(i), |_| {});
+                if i >= 2 * 64 {
+                    assert!((out - input(i - 64)).abs() < 1e-4, "{:?} {}", window, i);
+                }
+            }
+        }
+    }
+}
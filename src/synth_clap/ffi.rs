@@ -0,0 +1,57 @@
+use crate::{DrumVoice, SynthClap};
+
+/// Initializes `SynthClap` struct
+#[no_mangle]
+extern "C" fn synth_clap_init(sr: f32) -> SynthClap {
+    SynthClap::init(sr)
+}
+
+/// Triggers the clap
+#[no_mangle]
+unsafe extern "C" fn synth_clap_trigger(ptr: *mut SynthClap) {
+    ptr.as_mut().unwrap_unchecked().trigger();
+}
+
+/// Triggers the clap on the `offset`th call of `tick`, counting from zero
+#[no_mangle]
+unsafe extern "C" fn synth_clap_trigger_at(ptr: *mut SynthClap, offset: usize) {
+    ptr.as_mut().unwrap_unchecked().trigger_at(offset);
+}
+
+/// Quickly fades out a ringing hit
+#[no_mangle]
+unsafe extern "C" fn synth_clap_choke(ptr: *mut SynthClap) {
+    ptr.as_mut().unwrap_unchecked().choke();
+}
+
+/// Returns next sample
+#[no_mangle]
+unsafe extern "C" fn synth_clap_tick(ptr: *mut SynthClap) -> f32 {
+    ptr.as_mut().unwrap_unchecked().tick()
+}
+
+/// Fills `out` with the next `length` samples
+#[no_mangle]
+unsafe extern "C" fn synth_clap_process(ptr: *mut SynthClap, out: *mut f32, length: usize) {
+    ptr.as_mut()
+        .unwrap_unchecked()
+        .process(core::slice::from_raw_parts_mut(out, length));
+}
+
+/// Only accepts values between 0.0 and 1.0, otherwise clamps
+#[no_mangle]
+unsafe extern "C" fn synth_clap_set_decay(ptr: *mut SynthClap, val: f32) {
+    ptr.as_mut().unwrap_unchecked().set_decay(val);
+}
+
+/// Only accepts values between 0.0 and 1.0, otherwise clamps
+#[no_mangle]
+unsafe extern "C" fn synth_clap_set_tone(ptr: *mut SynthClap, val: f32) {
+    ptr.as_mut().unwrap_unchecked().set_tone(val);
+}
+
+/// Only accepts values between 0.0 and 1.0, otherwise clamps
+#[no_mangle]
+unsafe extern "C" fn synth_clap_set_spread(ptr: *mut SynthClap, val: f32) {
+    ptr.as_mut().unwrap_unchecked().set_spread(val);
+}
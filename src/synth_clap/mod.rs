@@ -0,0 +1,246 @@
+mod ffi;
+
+use core::f32::consts::PI;
+
+use embedded_audio_tools::{
+    envelopes::{AudioRateADSR, EnvelopeState},
+    float::lerp_unchecked,
+};
+use libm::{expf, powf};
+
+use crate::drum_voice::{DrumVoice, TriggerSchedule};
+use crate::noise::WhiteNoise;
+
+/// cbindgen:ignore
+const CLAP_BURSTS: usize = 4;
+/// cbindgen:ignore
+const BURST_DECAY: f32 = 0.003; // s
+/// cbindgen:ignore
+const TAIL_LEVEL: f32 = 0.6;
+
+/// cbindgen:ignore
+const SHORTEST_SPREAD: f32 = 0.005; // s
+/// cbindgen:ignore
+const LONGEST_SPREAD: f32 = 0.020; // s
+
+/// cbindgen:ignore
+const SHORTEST_CLAP_DECAY: f32 = 0.1; // s
+/// cbindgen:ignore
+const LONGEST_CLAP_DECAY: f32 = 1.0; // s
+
+/// cbindgen:ignore
+const DARKEST_CLAP: f32 = 800.0; // Hz
+/// cbindgen:ignore
+const BRIGHTEST_CLAP: f32 = 2500.0; // Hz
+
+/// cbindgen:ignore
+const SILENCE: f32 = 0.0001;
+
+/// Bandpassed noise with a few quick bursts and a reverberant tail
+#[repr(C)]
+pub struct SynthClap {
+    // Audio Tools
+    tail_env: AudioRateADSR,
+    noise: WhiteNoise,
+    schedule: TriggerSchedule,
+
+    // State
+    sr: f32,
+    bursts_left: usize,
+    burst_timer: usize,
+    burst_amp: f32,
+    lp_state: f32,
+    hp_state: f32,
+
+    // Coefficients
+    burst_coeff: f32,
+    lp_coeff: f32,
+    hp_coeff: f32,
+
+    // Params
+    burst_spacing: usize,
+    spread: f32,
+    tone: f32,
+}
+
+impl SynthClap {
+    pub fn init(sr: f32) -> SynthClap {
+        let mut clap = SynthClap {
+            tail_env: AudioRateADSR::new(0.001, 0.3, 0.0, 0.01, 0.5, sr),
+            noise: WhiteNoise::default(),
+            schedule: TriggerSchedule::new(),
+
+            sr,
+            bursts_left: 0,
+            burst_timer: 0,
+            burst_amp: 0.0,
+            lp_state: 0.0,
+            hp_state: 0.0,
+
+            burst_coeff: 0.0,
+            lp_coeff: 0.0,
+            hp_coeff: 0.0,
+
+            burst_spacing: 0,
+            spread: 0.5,
+            tone: 0.5,
+        };
+
+        clap.update_coeffs();
+        clap
+    }
+
+    pub fn trigger(&mut self) {
+        self.bursts_left = CLAP_BURSTS;
+        self.burst_timer = 0;
+    }
+
+    /// Triggers on the `offset`th call of `tick`, counting from zero
+    ///
+    /// Up to four triggers can be pending, further ones get dropped.
+    pub fn trigger_at(&mut self, offset: usize) {
+        self.schedule.schedule(offset, 1.0, false);
+    }
+
+    /// Quickly fades out a ringing hit
+    pub fn choke(&mut self) {
+        // the current burst dies out on its own within a few ms
+        self.bursts_left = 0;
+        self.tail_env.trigger_off();
+    }
+
+    pub fn tick(&mut self) -> f32 {
+        if self.schedule.advance().is_some() {
+            self.trigger();
+        }
+
+        if self.bursts_left > 0 {
+            if self.burst_timer == 0 {
+                self.burst_amp = 1.0;
+                self.burst_timer = self.burst_spacing;
+                self.bursts_left -= 1;
+
+                // the tail grows out of the last burst
+                if self.bursts_left == 0 {
+                    self.tail_env.reset();
+                    self.tail_env.trigger_on();
+                }
+            }
+
+            self.burst_timer -= 1;
+        } else if self.burst_amp < SILENCE && self.tail_env.get_state() == EnvelopeState::Idle {
+            return 0.0;
+        }
+
+        // bandpass from a lowpass above and a highpass below the tone
+        let white = self.noise.tick();
+        self.lp_state += self.lp_coeff * (white - self.lp_state);
+        self.hp_state += self.hp_coeff * (self.lp_state - self.hp_state);
+        let band = self.lp_state - self.hp_state;
+
+        let amp = self.burst_amp + TAIL_LEVEL * self.tail_env.tick();
+        self.burst_amp *= self.burst_coeff;
+
+        amp * band
+    }
+
+    pub fn update_sr(&mut self, sr: f32) {
+        self.tail_env.set_sr(sr);
+        self.sr = sr;
+        self.update_coeffs();
+    }
+
+    /// Only accepts values between 0.0 and 1.0, otherwise clamps
+    pub fn set_decay(&mut self, val: f32) {
+        self.tail_env.set_decay(lerp_unchecked(
+            SHORTEST_CLAP_DECAY,
+            LONGEST_CLAP_DECAY,
+            val.clamp(0.0, 1.0),
+        ));
+    }
+
+    /// Only accepts values between 0.0 and 1.0, otherwise clamps
+    pub fn set_tone(&mut self, val: f32) {
+        self.tone = val.clamp(0.0, 1.0);
+        self.update_coeffs();
+    }
+
+    /// Time between the bursts. Only accepts values between 0.0 and 1.0, otherwise clamps
+    pub fn set_spread(&mut self, val: f32) {
+        self.spread = val.clamp(0.0, 1.0);
+        self.update_coeffs();
+    }
+
+    fn update_coeffs(&mut self) {
+        let spacing = lerp_unchecked(SHORTEST_SPREAD, LONGEST_SPREAD, self.spread);
+        // at least one sample, the countdown would underflow otherwise
+        self.burst_spacing = ((spacing * self.sr) as usize).max(1);
+        self.burst_coeff = expf(-1.0 / (BURST_DECAY * self.sr));
+
+        let center = DARKEST_CLAP * powf(BRIGHTEST_CLAP / DARKEST_CLAP, self.tone);
+        self.lp_coeff = 1.0 - expf(-2.0 * PI * (2.0 * center).min(0.45 * self.sr) / self.sr);
+        self.hp_coeff = 1.0 - expf(-2.0 * PI * 0.5 * center / self.sr);
+    }
+}
+
+impl DrumVoice for SynthClap {
+    fn trigger(&mut self) {
+        SynthClap::trigger(self);
+    }
+
+    fn trigger_at(&mut self, offset: usize) {
+        SynthClap::trigger_at(self, offset);
+    }
+
+    fn choke(&mut self) {
+        SynthClap::choke(self);
+    }
+
+    fn tick(&mut self) -> f32 {
+        SynthClap::tick(self)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    const SR: f32 = 48000.0;
+
+    #[test]
+    fn bursts_then_tail() {
+        let mut clap = SynthClap::init(SR);
+        assert_eq!(clap.tick(), 0.0);
+
+        clap.set_spread(1.0);
+        clap.trigger();
+
+        // every burst restarts the envelope
+        let spacing = clap.burst_spacing;
+        let onsets = (0..CLAP_BURSTS * spacing)
+            .filter(|_| {
+                clap.tick();
+                clap.burst_amp == clap.burst_coeff
+            })
+            .count();
+        assert_eq!(onsets, CLAP_BURSTS);
+
+        for _ in 0..SR as usize * 2 {
+            clap.tick();
+        }
+        assert_eq!(clap.tick(), 0.0);
+    }
+
+    #[test]
+    fn low_sample_rate_keeps_bursts_apart() {
+        // shorter spacing than a sample
+        let mut clap = SynthClap::init(100.0);
+        clap.set_spread(0.0);
+        assert_eq!(clap.burst_spacing, 1);
+
+        clap.trigger();
+        for _ in 0..1000 {
+            assert!(clap.tick().is_finite());
+        }
+    }
+}
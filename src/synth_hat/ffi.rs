@@ -0,0 +1,69 @@
+use crate::{DrumVoice, SynthHat};
+
+/// Initializes `SynthHat` struct
+#[no_mangle]
+extern "C" fn synth_hat_init(sr: f32) -> SynthHat {
+    SynthHat::init(sr)
+}
+
+/// Triggers a closed hit, which chokes a ringing open hit
+#[no_mangle]
+unsafe extern "C" fn synth_hat_trigger(ptr: *mut SynthHat) {
+    ptr.as_mut().unwrap_unchecked().trigger();
+}
+
+/// Triggers an open hit
+#[no_mangle]
+unsafe extern "C" fn synth_hat_trigger_open(ptr: *mut SynthHat) {
+    ptr.as_mut().unwrap_unchecked().trigger_open();
+}
+
+/// Quickly fades out a ringing hit
+#[no_mangle]
+unsafe extern "C" fn synth_hat_choke(ptr: *mut SynthHat) {
+    ptr.as_mut().unwrap_unchecked().choke();
+}
+
+/// Triggers the hat on the `offset`th call of `tick`, counting from zero
+#[no_mangle]
+unsafe extern "C" fn synth_hat_trigger_at(ptr: *mut SynthHat, offset: usize) {
+    ptr.as_mut().unwrap_unchecked().trigger_at(offset);
+}
+
+/// Returns next sample
+#[no_mangle]
+unsafe extern "C" fn synth_hat_tick(ptr: *mut SynthHat) -> f32 {
+    ptr.as_mut().unwrap_unchecked().tick()
+}
+
+/// Fills `out` with the next `length` samples
+#[no_mangle]
+unsafe extern "C" fn synth_hat_process(ptr: *mut SynthHat, out: *mut f32, length: usize) {
+    ptr.as_mut()
+        .unwrap_unchecked()
+        .process(core::slice::from_raw_parts_mut(out, length));
+}
+
+/// Only accepts values between 0.0 and 1.0, otherwise clamps
+#[no_mangle]
+unsafe extern "C" fn synth_hat_set_pitch(ptr: *mut SynthHat, val: f32) {
+    ptr.as_mut().unwrap_unchecked().set_pitch(val);
+}
+
+/// Only accepts values between 0.0 and 1.0, otherwise clamps
+#[no_mangle]
+unsafe extern "C" fn synth_hat_set_closed_decay(ptr: *mut SynthHat, val: f32) {
+    ptr.as_mut().unwrap_unchecked().set_closed_decay(val);
+}
+
+/// Only accepts values between 0.0 and 1.0, otherwise clamps
+#[no_mangle]
+unsafe extern "C" fn synth_hat_set_open_decay(ptr: *mut SynthHat, val: f32) {
+    ptr.as_mut().unwrap_unchecked().set_open_decay(val);
+}
+
+/// Only accepts values between 0.0 and 1.0, otherwise clamps
+#[no_mangle]
+unsafe extern "C" fn synth_hat_set_tone(ptr: *mut SynthHat, val: f32) {
+    ptr.as_mut().unwrap_unchecked().set_tone(val);
+}
@@ -0,0 +1,237 @@
+mod ffi;
+
+use core::f32::consts::PI;
+
+use embedded_audio_tools::{
+    envelopes::{AudioRateADSR, EnvelopeState},
+    float::lerp_unchecked,
+    FunctionalOscillator, PhaseAccumulator, SoftPhaseAccumulator,
+};
+use libm::{expf, powf};
+
+use crate::drum_voice::{DrumVoice, TriggerSchedule};
+
+/// Inharmonic square wave bank of the TR-808 cymbal circuit
+/// cbindgen:ignore
+const METALLIC_FREQS: [f32; 6] = [205.3, 304.4, 369.6, 522.7, 540.0, 800.0]; // Hz
+
+/// cbindgen:ignore
+const LOWEST_HAT_PITCH: f32 = 0.5; // relative to the metallic frequencies
+/// cbindgen:ignore
+const HIGHEST_HAT_PITCH: f32 = 2.0; // relative to the metallic frequencies
+
+/// cbindgen:ignore
+const SHORTEST_CLOSED_DECAY: f32 = 0.02; // s
+/// cbindgen:ignore
+const LONGEST_CLOSED_DECAY: f32 = 0.15; // s
+/// cbindgen:ignore
+const SHORTEST_OPEN_DECAY: f32 = 0.2; // s
+/// cbindgen:ignore
+const LONGEST_OPEN_DECAY: f32 = 1.5; // s
+
+/// cbindgen:ignore
+const DARKEST_HAT: f32 = 4000.0; // Hz
+/// cbindgen:ignore
+const BRIGHTEST_HAT: f32 = 10000.0; // Hz
+
+/// cbindgen:ignore
+const CHOKE_TIME: f32 = 0.005; // s
+
+/// Metallic hi-hat with open and closed hits, where a closed hit chokes an open one
+#[repr(C)]
+pub struct SynthHat {
+    // Audio Tools
+    volume_env: AudioRateADSR,
+    oscs: [FunctionalOscillator<SoftPhaseAccumulator>; 6],
+    schedule: TriggerSchedule,
+
+    // State
+    sr: f32,
+    hp_state: f32,
+    hp_coeff: f32,
+
+    // Params
+    closed_decay: f32,
+    open_decay: f32,
+    tone: f32,
+}
+
+impl SynthHat {
+    pub fn init(sr: f32) -> SynthHat {
+        let mut hat = SynthHat {
+            volume_env: AudioRateADSR::new(0.0005, 0.05, 0.0, CHOKE_TIME, 0.5, sr),
+            oscs: core::array::from_fn(|i| {
+                FunctionalOscillator::new(SoftPhaseAccumulator::new(METALLIC_FREQS[i], sr))
+            }),
+            schedule: TriggerSchedule::new(),
+
+            sr,
+            hp_state: 0.0,
+            hp_coeff: 0.0,
+
+            closed_decay: 0.05,
+            open_decay: 0.5,
+            tone: 0.5,
+        };
+
+        hat.update_filter();
+        hat
+    }
+
+    /// Triggers a closed hit, which chokes a ringing open hit
+    pub fn trigger(&mut self) {
+        self.volume_env.set_decay(self.closed_decay);
+        self.restart();
+    }
+
+    /// Triggers an open hit
+    pub fn trigger_open(&mut self) {
+        self.volume_env.set_decay(self.open_decay);
+        self.restart();
+    }
+
+    /// Quickly fades out a ringing hit
+    pub fn choke(&mut self) {
+        self.volume_env.trigger_off();
+    }
+
+    /// Triggers on the `offset`th call of `tick`, counting from zero
+    ///
+    /// Up to four triggers can be pending, further ones get dropped.
+    pub fn trigger_at(&mut self, offset: usize) {
+        self.schedule.schedule(offset, 1.0, false);
+    }
+
+    pub fn tick(&mut self) -> f32 {
+        if self.schedule.advance().is_some() {
+            self.trigger();
+        }
+
+        if self.volume_env.get_state() == EnvelopeState::Idle {
+            return 0.0;
+        }
+
+        // squaring the sines turns them into the metallic pulse bank
+        let metal = self
+            .oscs
+            .iter_mut()
+            .map(|osc| if osc.next() >= 0.0 { 1.0 } else { -1.0 })
+            .sum::<f32>()
+            / METALLIC_FREQS.len() as f32;
+
+        self.hp_state += self.hp_coeff * (metal - self.hp_state);
+
+        self.volume_env.tick() * (metal - self.hp_state)
+    }
+
+    pub fn update_sr(&mut self, sr: f32) {
+        self.volume_env.set_sr(sr);
+        self.oscs
+            .iter_mut()
+            .for_each(|osc| osc.set_sr_unchecked(sr));
+        self.sr = sr;
+        self.update_filter();
+    }
+
+    /// Only accepts values between 0.0 and 1.0, otherwise clamps
+    pub fn set_pitch(&mut self, val: f32) {
+        let ratio = lerp_unchecked(LOWEST_HAT_PITCH, HIGHEST_HAT_PITCH, val.clamp(0.0, 1.0));
+
+        for (osc, freq) in self.oscs.iter_mut().zip(METALLIC_FREQS) {
+            osc.set_freq_unchecked(freq * ratio);
+        }
+    }
+
+    /// Decay of closed hits. Only accepts values between 0.0 and 1.0, otherwise clamps
+    pub fn set_closed_decay(&mut self, val: f32) {
+        self.closed_decay = lerp_unchecked(
+            SHORTEST_CLOSED_DECAY,
+            LONGEST_CLOSED_DECAY,
+            val.clamp(0.0, 1.0),
+        );
+    }
+
+    /// Decay of open hits. Only accepts values between 0.0 and 1.0, otherwise clamps
+    pub fn set_open_decay(&mut self, val: f32) {
+        self.open_decay =
+            lerp_unchecked(SHORTEST_OPEN_DECAY, LONGEST_OPEN_DECAY, val.clamp(0.0, 1.0));
+    }
+
+    /// Only accepts values between 0.0 and 1.0, otherwise clamps
+    pub fn set_tone(&mut self, val: f32) {
+        self.tone = val.clamp(0.0, 1.0);
+        self.update_filter();
+    }
+
+    fn restart(&mut self) {
+        self.volume_env.reset();
+        self.volume_env.trigger_on();
+    }
+
+    fn update_filter(&mut self) {
+        let cutoff = DARKEST_HAT * powf(BRIGHTEST_HAT / DARKEST_HAT, self.tone);
+        self.hp_coeff = 1.0 - expf(-2.0 * PI * cutoff.min(0.45 * self.sr) / self.sr);
+    }
+}
+
+impl DrumVoice for SynthHat {
+    fn trigger(&mut self) {
+        SynthHat::trigger(self);
+    }
+
+    fn trigger_at(&mut self, offset: usize) {
+        SynthHat::trigger_at(self, offset);
+    }
+
+    fn choke(&mut self) {
+        SynthHat::choke(self);
+    }
+
+    fn tick(&mut self) -> f32 {
+        SynthHat::tick(self)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    const SR: f32 = 48000.0;
+
+    /// Samples until the hat is silent
+    fn ring_time(hat: &mut SynthHat) -> usize {
+        (0..SR as usize * 2)
+            .position(|_| {
+                hat.tick();
+                hat.volume_env.get_state() == EnvelopeState::Idle
+            })
+            .unwrap()
+    }
+
+    #[test]
+    fn open_rings_longer() {
+        let mut hat = SynthHat::init(SR);
+        hat.trigger();
+        let closed = ring_time(&mut hat);
+
+        hat.trigger_open();
+        let open = ring_time(&mut hat);
+
+        assert!(open > 4 * closed);
+    }
+
+    #[test]
+    fn closed_chokes_open() {
+        let mut hat = SynthHat::init(SR);
+        hat.trigger_open();
+        for _ in 0..480 {
+            hat.tick();
+        }
+        hat.trigger();
+        let choked = ring_time(&mut hat);
+
+        hat.trigger_open();
+        hat.choke();
+        assert!(ring_time(&mut hat) < choked);
+    }
+}
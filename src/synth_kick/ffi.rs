@@ -1,4 +1,8 @@
-use crate::SynthKick;
+use crate::{DrumVoice, SynthKick};
+
+use super::{RetriggerPolicy, VelocityCurve};
+use crate::tools::Waveform;
+use crate::Waveshaper;
 
 /// Initializes `SynthKick` struct
 #[no_mangle]
@@ -12,12 +16,77 @@ unsafe extern "C" fn synth_kick_trigger(ptr: *mut SynthKick) {
     ptr.as_mut().unwrap_unchecked().trigger();
 }
 
+/// Triggers the kick. Only accepts velocities between 0.0 and 1.0, otherwise clamps
+#[no_mangle]
+unsafe extern "C" fn synth_kick_trigger_with_velocity(ptr: *mut SynthKick, velocity: f32) {
+    ptr.as_mut()
+        .unwrap_unchecked()
+        .trigger_with_velocity(velocity);
+}
+
+/// Triggers the kick. Accented hits are louder and sweep deeper.
+#[no_mangle]
+unsafe extern "C" fn synth_kick_trigger_with_accent(
+    ptr: *mut SynthKick,
+    velocity: f32,
+    accent: bool,
+) {
+    ptr.as_mut()
+        .unwrap_unchecked()
+        .trigger_with_accent(velocity, accent);
+}
+
+/// Triggers the kick on the `offset`th call of `tick`, counting from zero
+#[no_mangle]
+unsafe extern "C" fn synth_kick_trigger_at(ptr: *mut SynthKick, offset: usize) {
+    ptr.as_mut().unwrap_unchecked().trigger_at(offset);
+}
+
+/// Like `synth_kick_trigger_with_accent`, but on the `offset`th call of `tick`
+#[no_mangle]
+unsafe extern "C" fn synth_kick_trigger_at_with_accent(
+    ptr: *mut SynthKick,
+    offset: usize,
+    velocity: f32,
+    accent: bool,
+) {
+    ptr.as_mut()
+        .unwrap_unchecked()
+        .trigger_at_with_accent(offset, velocity, accent);
+}
+
+/// Note off, only has an effect in gate mode
+#[no_mangle]
+unsafe extern "C" fn synth_kick_release(ptr: *mut SynthKick) {
+    ptr.as_mut().unwrap_unchecked().release();
+}
+
+/// Quickly fades out all ringing hits
+#[no_mangle]
+unsafe extern "C" fn synth_kick_choke(ptr: *mut SynthKick) {
+    ptr.as_mut().unwrap_unchecked().choke();
+}
+
 /// Returns next sample
 #[no_mangle]
 unsafe extern "C" fn synth_kick_tick(ptr: *mut SynthKick) -> f32 {
     ptr.as_mut().unwrap_unchecked().tick()
 }
 
+/// Fills `out` with the next `length` samples
+#[no_mangle]
+unsafe extern "C" fn synth_kick_process(ptr: *mut SynthKick, out: *mut f32, length: usize) {
+    ptr.as_mut()
+        .unwrap_unchecked()
+        .process(core::slice::from_raw_parts_mut(out, length));
+}
+
+/// Behavior when the kick gets triggered while still ringing
+#[no_mangle]
+unsafe extern "C" fn synth_kick_set_retrigger_policy(ptr: *mut SynthKick, policy: RetriggerPolicy) {
+    ptr.as_mut().unwrap_unchecked().set_retrigger_policy(policy);
+}
+
 /// Only accepts values between 0.0 and 1.0, otherwise clamps
 #[no_mangle]
 unsafe extern "C" fn synth_kick_set_attack(ptr: *mut SynthKick, val: f32) {
@@ -30,8 +99,198 @@ unsafe extern "C" fn synth_kick_set_decay(ptr: *mut SynthKick, val: f32) {
     ptr.as_mut().unwrap_unchecked().set_decay(val);
 }
 
+/// Fade out after `synth_kick_release` in gate mode. Only accepts values between 0.0 and 1.0, otherwise clamps
+#[no_mangle]
+unsafe extern "C" fn synth_kick_set_release(ptr: *mut SynthKick, val: f32) {
+    ptr.as_mut().unwrap_unchecked().set_release(val);
+}
+
+/// Time at full volume before decaying, up to 2 s. Only accepts values between 0.0 and 1.0, otherwise clamps
+#[no_mangle]
+unsafe extern "C" fn synth_kick_set_hold(ptr: *mut SynthKick, val: f32) {
+    ptr.as_mut().unwrap_unchecked().set_hold(val);
+}
+
+/// Holds the kick until `synth_kick_release` when enabled
+#[no_mangle]
+unsafe extern "C" fn synth_kick_set_gate_mode(ptr: *mut SynthKick, gate_mode: bool) {
+    ptr.as_mut().unwrap_unchecked().set_gate_mode(gate_mode);
+}
+
 /// Only accepts values between 0.0 and 1.0, otherwise clamps
 #[no_mangle]
 unsafe extern "C" fn synth_kick_set_pitch(ptr: *mut SynthKick, val: f32) {
     ptr.as_mut().unwrap_unchecked().set_pitch(val);
 }
+
+/// Tunes the kick to a MIDI note, detuned by `cents`
+#[no_mangle]
+unsafe extern "C" fn synth_kick_set_note(ptr: *mut SynthKick, midi_note: u8, cents: f32) {
+    ptr.as_mut().unwrap_unchecked().set_note(midi_note, cents);
+}
+
+/// Portamento between notes, up to 1 s. Only accepts values between 0.0 and 1.0, otherwise clamps
+#[no_mangle]
+unsafe extern "C" fn synth_kick_set_glide(ptr: *mut SynthKick, val: f32) {
+    ptr.as_mut().unwrap_unchecked().set_glide(val);
+}
+
+/// Velocity to amplitude curve. Sensitivity between 0.0 and 1.0, curve exponent between 0.1 and 10.0
+#[no_mangle]
+unsafe extern "C" fn synth_kick_set_velocity_amp_curve(
+    ptr: *mut SynthKick,
+    sensitivity: f32,
+    curve: f32,
+) {
+    ptr.as_mut()
+        .unwrap_unchecked()
+        .set_velocity_amp_curve(VelocityCurve::new(sensitivity, curve));
+}
+
+/// Velocity to pitch sweep depth curve. Sensitivity between 0.0 and 1.0, curve exponent between 0.1 and 10.0
+#[no_mangle]
+unsafe extern "C" fn synth_kick_set_velocity_pitch_curve(
+    ptr: *mut SynthKick,
+    sensitivity: f32,
+    curve: f32,
+) {
+    ptr.as_mut()
+        .unwrap_unchecked()
+        .set_velocity_pitch_curve(VelocityCurve::new(sensitivity, curve));
+}
+
+/// Velocity to transient level curve. Sensitivity between 0.0 and 1.0, curve exponent between 0.1 and 10.0
+#[no_mangle]
+unsafe extern "C" fn synth_kick_set_velocity_click_curve(
+    ptr: *mut SynthKick,
+    sensitivity: f32,
+    curve: f32,
+) {
+    ptr.as_mut()
+        .unwrap_unchecked()
+        .set_velocity_click_curve(VelocityCurve::new(sensitivity, curve));
+}
+
+/// Only accepts values between 0.0 and 1.0, otherwise clamps
+#[no_mangle]
+unsafe extern "C" fn synth_kick_set_accent_amount(ptr: *mut SynthKick, val: f32) {
+    ptr.as_mut().unwrap_unchecked().set_accent_amount(val);
+}
+
+/// Linear drive gain. Values below 1.0 are clamped, 1.0 bypasses the overdrive stage
+#[no_mangle]
+unsafe extern "C" fn synth_kick_set_overdrive(ptr: *mut SynthKick, val: f32) {
+    ptr.as_mut().unwrap_unchecked().set_overdrive(val);
+}
+
+/// Curve of the overdrive stage
+#[no_mangle]
+unsafe extern "C" fn synth_kick_set_overdrive_shape(ptr: *mut SynthKick, shape: Waveshaper) {
+    ptr.as_mut().unwrap_unchecked().set_overdrive_shape(shape);
+}
+
+/// Only accepts values between 0.0 and 1.0, otherwise clamps
+#[no_mangle]
+unsafe extern "C" fn synth_kick_set_overdrive_tone(ptr: *mut SynthKick, val: f32) {
+    ptr.as_mut().unwrap_unchecked().set_overdrive_tone(val);
+}
+
+/// Deprecated, use `synth_kick_set_overdrive_tone` instead
+#[no_mangle]
+unsafe extern "C" fn synth_kick_set_overdrive_param(ptr: *mut SynthKick, val: f32) {
+    ptr.as_mut().unwrap_unchecked().set_overdrive_tone(val);
+}
+
+/// Oversampling of the overdrive stage, 1, 2, 4 or 8. Other values round down.
+#[no_mangle]
+unsafe extern "C" fn synth_kick_set_overdrive_oversampling(ptr: *mut SynthKick, factor: usize) {
+    ptr.as_mut()
+        .unwrap_unchecked()
+        .set_overdrive_oversampling(factor);
+}
+
+/// Delay of the overdrive stage in samples, 0.0 while it is bypassed
+#[no_mangle]
+unsafe extern "C" fn synth_kick_overdrive_latency_samples(ptr: *const SynthKick) -> f32 {
+    ptr.as_ref().unwrap_unchecked().overdrive_latency_samples()
+}
+
+/// Depth of the pitch sweep on top of the pitch in Hz
+#[no_mangle]
+unsafe extern "C" fn synth_kick_set_sweep_depth_hz(ptr: *mut SynthKick, hz: f32) {
+    ptr.as_mut().unwrap_unchecked().set_sweep_depth_hz(hz);
+}
+
+/// Depth of the pitch sweep above the pitch in semitones
+#[no_mangle]
+unsafe extern "C" fn synth_kick_set_sweep_depth_semitones(ptr: *mut SynthKick, semitones: f32) {
+    ptr.as_mut()
+        .unwrap_unchecked()
+        .set_sweep_depth_semitones(semitones);
+}
+
+/// Time of the pitch sweep in seconds, between 5 ms and 2 s
+#[no_mangle]
+unsafe extern "C" fn synth_kick_set_sweep_time(ptr: *mut SynthKick, seconds: f32) {
+    ptr.as_mut().unwrap_unchecked().set_sweep_time(seconds);
+}
+
+/// Only accepts values between 0.0 and 1.0, otherwise clamps. 0.5 is linear
+#[no_mangle]
+unsafe extern "C" fn synth_kick_set_sweep_curve(ptr: *mut SynthKick, val: f32) {
+    ptr.as_mut().unwrap_unchecked().set_sweep_curve(val);
+}
+
+/// Only accepts values between 0.0 and 1.0, otherwise clamps
+#[no_mangle]
+unsafe extern "C" fn synth_kick_set_click_level(ptr: *mut SynthKick, val: f32) {
+    ptr.as_mut().unwrap_unchecked().set_click_level(val);
+}
+
+/// Only accepts values between 0.0 and 1.0, otherwise clamps
+#[no_mangle]
+unsafe extern "C" fn synth_kick_set_noise_level(ptr: *mut SynthKick, val: f32) {
+    ptr.as_mut().unwrap_unchecked().set_noise_level(val);
+}
+
+/// Only accepts values between 0.0 and 1.0, otherwise clamps
+#[no_mangle]
+unsafe extern "C" fn synth_kick_set_transient_decay(ptr: *mut SynthKick, val: f32) {
+    ptr.as_mut().unwrap_unchecked().set_transient_decay(val);
+}
+
+/// Only accepts values between 0.0 and 1.0, otherwise clamps
+#[no_mangle]
+unsafe extern "C" fn synth_kick_set_transient_tone(ptr: *mut SynthKick, val: f32) {
+    ptr.as_mut().unwrap_unchecked().set_transient_tone(val);
+}
+
+/// Shape of the kick body
+#[no_mangle]
+unsafe extern "C" fn synth_kick_set_waveform(ptr: *mut SynthKick, wave: Waveform) {
+    ptr.as_mut().unwrap_unchecked().set_waveform(wave);
+}
+
+/// Blend from a sine (0.0) to the waveform (1.0). Only accepts values between 0.0 and 1.0, otherwise clamps
+#[no_mangle]
+unsafe extern "C" fn synth_kick_set_waveform_morph(ptr: *mut SynthKick, val: f32) {
+    ptr.as_mut().unwrap_unchecked().set_waveform_morph(val);
+}
+
+/// Frequency of the FM modulator relative to the body, between 0.25 and 16.0
+#[no_mangle]
+unsafe extern "C" fn synth_kick_set_fm_ratio(ptr: *mut SynthKick, ratio: f32) {
+    ptr.as_mut().unwrap_unchecked().set_fm_ratio(ratio);
+}
+
+/// Only accepts values between 0.0 and 1.0, otherwise clamps. 0.0 turns FM off
+#[no_mangle]
+unsafe extern "C" fn synth_kick_set_fm_index(ptr: *mut SynthKick, val: f32) {
+    ptr.as_mut().unwrap_unchecked().set_fm_index(val);
+}
+
+/// Only accepts values between 0.0 and 1.0, otherwise clamps
+#[no_mangle]
+unsafe extern "C" fn synth_kick_set_fm_decay(ptr: *mut SynthKick, val: f32) {
+    ptr.as_mut().unwrap_unchecked().set_fm_decay(val);
+}
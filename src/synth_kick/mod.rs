@@ -1,14 +1,23 @@
 mod ffi;
+mod transient;
+mod voice;
 
-use embedded_audio_tools::{
-    envelopes::{AudioRateADSR, EnvelopeState},
-    float::lerp_unchecked,
-    FunctionalOscillator, PhaseAccumulator, SoftPhaseAccumulator,
-};
+use embedded_audio_tools::{float::lerp_unchecked, Waveform};
+use libm::{exp2f, expf, powf};
+
+use crate::drum_voice::{DrumVoice, TriggerSchedule};
+use crate::oversampler::Oversampler;
+use crate::waveshaper::Waveshaper;
+use voice::{BodyParams, Hit, KickVoice};
 
 /// cbindgen:ignore
 const SYNTH_KICK_LOWEST_DRIVE: f32 = 1.0;
 
+/// cbindgen:ignore
+const DARKEST_OD_TONE: f32 = 200.0; // Hz
+/// cbindgen:ignore
+const BRIGHTEST_OD_TONE: f32 = 20000.0; // Hz
+
 /// cbindgen:ignore
 const SHORTEST_ATTACK: f32 = 0.0001; // s
 /// cbindgen:ignore
@@ -19,166 +28,1040 @@ const SHORTEST_DECAY: f32 = 0.100; // s
 /// cbindgen:ignore
 const LONGEST_DECAY: f32 = 2.0; // s
 
+/// cbindgen:ignore
+const LONGEST_HOLD: f32 = 2.0; // s
+
+/// cbindgen:ignore
+const SHORTEST_RELEASE: f32 = 0.010; // s
+/// cbindgen:ignore
+const LONGEST_RELEASE: f32 = 2.0; // s
+
+/// cbindgen:ignore
+const LONGEST_GLIDE: f32 = 1.0; // s
+/// cbindgen:ignore
+const GLIDE_DONE: f32 = 0.0001;
+
 /// cbindgen:ignore
 const LOWEST_PITCH: f32 = 25.0; // Hz
 /// cbindgen:ignore
 const HIGHEST_PITCH: f32 = 125.0; // Hz
 /// cbindgen:ignore
-const PITCH_RANGE: f32 = 200.0; // Hz
-/// cbindgen:ignore
 const DEFAULT_PITCH: f32 = 40.0; // Hz
 
+/// cbindgen:ignore
+const DEFAULT_SWEEP_DEPTH: f32 = 200.0; // Hz
+/// cbindgen:ignore
+const MAX_SWEEP_DEPTH_HZ: f32 = 2000.0; // Hz
+/// cbindgen:ignore
+const MAX_SWEEP_DEPTH_SEMITONES: f32 = 72.0;
+
+/// cbindgen:ignore
+const DEFAULT_SWEEP_TIME: f32 = 0.05; // s
+/// cbindgen:ignore
+const SHORTEST_SWEEP_TIME: f32 = 0.005; // s
+/// cbindgen:ignore
+const LONGEST_SWEEP_TIME: f32 = 2.0; // s
+
+/// cbindgen:ignore
+const SWEEP_CURVE_CONVEX: f32 = 0.25;
+/// cbindgen:ignore
+const SWEEP_CURVE_CONCAVE: f32 = 4.0;
+
+/// cbindgen:ignore
+const LOWEST_FM_RATIO: f32 = 0.25;
+/// cbindgen:ignore
+const HIGHEST_FM_RATIO: f32 = 16.0;
+/// cbindgen:ignore
+const MAX_FM_INDEX: f32 = 10.0; // rad
+
+/// cbindgen:ignore
+const SHORTEST_FM_DECAY: f32 = 0.005; // s
+/// cbindgen:ignore
+const LONGEST_FM_DECAY: f32 = 1.0; // s
+
 /// cbindgen:ignore
 const FADE_OUT: f32 = 0.035; // s
+/// cbindgen:ignore
+const RETRIGGER_FADE: f32 = 0.005; // s
+
+/// cbindgen:ignore
+const MIN_VELOCITY_CURVE: f32 = 0.1;
+/// cbindgen:ignore
+const MAX_VELOCITY_CURVE: f32 = 10.0;
 
-#[derive(PartialEq)]
+/// What happens when the kick gets triggered while a hit is still ringing
+#[derive(Clone, Copy, PartialEq)]
 #[repr(C)]
-enum KickState {
-    Idle,
-    Triggered,
-    Retriggered,
+pub enum RetriggerPolicy {
+    /// Restarts the ringing hit immediately
+    HardCut,
+    /// Crossfades from the ringing hit to the new one within 5 ms
+    FastFade,
+    /// Lets the ringing hit decay underneath the new one
+    Layered,
+}
+
+/// Unit of the pitch sweep depth
+#[derive(Clone, Copy, PartialEq)]
+#[repr(C)]
+pub enum SweepUnit {
+    /// Added linearly on top of the pitch
+    Hertz,
+    /// Relative to the pitch, sweeps exponentially
+    Semitones,
+}
+
+/// Maps the velocity of a hit onto the scaling of a parameter
+#[derive(Clone, Copy)]
+#[repr(C)]
+pub struct VelocityCurve {
+    /// 0.0 ignores the velocity, 1.0 scales the parameter all the way down to zero
+    sensitivity: f32,
+    /// Exponent applied to the velocity, 1.0 is linear
+    curve: f32,
+}
+
+impl VelocityCurve {
+    /// Only accepts sensitivities between 0.0 and 1.0 and curves between 0.1 and 10.0, otherwise clamps
+    pub fn new(sensitivity: f32, curve: f32) -> VelocityCurve {
+        VelocityCurve {
+            sensitivity: sensitivity.clamp(0.0, 1.0),
+            curve: curve.clamp(MIN_VELOCITY_CURVE, MAX_VELOCITY_CURVE),
+        }
+    }
+
+    /// Scaling factor between `1.0 - sensitivity` and 1.0
+    pub fn apply(&self, velocity: f32) -> f32 {
+        1.0 - self.sensitivity + self.sensitivity * powf(velocity.clamp(0.0, 1.0), self.curve)
+    }
 }
 
 #[repr(C)]
 pub struct SynthKick {
-    // Audio Tools
-    pitch_env: AudioRateADSR,
-    volume_env: AudioRateADSR,
-    osc: FunctionalOscillator<SoftPhaseAccumulator>,
+    // Voices, the third one lets a fast roll fade out two hits at once
+    voices: [KickVoice; 3],
+    body: BodyParams,
+    schedule: TriggerSchedule,
 
     // State
-    current_sample: f32,
-    state: KickState,
-    global_pitch: f32,
-    retrigger_slope: f32,
-    retrigger_fade_out_amp: f32,
+    sr: f32,
+    current_voice: usize,
+
+    // Glide
+    target_pitch: f32,
+    glide_from: f32,
+    glide_left: f32,
+    glide_coeff: f32,
+
+    // Overdrive
+    oversampler: Oversampler,
+    od_tone_coeff: f32,
+    od_tone_state: f32,
+    od_compensation: f32,
 
     // Params
+    glide_time: f32,
+    gate_mode: bool,
+    retrigger_policy: RetriggerPolicy,
     overdrive: f32,
-    od_param: f32,
+    od_shape: Waveshaper,
+    od_tone: f32,
+    amp_velocity: VelocityCurve,
+    pitch_velocity: VelocityCurve,
+    click_velocity: VelocityCurve,
+    accent_amount: f32,
 }
 
 impl SynthKick {
     pub fn init(sr: f32) -> SynthKick {
-        SynthKick {
-            pitch_env: AudioRateADSR::new(0.01, 0.05, 0.0, 0.10, 0.5, sr),
-            volume_env: AudioRateADSR::new(0.01, 3.0, 0.0, 0.10, 0.5, sr),
-            osc: FunctionalOscillator::new(SoftPhaseAccumulator::new(DEFAULT_PITCH, sr)),
+        let mut kick = SynthKick {
+            voices: [
+                KickVoice::init(sr),
+                KickVoice::init(sr),
+                KickVoice::init(sr),
+            ],
+            body: BodyParams::new(),
+            schedule: TriggerSchedule::new(),
 
-            current_sample: 0.0,
-            state: KickState::Idle,
-            global_pitch: DEFAULT_PITCH,
-            retrigger_slope: 1.0 / (FADE_OUT * sr),
-            retrigger_fade_out_amp: 1.0,
+            sr,
+            current_voice: 0,
 
+            target_pitch: DEFAULT_PITCH,
+            glide_from: DEFAULT_PITCH,
+            glide_left: 0.0,
+            glide_coeff: 1.0,
+
+            oversampler: Oversampler::new(),
+            od_tone_coeff: 1.0,
+            od_tone_state: 0.0,
+            od_compensation: 1.0,
+
+            glide_time: 0.0,
+            gate_mode: false,
+            retrigger_policy: RetriggerPolicy::FastFade,
             overdrive: SYNTH_KICK_LOWEST_DRIVE,
-            od_param: 1.0,
-        }
+            od_shape: Waveshaper::Tanh,
+            od_tone: 1.0,
+            amp_velocity: VelocityCurve::new(1.0, 1.0),
+            pitch_velocity: VelocityCurve::new(0.5, 1.0),
+            click_velocity: VelocityCurve::new(1.0, 1.0),
+            accent_amount: 0.3,
+        };
+
+        kick.update_od_tone();
+        kick.update_glide();
+        kick
     }
 
+    /// Triggers with full velocity and no accent
     pub fn trigger(&mut self) {
-        match self.state {
-            KickState::Idle => {
-                self.pitch_env.reset();
-                self.pitch_env.trigger_on();
+        self.trigger_with_velocity(1.0);
+    }
 
-                self.volume_env.reset();
-                self.volume_env.trigger_on();
+    /// Only accepts velocities between 0.0 and 1.0, otherwise clamps
+    pub fn trigger_with_velocity(&mut self, velocity: f32) {
+        self.trigger_with_accent(velocity, false);
+    }
 
-                self.osc.set_phase_shift_unchecked(0.0); // reset phase
+    /// Accented hits are louder and sweep deeper by the accent amount
+    pub fn trigger_with_accent(&mut self, velocity: f32, accent: bool) {
+        let hit = self.hit_for(velocity.clamp(0.0, 1.0), accent);
 
-                self.state = KickState::Triggered;
-            }
+        match self.retrigger_policy {
+            RetriggerPolicy::HardCut => self.voices[self.current_voice].start(hit),
 
-            KickState::Triggered => {
-                self.retrigger_fade_out_amp = 1.0;
-
-                self.state = KickState::Retriggered;
+            RetriggerPolicy::FastFade => {
+                if self.voices[self.current_voice].is_active() {
+                    self.voices[self.current_voice].crossfade_out();
+                    self.current_voice = self.quietest_voice();
+                }
+                self.voices[self.current_voice].start(hit);
             }
 
-            KickState::Retriggered => {} // already retriggered
+            RetriggerPolicy::Layered => {
+                if self.voices[self.current_voice].is_active() {
+                    // a gated hit would otherwise sustain forever underneath
+                    if self.gate_mode {
+                        self.voices[self.current_voice].release();
+                    }
+                    self.current_voice = (self.current_voice + 1) % self.voices.len();
+                }
+                self.voices[self.current_voice].start(hit);
+            }
         }
     }
 
-    pub fn tick(&mut self) -> f32 {
-        match self.state {
-            KickState::Idle => self.current_sample = 0.0,
+    /// Triggers with full velocity on the `offset`th call of `tick`, counting from zero
+    ///
+    /// Up to four triggers can be pending, further ones get dropped.
+    pub fn trigger_at(&mut self, offset: usize) {
+        self.trigger_at_with_accent(offset, 1.0, false);
+    }
 
-            KickState::Triggered => {
-                if self.volume_env.get_state() == EnvelopeState::Idle {
-                    self.state = KickState::Idle;
-                }
+    /// Like `trigger_with_accent`, but on the `offset`th call of `tick`, counting from zero
+    pub fn trigger_at_with_accent(&mut self, offset: usize, velocity: f32, accent: bool) {
+        self.schedule.schedule(offset, velocity, accent);
+    }
 
-                // apply pitch envelope
-                self.osc
-                    .set_freq_unchecked(self.global_pitch + PITCH_RANGE * self.pitch_env.tick());
+    /// Note off. In gate mode the kick sustains until released, otherwise it is ignored.
+    pub fn release(&mut self) {
+        if self.gate_mode {
+            self.for_each_voice(KickVoice::release);
+        }
+    }
 
-                // apply volume curve
-                self.current_sample = self.volume_env.tick() * self.osc.next();
-            }
+    /// Quickly fades out all ringing hits and drops pending triggers
+    pub fn choke(&mut self) {
+        self.voices.iter_mut().for_each(KickVoice::choke);
+        self.schedule.clear();
+    }
 
-            KickState::Retriggered => {
-                if (self.current_sample.is_sign_positive() && self.current_sample >= 0.0001)
-                    || (self.current_sample.is_sign_negative() && self.current_sample <= -0.0001)
-                {
-                    self.retrigger_fade_out_amp -= self.retrigger_slope;
-                    self.current_sample *= self.retrigger_fade_out_amp;
-                } else {
-                    self.state = KickState::Idle;
+    pub fn tick(&mut self) -> f32 {
+        if let Some(trigger) = self.schedule.advance() {
+            self.trigger_with_accent(trigger.velocity, trigger.accent);
+        }
 
-                    self.trigger();
-                }
+        if self.glide_left > 0.0 {
+            self.glide_left -= self.glide_coeff * self.glide_left;
+            if self.glide_left < GLIDE_DONE {
+                self.glide_left = 0.0;
             }
+            self.body.pitch = lerp_unchecked(self.target_pitch, self.glide_from, self.glide_left);
         }
 
-        return self.current_sample;
+        let body = &self.body;
+        let sample = self.voices.iter_mut().map(|voice| voice.tick(body)).sum();
+
+        self.apply_overdrive(sample)
     }
 
     pub fn update_sr(&mut self, sr: f32) {
-        self.pitch_env.set_sr(sr);
-        self.volume_env.set_sr(sr);
-        self.osc.set_sr_unchecked(sr);
-        self.retrigger_slope = 1.0 / (FADE_OUT * sr); // quick inaudible fade out
+        self.voices.iter_mut().for_each(|voice| voice.update_sr(sr));
+        self.sr = sr;
+        self.update_od_tone();
+        self.update_glide();
     }
 
+    pub fn set_retrigger_policy(&mut self, policy: RetriggerPolicy) {
+        self.retrigger_policy = policy;
+    }
+
+    /// Linear drive gain. Values below 1.0 are clamped, 1.0 bypasses the overdrive stage.
     pub fn set_overdrive(&mut self, val: f32) {
         if val >= SYNTH_KICK_LOWEST_DRIVE {
             self.overdrive = val;
         } else {
             self.overdrive = SYNTH_KICK_LOWEST_DRIVE;
         }
+
+        self.od_compensation = self.od_shape.compensation(self.overdrive);
+    }
+
+    /// Curve of the overdrive stage
+    pub fn set_overdrive_shape(&mut self, shape: Waveshaper) {
+        self.od_shape = shape;
+        self.od_compensation = self.od_shape.compensation(self.overdrive);
+    }
+
+    /// Lowpass after the overdrive. Only accepts values between 0.0 and 1.0, otherwise clamps
+    pub fn set_overdrive_tone(&mut self, val: f32) {
+        self.od_tone = val.clamp(0.0, 1.0);
+        self.update_od_tone();
     }
 
+    /// The former overdrive parameter, which now sets the tone
+    #[deprecated(note = "use `set_overdrive_tone` instead")]
     pub fn set_overdrive_param(&mut self, val: f32) {
-        self.od_param = val;
+        self.set_overdrive_tone(val);
+    }
+
+    /// Oversampling of the overdrive stage, 1, 2, 4 or 8. Other values round down.
+    pub fn set_overdrive_oversampling(&mut self, factor: usize) {
+        self.oversampler.set_factor(factor);
+    }
+
+    /// Delay of the overdrive stage in samples caused by the oversampling, 0.0 while it is bypassed
+    pub fn overdrive_latency_samples(&self) -> f32 {
+        if self.overdrive > SYNTH_KICK_LOWEST_DRIVE {
+            self.oversampler.latency_samples()
+        } else {
+            0.0
+        }
+    }
+
+    /// How the velocity scales the amplitude of a hit
+    pub fn set_velocity_amp_curve(&mut self, curve: VelocityCurve) {
+        self.amp_velocity = curve;
+    }
+
+    /// How the velocity scales the depth of the pitch sweep
+    pub fn set_velocity_pitch_curve(&mut self, curve: VelocityCurve) {
+        self.pitch_velocity = curve;
+    }
+
+    /// How the velocity scales the level of the transient layer
+    pub fn set_velocity_click_curve(&mut self, curve: VelocityCurve) {
+        self.click_velocity = curve;
+    }
+
+    /// Only accepts values between 0.0 and 1.0, otherwise clamps
+    pub fn set_accent_amount(&mut self, val: f32) {
+        self.accent_amount = val.clamp(0.0, 1.0);
     }
 
     /// Only accepts values between 0.0 and 1.0, otherwise clamps
     pub fn set_attack(&mut self, val: f32) {
-        self.volume_env.set_attack(lerp_unchecked(
-            SHORTEST_ATTACK,
-            LONGEST_ATTACK,
-            val.clamp(0.0, 1.0),
-        ));
+        let attack = lerp_unchecked(SHORTEST_ATTACK, LONGEST_ATTACK, val.clamp(0.0, 1.0));
+        self.for_each_voice(|voice| voice.volume_env.set_attack(attack));
     }
 
     /// Only accepts values between 0.0 and 1.0, otherwise clamps
     pub fn set_decay(&mut self, val: f32) {
-        self.volume_env.set_decay(lerp_unchecked(
-            SHORTEST_DECAY,
-            LONGEST_DECAY,
-            val.clamp(0.0, 1.0),
-        ));
+        let decay = lerp_unchecked(SHORTEST_DECAY, LONGEST_DECAY, val.clamp(0.0, 1.0));
+        self.for_each_voice(|voice| voice.volume_env.set_decay(decay));
+    }
 
-        // self.volume_env.set_release(lerp_unchecked(
-        //     SHORTEST_DECAY,
-        //     LONGEST_DECAY,
-        //     val.clamp(0.0, 1.0),
-        // ));
+    /// Fade out after `release` in gate mode. Only accepts values between 0.0 and 1.0, otherwise clamps
+    pub fn set_release(&mut self, val: f32) {
+        let release = lerp_unchecked(SHORTEST_RELEASE, LONGEST_RELEASE, val.clamp(0.0, 1.0));
+        self.for_each_voice(|voice| voice.volume_env.set_release(release));
+    }
+
+    /// Time the kick stays at full volume before decaying, up to 2 s.
+    /// Only accepts values between 0.0 and 1.0, otherwise clamps
+    pub fn set_hold(&mut self, val: f32) {
+        let hold = LONGEST_HOLD * val.clamp(0.0, 1.0);
+        let sr = self.sr;
+        self.for_each_voice(|voice| voice.set_hold(hold, sr));
+    }
+
+    /// In gate mode the kick holds its volume while the note is on and fades out on `release`,
+    /// otherwise it decays on its own
+    pub fn set_gate_mode(&mut self, gate_mode: bool) {
+        let sustain = if gate_mode { 1.0 } else { 0.0 };
+        self.for_each_voice(|voice| voice.volume_env.set_sustain(sustain));
+
+        // don't leave sustaining hits behind
+        if self.gate_mode && !gate_mode {
+            self.for_each_voice(KickVoice::release);
+        }
+        self.gate_mode = gate_mode;
     }
 
     /// Only accepts values between 0.0 and 1.0, otherwise clamps
     pub fn set_pitch(&mut self, val: f32) {
-        self.global_pitch = lerp_unchecked(LOWEST_PITCH, HIGHEST_PITCH, val.clamp(0.0, 1.0));
+        self.target_pitch = lerp_unchecked(LOWEST_PITCH, HIGHEST_PITCH, val.clamp(0.0, 1.0));
+        self.body.pitch = self.target_pitch;
+        self.glide_left = 0.0;
+    }
+
+    /// Tunes the kick to a MIDI note, detuned by `cents`. While the kick is still ringing,
+    /// the pitch glides to the new note within the glide time.
+    pub fn set_note(&mut self, midi_note: u8, cents: f32) {
+        self.target_pitch = note_to_freq(midi_note, cents);
+
+        if self.glide_time == 0.0 || !self.voices.iter().any(KickVoice::is_active) {
+            self.body.pitch = self.target_pitch;
+            self.glide_left = 0.0;
+        } else {
+            self.glide_from = self.body.pitch;
+            self.glide_left = 1.0;
+        }
+    }
+
+    /// Portamento between notes, up to 1 s. Only accepts values between 0.0 and 1.0, otherwise clamps
+    pub fn set_glide(&mut self, val: f32) {
+        self.glide_time = LONGEST_GLIDE * val.clamp(0.0, 1.0);
+        self.update_glide();
+    }
+
+    /// Shape of the kick body
+    pub fn set_waveform(&mut self, wave: Waveform) {
+        // `Waveform` is not `Clone`, so rebuild it for every voice
+        self.for_each_voice(|voice| {
+            voice.osc.set_waveform(match &wave {
+                Waveform::Sine => Waveform::Sine,
+                Waveform::Rectangle => Waveform::Rectangle,
+                Waveform::Sawtooth => Waveform::Sawtooth,
+                Waveform::Triangle => Waveform::Triangle,
+            })
+        });
+    }
+
+    /// Blend from a sine (0.0) to the waveform (1.0). Only accepts values between 0.0 and 1.0, otherwise clamps
+    pub fn set_waveform_morph(&mut self, val: f32) {
+        self.for_each_voice(|voice| voice.osc.set_morph(val));
+    }
+
+    /// Frequency of the FM modulator relative to the body, between 0.25 and 16.0
+    pub fn set_fm_ratio(&mut self, ratio: f32) {
+        self.body.fm_ratio = ratio.clamp(LOWEST_FM_RATIO, HIGHEST_FM_RATIO);
+    }
+
+    /// Depth of the FM modulator, 0.0 turns it off. Only accepts values between 0.0 and 1.0, otherwise clamps
+    pub fn set_fm_index(&mut self, val: f32) {
+        self.body.fm_index = val.clamp(0.0, 1.0) * MAX_FM_INDEX;
+    }
+
+    /// Decay of the FM envelope. Only accepts values between 0.0 and 1.0, otherwise clamps
+    pub fn set_fm_decay(&mut self, val: f32) {
+        let decay = lerp_unchecked(SHORTEST_FM_DECAY, LONGEST_FM_DECAY, val.clamp(0.0, 1.0));
+        self.for_each_voice(|voice| voice.fm_env.set_decay(decay));
+    }
+
+    /// Level of the pitched click. Only accepts values between 0.0 and 1.0, otherwise clamps
+    pub fn set_click_level(&mut self, val: f32) {
+        self.for_each_voice(|voice| voice.transient.set_click_level(val));
+    }
+
+    /// Level of the noise burst. Only accepts values between 0.0 and 1.0, otherwise clamps
+    pub fn set_noise_level(&mut self, val: f32) {
+        self.for_each_voice(|voice| voice.transient.set_noise_level(val));
+    }
+
+    /// Decay of click and noise from 1 ms to 50 ms. Only accepts values between 0.0 and 1.0, otherwise clamps
+    pub fn set_transient_decay(&mut self, val: f32) {
+        self.for_each_voice(|voice| voice.transient.set_decay(val));
+    }
+
+    /// Pitch of the click and brightness of the noise. Only accepts values between 0.0 and 1.0, otherwise clamps
+    pub fn set_transient_tone(&mut self, val: f32) {
+        self.for_each_voice(|voice| voice.transient.set_tone(val));
+    }
+
+    /// Depth of the pitch sweep on top of the pitch in Hz
+    pub fn set_sweep_depth_hz(&mut self, hz: f32) {
+        self.body.sweep_depth = hz.clamp(0.0, MAX_SWEEP_DEPTH_HZ);
+        self.body.sweep_unit = SweepUnit::Hertz;
+    }
+
+    /// Depth of the pitch sweep above the pitch in semitones
+    pub fn set_sweep_depth_semitones(&mut self, semitones: f32) {
+        self.body.sweep_depth = semitones.clamp(0.0, MAX_SWEEP_DEPTH_SEMITONES);
+        self.body.sweep_unit = SweepUnit::Semitones;
+    }
+
+    /// Time of the pitch sweep in seconds, between 5 ms and 2 s
+    pub fn set_sweep_time(&mut self, seconds: f32) {
+        let time = seconds.clamp(SHORTEST_SWEEP_TIME, LONGEST_SWEEP_TIME);
+        self.for_each_voice(|voice| voice.pitch_env.set_decay(time));
+    }
+
+    /// Shape of the pitch sweep. Only accepts values between 0.0 and 1.0, otherwise clamps
+    ///
+    /// 0.0 holds the pitch up for longer, 0.5 is linear and 1.0 drops quickly into a long tail.
+    pub fn set_sweep_curve(&mut self, val: f32) {
+        self.body.sweep_curve = SWEEP_CURVE_CONVEX
+            * powf(
+                SWEEP_CURVE_CONCAVE / SWEEP_CURVE_CONVEX,
+                val.clamp(0.0, 1.0),
+            );
+    }
+
+    fn hit_for(&self, velocity: f32, accent: bool) -> Hit {
+        let accent = if accent {
+            1.0 + self.accent_amount
+        } else {
+            1.0
+        };
+
+        Hit {
+            amp: self.amp_velocity.apply(velocity) * accent,
+            pitch_depth: self.pitch_velocity.apply(velocity) * accent,
+            click: self.click_velocity.apply(velocity) * accent,
+        }
+    }
+
+    /// An idle voice if there is one, otherwise the one that faded out the furthest
+    fn quietest_voice(&self) -> usize {
+        (0..self.voices.len())
+            .min_by(|a, b| {
+                self.voices[*a]
+                    .fade_level()
+                    .total_cmp(&self.voices[*b].fade_level())
+            })
+            .unwrap_or(0)
+    }
+
+    fn for_each_voice(&mut self, f: impl FnMut(&mut KickVoice)) {
+        self.voices.iter_mut().for_each(f);
+    }
+
+    fn apply_overdrive(&mut self, input: f32) -> f32 {
+        if self.overdrive <= SYNTH_KICK_LOWEST_DRIVE {
+            return input;
+        }
+
+        let shape = self.od_shape;
+        let shaped = self
+            .oversampler
+            .process(input * self.overdrive, |x| shape.process(x))
+            * self.od_compensation;
+
+        // one pole lowpass tames the upper harmonics
+        self.od_tone_state += self.od_tone_coeff * (shaped - self.od_tone_state);
+        self.od_tone_state
+    }
+
+    fn update_glide(&mut self) {
+        // the remaining distance falls to `GLIDE_DONE` within the glide time and then snaps
+        self.glide_coeff = if self.glide_time > 0.0 {
+            1.0 - powf(GLIDE_DONE, 1.0 / (self.glide_time * self.sr))
+        } else {
+            1.0
+        };
+    }
+
+    fn update_od_tone(&mut self) {
+        let cutoff = DARKEST_OD_TONE * powf(BRIGHTEST_OD_TONE / DARKEST_OD_TONE, self.od_tone);
+        self.od_tone_coeff =
+            1.0 - expf(-2.0 * core::f32::consts::PI * cutoff.min(0.45 * self.sr) / self.sr);
+    }
+}
+
+/// Equal tempered frequency of a MIDI note with A4 (69) at 440 Hz
+fn note_to_freq(midi_note: u8, cents: f32) -> f32 {
+    440.0 * exp2f((midi_note as f32 - 69.0 + cents / 100.0) / 12.0)
+}
+
+impl DrumVoice for SynthKick {
+    fn trigger(&mut self) {
+        SynthKick::trigger(self);
+    }
+
+    fn trigger_at(&mut self, offset: usize) {
+        SynthKick::trigger_at(self, offset);
+    }
+
+    fn choke(&mut self) {
+        SynthKick::choke(self);
+    }
+
+    fn tick(&mut self) -> f32 {
+        SynthKick::tick(self)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::transient::Transient;
+    use super::*;
+    use embedded_audio_tools::envelopes::EnvelopeState;
+
+    const SR: f32 = 48000.0;
+
+    fn peak(kick: &mut SynthKick) -> f32 {
+        (0..SR as usize / 10)
+            .map(|_| kick.tick().abs())
+            .fold(0.0, f32::max)
+    }
+
+    #[test]
+    fn release_waits_for_attack_and_hold() {
+        let mut kick = SynthKick::init(SR);
+        kick.set_gate_mode(true);
+        kick.set_hold(0.05);
+
+        // staccato note-off one sample after the note-on
+        kick.trigger();
+        kick.tick();
+        kick.release();
+
+        // 10 ms attack plus 100 ms hold
+        let state = |kick: &SynthKick| kick.voices[kick.current_voice].volume_env.get_state();
+        for _ in 0..(0.105 * SR) as usize {
+            kick.tick();
+        }
+        assert!(state(&kick) == EnvelopeState::Decay);
+
+        for _ in 0..(0.01 * SR) as usize {
+            kick.tick();
+        }
+        assert!(state(&kick) == EnvelopeState::Release);
+    }
+
+    #[test]
+    fn velocity_scales_amplitude() {
+        let mut kick = SynthKick::init(SR);
+        kick.trigger();
+        let full = peak(&mut kick);
+
+        let mut kick = SynthKick::init(SR);
+        kick.trigger_with_velocity(0.5);
+        let half = peak(&mut kick);
+
+        let mut kick = SynthKick::init(SR);
+        kick.trigger_with_accent(0.5, true);
+        let accented = peak(&mut kick);
+
+        assert!(full > 0.5);
+        assert!((half / full - 0.5).abs() < 0.05);
+        assert!(accented > half);
+    }
+
+    /// Energy of the 2nd to 9th harmonic relative to the fundamental of a driven 100 Hz sine
+    fn harmonic_ratio(kick: &mut SynthKick) -> f32 {
+        let freq = 100.0;
+        // whole number of periods
+        let output: [f32; SR as usize / 10] = core::array::from_fn(|i| {
+            kick.apply_overdrive(libm::sinf(
+                2.0 * core::f32::consts::PI * freq * i as f32 / SR,
+            ))
+        });
+
+        let power = |harmonic: f32| {
+            let (re, im) = output
+                .iter()
+                .enumerate()
+                .fold((0.0, 0.0), |(re, im), (i, x)| {
+                    let w = 2.0 * core::f32::consts::PI * harmonic * freq * i as f32 / SR;
+                    (re + x * libm::cosf(w), im + x * libm::sinf(w))
+                });
+            re * re + im * im
+        };
+
+        (2..10).map(|h| power(h as f32)).sum::<f32>() / power(1.0)
+    }
+
+    #[test]
+    fn harmonics_increase_with_drive() {
+        for shape in [
+            Waveshaper::Tanh,
+            Waveshaper::HardClip,
+            Waveshaper::Foldback,
+            Waveshaper::Tube,
+        ] {
+            let mut ratios = [0.0; 3];
+            for (ratio, drive) in ratios.iter_mut().zip([1.5, 3.0, 6.0]) {
+                let mut kick = SynthKick::init(SR);
+                kick.set_overdrive_shape(shape);
+                kick.set_overdrive(drive);
+                *ratio = harmonic_ratio(&mut kick);
+            }
+
+            assert!(ratios[0] > 1e-4, "{:?}: {:?}", shape, ratios);
+            assert!(
+                ratios[0] < ratios[1] && ratios[1] < ratios[2],
+                "{:?}: {:?}",
+                shape,
+                ratios
+            );
+        }
+    }
+
+    #[test]
+    fn overdrive_bypassed_by_default() {
+        let mut kick = SynthKick::init(SR);
+        assert_eq!(kick.apply_overdrive(0.5), 0.5);
+
+        kick.set_overdrive(0.5);
+        assert_eq!(kick.apply_overdrive(-0.25), -0.25);
+    }
+
+    #[test]
+    fn dark_tone_removes_harmonics() {
+        let mut bright = SynthKick::init(SR);
+        bright.set_overdrive(6.0);
+        let mut dark = SynthKick::init(SR);
+        dark.set_overdrive(6.0);
+        dark.set_overdrive_tone(0.0);
+
+        assert!(harmonic_ratio(&mut dark) < harmonic_ratio(&mut bright));
+    }
+
+    #[test]
+    fn sweep_depth_units() {
+        let mut kick = SynthKick::init(SR);
+        assert_eq!(kick.body.swept_pitch(0.0, 1.0), DEFAULT_PITCH);
+        assert_eq!(
+            kick.body.swept_pitch(1.0, 1.0),
+            DEFAULT_PITCH + DEFAULT_SWEEP_DEPTH
+        );
+
+        kick.set_sweep_depth_semitones(24.0);
+        assert_eq!(kick.body.swept_pitch(0.0, 1.0), DEFAULT_PITCH);
+        assert!((kick.body.swept_pitch(1.0, 1.0) - 4.0 * DEFAULT_PITCH).abs() < 1e-3);
+        assert!((kick.body.swept_pitch(0.5, 1.0) - 2.0 * DEFAULT_PITCH).abs() < 1e-3);
+
+        kick.set_sweep_depth_hz(0.0);
+        assert_eq!(kick.body.swept_pitch(1.0, 1.0), DEFAULT_PITCH);
+    }
+
+    #[test]
+    fn sweep_curve_shapes() {
+        let mut kick = SynthKick::init(SR);
+        kick.set_sweep_curve(0.5);
+        assert!(
+            (kick.body.swept_pitch(0.5, 1.0) - (DEFAULT_PITCH + 0.5 * DEFAULT_SWEEP_DEPTH)).abs()
+                < 1e-3
+        );
+
+        kick.set_sweep_curve(1.0);
+        let concave = kick.body.swept_pitch(0.5, 1.0);
+        kick.set_sweep_curve(0.0);
+        let convex = kick.body.swept_pitch(0.5, 1.0);
+
+        assert!((concave - (DEFAULT_PITCH + 0.0625 * DEFAULT_SWEEP_DEPTH)).abs() < 1e-3);
+        assert!(convex > DEFAULT_PITCH + 0.8 * DEFAULT_SWEEP_DEPTH);
+    }
+
+    #[test]
+    fn transient_layer() {
+        let mut plain = SynthKick::init(SR);
+        plain.trigger();
+        let plain_onset: f32 = (0..48).map(|_| plain.tick().abs()).sum();
+
+        let mut clicky = SynthKick::init(SR);
+        clicky.set_click_level(1.0);
+        clicky.set_noise_level(1.0);
+        clicky.set_transient_decay(0.0);
+        clicky.trigger();
+        let clicky_onset: f32 = (0..48).map(|_| clicky.tick().abs()).sum();
+
+        assert!(clicky_onset > 2.0 * plain_onset);
+
+        // transient is gone after a few milliseconds
+        for _ in 0..480 {
+            plain.tick();
+            clicky.tick();
+        }
+        assert!((plain.tick() - clicky.tick()).abs() < 1e-3);
+    }
+
+    #[test]
+    fn transient_is_silent_by_default() {
+        let mut transient = Transient::init(SR);
+        transient.trigger();
+        assert!((0..480).all(|_| transient.tick() == 0.0));
+    }
+
+    #[test]
+    fn rectangle_body() {
+        let mut kick = SynthKick::init(SR);
+        kick.set_waveform(Waveform::Rectangle);
+        kick.trigger();
+
+        // envelope scaled rectangle keeps the sign for whole half periods
+        let out: [f32; 4800] = core::array::from_fn(|_| kick.tick());
+        let sign_changes = out
+            .windows(2)
+            .filter(|w| (w[0] < 0.0) != (w[1] < 0.0))
+            .count();
+        assert!(sign_changes > 4 && sign_changes < 100);
+        // only the band-limited steps pass through zero
+        let near_zero = out.iter().skip(480).filter(|x| x.abs() <= 0.1).count();
+        assert!(near_zero <= 2 * sign_changes);
+    }
+
+    #[test]
+    fn fm_changes_body() {
+        let mut plain = SynthKick::init(SR);
+        let mut fm = SynthKick::init(SR);
+        fm.set_fm_ratio(3.0);
+        fm.set_fm_index(0.5);
+        plain.trigger();
+        fm.trigger();
+
+        let difference: f32 = (0..4800).map(|_| (plain.tick() - fm.tick()).abs()).sum();
+        assert!(difference > 10.0);
+
+        // without index the modulator is skipped
+        let mut silent_fm = SynthKick::init(SR);
+        silent_fm.set_fm_ratio(3.0);
+        let mut plain = SynthKick::init(SR);
+        plain.trigger();
+        silent_fm.trigger();
+        assert!((0..4800).all(|_| plain.tick() == silent_fm.tick()));
+    }
+
+    #[test]
+    fn sample_accurate_trigger() {
+        let mut kick = SynthKick::init(SR);
+        kick.trigger_at(100);
+
+        let mut block = [0.0; 256];
+        kick.process(&mut block);
+
+        assert!(block[..100].iter().all(|x| *x == 0.0));
+        assert!(block[101..].iter().any(|x| *x != 0.0));
+    }
+
+    #[test]
+    fn retrigger_policies() {
+        let render = |policy| {
+            let mut kick = SynthKick::init(SR);
+            kick.set_retrigger_policy(policy);
+            kick.trigger();
+            for _ in 0..4800 {
+                kick.tick();
+            }
+            kick.trigger();
+            let out: [f32; 4800] = core::array::from_fn(|_| kick.tick());
+            (
+                out,
+                kick.voices.iter().filter(|voice| voice.is_active()).count(),
+            )
+        };
+
+        let (_, hard_cut_voices) = render(RetriggerPolicy::HardCut);
+        let (_, fast_fade_voices) = render(RetriggerPolicy::FastFade);
+        let (layered, layered_voices) = render(RetriggerPolicy::Layered);
+
+        assert_eq!(hard_cut_voices, 1);
+        assert_eq!(fast_fade_voices, 1);
+        assert_eq!(layered_voices, 2);
+        assert!(layered.iter().any(|x| x.abs() > 1.0));
+    }
+
+    /// Largest jump between two successive samples
+    fn max_step(out: &[f32]) -> f32 {
+        out.windows(2)
+            .map(|w| (w[1] - w[0]).abs())
+            .fold(0.0, f32::max)
+    }
+
+    #[test]
+    fn retrigger_starts_immediately() {
+        let mut kick = SynthKick::init(SR);
+        kick.trigger();
+        for _ in 0..2400 {
+            kick.tick();
+        }
+
+        kick.trigger();
+        kick.tick();
+        assert_eq!(kick.voices.iter().filter(|v| v.is_active()).count(), 2);
+
+        // the old hit is gone after the crossfade, whatever its phase
+        for _ in 0..(RETRIGGER_FADE * SR) as usize + 1 {
+            kick.tick();
+        }
+        assert_eq!(kick.voices.iter().filter(|v| v.is_active()).count(), 1);
+    }
+
+    #[test]
+    fn flam_is_click_free() {
+        let mut single = SynthKick::init(SR);
+        single.trigger();
+        let single: [f32; 4800] = core::array::from_fn(|_| single.tick());
+
+        let mut kick = SynthKick::init(SR);
+        kick.trigger();
+        kick.trigger_at(480); // 10 ms flam
+        let mut flam = [0.0; 4800];
+        kick.process(&mut flam);
+
+        assert!(max_step(&flam) < 2.0 * max_step(&single));
+    }
+
+    #[test]
+    fn fast_retrigger_is_click_free() {
+        let mut single = SynthKick::init(SR);
+        single.trigger();
+        let single: [f32; 4800] = core::array::from_fn(|_| single.tick());
+
+        // hits 3 ms apart, within the retrigger fade of the previous two
+        let mut kick = SynthKick::init(SR);
+        for i in 0..8 {
+            kick.trigger_at(i * 144);
+        }
+        let mut roll = [0.0; 4800];
+        kick.process(&mut roll);
+
+        assert!(max_step(&roll) < 2.0 * max_step(&single));
+
+        // a third hit within 5 ms starts on the spare voice
+        let mut kick = SynthKick::init(SR);
+        kick.trigger_at(0);
+        kick.trigger_at(100);
+        kick.trigger_at(200);
+        let mut block = [0.0; 201];
+        kick.process(&mut block);
+        assert!(kick.voices.iter().all(KickVoice::is_active));
+    }
+
+    #[test]
+    fn roll_keeps_retriggering() {
+        let mut kick = SynthKick::init(SR);
+        let mut single = SynthKick::init(SR);
+        single.trigger();
+        let single: [f32; 1200] = core::array::from_fn(|_| single.tick());
+
+        // a 32nd note roll at 150 bpm, humanized by a few ms
+        for i in 0..16 {
+            kick.trigger_at((i % 3) * 200);
+            let mut block = [0.0; 1200];
+            kick.process(&mut block);
+
+            assert!(max_step(&block) < 2.0 * max_step(&single));
+            assert!(block.iter().any(|x| x.abs() > 0.5));
+        }
+    }
+
+    #[test]
+    fn choke_silences() {
+        let mut kick = SynthKick::init(SR);
+        kick.trigger();
+        for _ in 0..480 {
+            kick.tick();
+        }
+
+        kick.choke();
+        let out: [f32; 4800] = core::array::from_fn(|_| kick.tick());
+        assert!(out[..480].iter().any(|x| *x != 0.0));
+        assert!(out[(FADE_OUT * SR) as usize + 1..]
+            .iter()
+            .all(|x| *x == 0.0));
+    }
+
+    #[test]
+    fn gate_mode_sustains_until_release() {
+        let mut kick = SynthKick::init(SR);
+        kick.set_gate_mode(true);
+        kick.set_decay(0.0);
+        kick.set_release(0.0);
+        kick.trigger();
+
+        for _ in 0..SR as usize {
+            kick.tick();
+        }
+        let held = (0..480).map(|_| kick.tick().abs()).fold(0.0, f32::max);
+        assert!(held > 0.5);
+
+        kick.release();
+        for _ in 0..(SHORTEST_RELEASE * SR) as usize + 1 {
+            kick.tick();
+        }
+        assert!((0..480).all(|_| kick.tick() == 0.0));
+    }
+
+    #[test]
+    fn hold_delays_decay() {
+        let ring_time = |hold| {
+            let mut kick = SynthKick::init(SR);
+            kick.set_decay(0.0);
+            kick.set_hold(hold);
+            kick.trigger();
+            (0..SR as usize * 4)
+                .position(|_| {
+                    kick.tick();
+                    !kick.voices[0].is_active()
+                })
+                .unwrap()
+        };
+
+        let hold_samples = (0.5 * LONGEST_HOLD * SR) as usize;
+        assert!(ring_time(0.5) >= ring_time(0.0) + hold_samples);
+    }
+
+    #[test]
+    fn note_pitch() {
+        assert_eq!(note_to_freq(69, 0.0), 440.0);
+        assert!((note_to_freq(33, 0.0) - 55.0).abs() < 1e-3);
+        assert!((note_to_freq(33, 100.0) - note_to_freq(34, 0.0)).abs() < 1e-3);
+
+        let mut kick = SynthKick::init(SR);
+        kick.set_note(36, 0.0);
+        assert!((kick.body.pitch - 65.406).abs() < 1e-2);
+    }
+
+    #[test]
+    fn glide_while_ringing() {
+        let mut kick = SynthKick::init(SR);
+        kick.set_glide(0.1);
+        kick.set_note(36, 0.0);
+        let start = kick.body.pitch;
+
+        // jumps while silent, glides while ringing
+        kick.trigger();
+        kick.tick();
+        kick.set_note(43, 0.0);
+        assert_eq!(kick.body.pitch, start);
+
+        let glide_samples = (0.1 * LONGEST_GLIDE * SR) as usize;
+        for _ in 0..glide_samples / 2 {
+            kick.tick();
+        }
+        assert!(kick.body.pitch > start && kick.body.pitch < kick.target_pitch);
+
+        // arrives within the glide time
+        for _ in 0..glide_samples / 2 + 1 {
+            kick.tick();
+        }
+        assert_eq!(kick.body.pitch, kick.target_pitch);
+    }
+
+    #[test]
+    fn velocity_curves() {
+        let linear = VelocityCurve::new(1.0, 1.0);
+        assert_eq!(linear.apply(0.25), 0.25);
+        assert_eq!(linear.apply(2.0), 1.0);
+
+        let insensitive = VelocityCurve::new(0.0, 3.0);
+        assert_eq!(insensitive.apply(0.1), 1.0);
+
+        let half = VelocityCurve::new(0.5, 2.0);
+        assert_eq!(half.apply(0.0), 0.5);
+        assert_eq!(half.apply(0.5), 0.625);
     }
 }
@@ -0,0 +1,144 @@
+use core::f32::consts::PI;
+
+use libm::{expf, powf, sinf};
+
+use crate::noise::WhiteNoise;
+
+/// cbindgen:ignore
+const SHORTEST_TRANSIENT_DECAY: f32 = 0.001; // s
+/// cbindgen:ignore
+const LONGEST_TRANSIENT_DECAY: f32 = 0.050; // s
+
+/// cbindgen:ignore
+const LOWEST_CLICK_PITCH: f32 = 500.0; // Hz
+/// cbindgen:ignore
+const HIGHEST_CLICK_PITCH: f32 = 5000.0; // Hz
+
+/// cbindgen:ignore
+const DARKEST_NOISE: f32 = 1000.0; // Hz
+/// cbindgen:ignore
+const BRIGHTEST_NOISE: f32 = 16000.0; // Hz
+
+/// Envelope level below which the transient stops rendering
+/// cbindgen:ignore
+const SILENCE: f32 = 0.0001;
+
+/// Short pitched click and lowpassed noise burst on a shared exponential envelope
+#[repr(C)]
+pub struct Transient {
+    noise: WhiteNoise,
+    sr: f32,
+
+    // State
+    amp: f32,
+    click_phase: f32,
+    noise_state: f32,
+
+    // Coefficients
+    decay_coeff: f32,
+    click_increment: f32,
+    noise_coeff: f32,
+
+    // Params
+    click_level: f32,
+    noise_level: f32,
+    decay: f32,
+    tone: f32,
+}
+
+impl Transient {
+    pub fn init(sr: f32) -> Transient {
+        let mut transient = Transient {
+            noise: WhiteNoise::default(),
+            sr,
+
+            amp: 0.0,
+            click_phase: 0.0,
+            noise_state: 0.0,
+
+            decay_coeff: 0.0,
+            click_increment: 0.0,
+            noise_coeff: 0.0,
+
+            click_level: 0.0,
+            noise_level: 0.0,
+            decay: 0.3,
+            tone: 0.5,
+        };
+
+        transient.update_coeffs();
+        transient
+    }
+
+    pub fn trigger(&mut self) {
+        self.amp = 1.0;
+        self.click_phase = 0.0;
+    }
+
+    pub fn tick(&mut self) -> f32 {
+        if self.amp < SILENCE || (self.click_level == 0.0 && self.noise_level == 0.0) {
+            return 0.0;
+        }
+
+        let click = sinf(2.0 * PI * self.click_phase);
+        self.click_phase += self.click_increment;
+        if self.click_phase >= 1.0 {
+            self.click_phase -= 1.0;
+        }
+
+        self.noise_state += self.noise_coeff * (self.noise.tick() - self.noise_state);
+
+        let out = self.amp * (self.click_level * click + self.noise_level * self.noise_state);
+        self.amp *= self.decay_coeff;
+
+        out
+    }
+
+    pub fn set_sr(&mut self, sr: f32) {
+        self.sr = sr;
+        self.update_coeffs();
+    }
+
+    /// Only accepts values between 0.0 and 1.0, otherwise clamps
+    pub fn set_click_level(&mut self, val: f32) {
+        self.click_level = val.clamp(0.0, 1.0);
+    }
+
+    /// Only accepts values between 0.0 and 1.0, otherwise clamps
+    pub fn set_noise_level(&mut self, val: f32) {
+        self.noise_level = val.clamp(0.0, 1.0);
+    }
+
+    /// Only accepts values between 0.0 and 1.0, otherwise clamps
+    pub fn set_decay(&mut self, val: f32) {
+        self.decay = val.clamp(0.0, 1.0);
+        self.update_coeffs();
+    }
+
+    /// Pitch of the click and brightness of the noise.
+    /// Only accepts values between 0.0 and 1.0, otherwise clamps
+    pub fn set_tone(&mut self, val: f32) {
+        self.tone = val.clamp(0.0, 1.0);
+        self.update_coeffs();
+    }
+
+    fn update_coeffs(&mut self) {
+        let decay = exp_range(
+            SHORTEST_TRANSIENT_DECAY,
+            LONGEST_TRANSIENT_DECAY,
+            self.decay,
+        );
+        self.decay_coeff = expf(-1.0 / (decay * self.sr));
+
+        self.click_increment =
+            exp_range(LOWEST_CLICK_PITCH, HIGHEST_CLICK_PITCH, self.tone) / self.sr;
+
+        let cutoff = exp_range(DARKEST_NOISE, BRIGHTEST_NOISE, self.tone).min(0.45 * self.sr);
+        self.noise_coeff = 1.0 - expf(-2.0 * PI * cutoff / self.sr);
+    }
+}
+
+/// Exponential mapping of `val` between 0.0 and 1.0 onto `min` to `max`
+fn exp_range(min: f32, max: f32, val: f32) -> f32 {
+    min * powf(max / min, val)
+}
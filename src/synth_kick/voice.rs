@@ -0,0 +1,245 @@
+use core::f32::consts::PI;
+
+use embedded_audio_tools::envelopes::{AudioRateADSR, EnvelopeState};
+use libm::{exp2f, powf};
+
+use super::transient::Transient;
+use super::{
+    SweepUnit, DEFAULT_PITCH, DEFAULT_SWEEP_DEPTH, DEFAULT_SWEEP_TIME, FADE_OUT, RETRIGGER_FADE,
+};
+use crate::oscillator::MorphOscillator;
+
+/// cbindgen:ignore
+const HOLD_LEVEL: f32 = 1.0;
+
+/// Levels of a single hit, derived from its velocity and accent
+#[derive(Clone, Copy)]
+#[repr(C)]
+pub struct Hit {
+    pub amp: f32,
+    pub pitch_depth: f32,
+    pub click: f32,
+}
+
+/// Parameters of the kick body, shared by all voices
+#[repr(C)]
+pub struct BodyParams {
+    pub pitch: f32,
+    pub sweep_depth: f32,
+    pub sweep_unit: SweepUnit,
+    pub sweep_curve: f32,
+    pub fm_ratio: f32,
+    pub fm_index: f32,
+}
+
+impl BodyParams {
+    pub fn new() -> BodyParams {
+        BodyParams {
+            pitch: DEFAULT_PITCH,
+            sweep_depth: DEFAULT_SWEEP_DEPTH,
+            sweep_unit: SweepUnit::Hertz,
+            sweep_curve: 1.0,
+            fm_ratio: 1.0,
+            fm_index: 0.0,
+        }
+    }
+
+    /// Oscillator frequency for the pitch envelope value `env`, with the sweep scaled by `depth`
+    pub fn swept_pitch(&self, env: f32, depth: f32) -> f32 {
+        let sweep = self.sweep_depth * depth * powf(env, self.sweep_curve);
+
+        match self.sweep_unit {
+            SweepUnit::Hertz => self.pitch + sweep,
+            SweepUnit::Semitones => self.pitch * exp2f(sweep / 12.0),
+        }
+    }
+}
+
+/// Everything that rings for a single hit of the kick
+#[repr(C)]
+pub struct KickVoice {
+    pub pitch_env: AudioRateADSR,
+    pub volume_env: AudioRateADSR,
+    pub fm_env: AudioRateADSR,
+    pub osc: MorphOscillator,
+    pub modulator: MorphOscillator,
+    pub transient: Transient,
+
+    hit: Hit,
+    choke_amp: f32,
+    choke_slope: f32,
+    retrigger_slope: f32,
+    fade_slope: f32,
+    choked: bool,
+    hold_left: usize,
+    release_pending: bool,
+
+    hold_time: f32,
+    hold_samples: usize,
+}
+
+impl KickVoice {
+    pub fn init(sr: f32) -> KickVoice {
+        KickVoice {
+            pitch_env: AudioRateADSR::new(0.01, DEFAULT_SWEEP_TIME, 0.0, 0.10, 0.5, sr),
+            volume_env: AudioRateADSR::new(0.01, 3.0, 0.0, 0.10, 0.5, sr),
+            fm_env: AudioRateADSR::new(0.001, 0.1, 0.0, 0.10, 0.5, sr),
+            osc: MorphOscillator::new(DEFAULT_PITCH, sr),
+            modulator: MorphOscillator::new(DEFAULT_PITCH, sr),
+            transient: Transient::init(sr),
+
+            hit: Hit {
+                amp: 1.0,
+                pitch_depth: 1.0,
+                click: 1.0,
+            },
+            choke_amp: 1.0,
+            choke_slope: 1.0 / (FADE_OUT * sr),
+            retrigger_slope: 1.0 / (RETRIGGER_FADE * sr),
+            fade_slope: 0.0,
+            choked: false,
+            hold_left: 0,
+            release_pending: false,
+
+            hold_time: 0.0,
+            hold_samples: 0,
+        }
+    }
+
+    pub fn start(&mut self, hit: Hit) {
+        self.hit = hit;
+        self.choke_amp = 1.0;
+        self.choked = false;
+        self.hold_left = self.hold_samples;
+        self.release_pending = false;
+
+        self.transient.trigger();
+
+        self.pitch_env.reset();
+        self.pitch_env.trigger_on();
+
+        self.volume_env.reset();
+        self.volume_env.trigger_on();
+
+        self.fm_env.reset();
+        self.fm_env.trigger_on();
+
+        self.osc.reset_phase();
+        self.modulator.reset_phase();
+    }
+
+    /// Fades the voice out within 35 ms
+    pub fn choke(&mut self) {
+        self.fade_out(self.choke_slope);
+    }
+
+    /// Fades the voice out within 5 ms, while a new hit fades in on another voice
+    pub fn crossfade_out(&mut self) {
+        self.fade_out(self.retrigger_slope);
+    }
+
+    fn fade_out(&mut self, slope: f32) {
+        if self.is_active() {
+            // never slow down a fade that is already running
+            self.fade_slope = if self.choked {
+                self.fade_slope.max(slope)
+            } else {
+                slope
+            };
+            self.choked = true;
+        }
+    }
+
+    /// Releases the volume envelope, but not before the hold time has passed
+    pub fn release(&mut self) {
+        if self.volume_env.get_state() == EnvelopeState::Attack || self.hold_left > 0 {
+            self.release_pending = true;
+        } else {
+            self.volume_env.trigger_off();
+        }
+    }
+
+    /// Time the volume stays at its peak after the attack
+    pub fn set_hold(&mut self, seconds: f32, sr: f32) {
+        self.hold_time = seconds;
+        self.hold_samples = (seconds * sr) as usize;
+    }
+
+    /// Remaining level of a fade out, 1.0 while not fading and 0.0 once idle
+    pub fn fade_level(&self) -> f32 {
+        if !self.is_active() {
+            0.0
+        } else if self.choked {
+            self.choke_amp
+        } else {
+            1.0
+        }
+    }
+
+    pub fn is_active(&self) -> bool {
+        self.volume_env.get_state() != EnvelopeState::Idle
+    }
+
+    pub fn tick(&mut self, body: &BodyParams) -> f32 {
+        if !self.is_active() {
+            return 0.0;
+        }
+
+        // apply pitch envelope
+        let pitch = body.swept_pitch(self.pitch_env.tick(), self.hit.pitch_depth);
+        self.osc.set_freq(pitch);
+
+        // phase modulation, the modulator follows the pitch sweep
+        let fm_env = self.fm_env.tick();
+        let phase_offset = if body.fm_index > 0.0 {
+            self.modulator.set_freq(pitch * body.fm_ratio);
+            body.fm_index * fm_env * self.modulator.tick() / (2.0 * PI)
+        } else {
+            0.0
+        };
+
+        // apply volume curve, frozen at the peak while holding
+        let volume = if self.hold_left > 0 && self.volume_env.get_state() == EnvelopeState::Decay {
+            self.hold_left -= 1;
+            HOLD_LEVEL
+        } else {
+            // a release during the attack waits for the attack and the hold to finish
+            if self.release_pending
+                && self.hold_left == 0
+                && self.volume_env.get_state() != EnvelopeState::Attack
+            {
+                self.release_pending = false;
+                self.volume_env.trigger_off();
+            }
+            self.volume_env.tick()
+        };
+
+        let out = self.hit.amp * volume * self.osc.tick_with_offset(phase_offset)
+            + self.hit.click * self.transient.tick();
+
+        if self.choked {
+            self.choke_amp -= self.fade_slope;
+
+            if self.choke_amp <= 0.0 {
+                self.volume_env.reset();
+                return 0.0;
+            }
+
+            return out * self.choke_amp;
+        }
+
+        out
+    }
+
+    pub fn update_sr(&mut self, sr: f32) {
+        self.pitch_env.set_sr(sr);
+        self.volume_env.set_sr(sr);
+        self.fm_env.set_sr(sr);
+        self.osc.set_sr(sr);
+        self.modulator.set_sr(sr);
+        self.transient.set_sr(sr);
+        self.choke_slope = 1.0 / (FADE_OUT * sr);
+        self.retrigger_slope = 1.0 / (RETRIGGER_FADE * sr);
+        self.set_hold(self.hold_time, sr);
+    }
+}
@@ -0,0 +1,63 @@
+use crate::{DrumVoice, SynthSnare};
+
+/// Initializes `SynthSnare` struct
+#[no_mangle]
+extern "C" fn synth_snare_init(sr: f32) -> SynthSnare {
+    SynthSnare::init(sr)
+}
+
+/// Triggers the snare
+#[no_mangle]
+unsafe extern "C" fn synth_snare_trigger(ptr: *mut SynthSnare) {
+    ptr.as_mut().unwrap_unchecked().trigger();
+}
+
+/// Triggers the snare on the `offset`th call of `tick`, counting from zero
+#[no_mangle]
+unsafe extern "C" fn synth_snare_trigger_at(ptr: *mut SynthSnare, offset: usize) {
+    ptr.as_mut().unwrap_unchecked().trigger_at(offset);
+}
+
+/// Quickly fades out a ringing hit
+#[no_mangle]
+unsafe extern "C" fn synth_snare_choke(ptr: *mut SynthSnare) {
+    ptr.as_mut().unwrap_unchecked().choke();
+}
+
+/// Returns next sample
+#[no_mangle]
+unsafe extern "C" fn synth_snare_tick(ptr: *mut SynthSnare) -> f32 {
+    ptr.as_mut().unwrap_unchecked().tick()
+}
+
+/// Fills `out` with the next `length` samples
+#[no_mangle]
+unsafe extern "C" fn synth_snare_process(ptr: *mut SynthSnare, out: *mut f32, length: usize) {
+    ptr.as_mut()
+        .unwrap_unchecked()
+        .process(core::slice::from_raw_parts_mut(out, length));
+}
+
+/// Only accepts values between 0.0 and 1.0, otherwise clamps
+#[no_mangle]
+unsafe extern "C" fn synth_snare_set_pitch(ptr: *mut SynthSnare, val: f32) {
+    ptr.as_mut().unwrap_unchecked().set_pitch(val);
+}
+
+/// Only accepts values between 0.0 and 1.0, otherwise clamps
+#[no_mangle]
+unsafe extern "C" fn synth_snare_set_decay(ptr: *mut SynthSnare, val: f32) {
+    ptr.as_mut().unwrap_unchecked().set_decay(val);
+}
+
+/// Only accepts values between 0.0 and 1.0, otherwise clamps
+#[no_mangle]
+unsafe extern "C" fn synth_snare_set_tone(ptr: *mut SynthSnare, val: f32) {
+    ptr.as_mut().unwrap_unchecked().set_tone(val);
+}
+
+/// Only accepts values between 0.0 and 1.0, otherwise clamps
+#[no_mangle]
+unsafe extern "C" fn synth_snare_set_snappy(ptr: *mut SynthSnare, val: f32) {
+    ptr.as_mut().unwrap_unchecked().set_snappy(val);
+}
@@ -0,0 +1,218 @@
+mod ffi;
+
+use core::f32::consts::PI;
+
+use embedded_audio_tools::{
+    envelopes::{AudioRateADSR, EnvelopeState},
+    float::lerp_unchecked,
+    FunctionalOscillator, PhaseAccumulator, SoftPhaseAccumulator,
+};
+use libm::{expf, powf};
+
+use crate::drum_voice::{DrumVoice, TriggerSchedule};
+use crate::noise::WhiteNoise;
+
+/// cbindgen:ignore
+const LOWEST_SNARE_PITCH: f32 = 120.0; // Hz
+/// cbindgen:ignore
+const HIGHEST_SNARE_PITCH: f32 = 300.0; // Hz
+/// cbindgen:ignore
+const SNARE_SWEEP: f32 = 0.5; // relative to the pitch
+
+/// cbindgen:ignore
+const SHORTEST_SNARE_DECAY: f32 = 0.05; // s
+/// cbindgen:ignore
+const LONGEST_SNARE_DECAY: f32 = 0.6; // s
+
+/// cbindgen:ignore
+const DARKEST_SNARE_NOISE: f32 = 2000.0; // Hz
+/// cbindgen:ignore
+const BRIGHTEST_SNARE_NOISE: f32 = 12000.0; // Hz
+/// cbindgen:ignore
+const SNARE_NOISE_HIGHPASS: f32 = 400.0; // Hz
+
+/// Tuned drum body with a snappy noise layer
+#[repr(C)]
+pub struct SynthSnare {
+    // Audio Tools
+    pitch_env: AudioRateADSR,
+    body_env: AudioRateADSR,
+    noise_env: AudioRateADSR,
+    osc: FunctionalOscillator<SoftPhaseAccumulator>,
+    noise: WhiteNoise,
+    schedule: TriggerSchedule,
+
+    // State
+    sr: f32,
+    noise_lp_state: f32,
+    noise_hp_state: f32,
+    noise_lp_coeff: f32,
+    noise_hp_coeff: f32,
+
+    // Params
+    pitch: f32,
+    tone: f32,
+    snappy: f32,
+}
+
+impl SynthSnare {
+    pub fn init(sr: f32) -> SynthSnare {
+        let mut snare = SynthSnare {
+            pitch_env: AudioRateADSR::new(0.001, 0.03, 0.0, 0.01, 0.5, sr),
+            body_env: AudioRateADSR::new(0.001, 0.15, 0.0, 0.01, 0.5, sr),
+            noise_env: AudioRateADSR::new(0.001, 0.2, 0.0, 0.01, 0.5, sr),
+            osc: FunctionalOscillator::new(SoftPhaseAccumulator::new(LOWEST_SNARE_PITCH, sr)),
+            noise: WhiteNoise::default(),
+            schedule: TriggerSchedule::new(),
+
+            sr,
+            noise_lp_state: 0.0,
+            noise_hp_state: 0.0,
+            noise_lp_coeff: 1.0,
+            noise_hp_coeff: 0.0,
+
+            pitch: LOWEST_SNARE_PITCH,
+            tone: 0.5,
+            snappy: 0.5,
+        };
+
+        snare.update_filters();
+        snare
+    }
+
+    pub fn trigger(&mut self) {
+        self.pitch_env.reset();
+        self.pitch_env.trigger_on();
+
+        self.body_env.reset();
+        self.body_env.trigger_on();
+
+        self.noise_env.reset();
+        self.noise_env.trigger_on();
+
+        self.osc.set_phase_shift_unchecked(0.0); // reset phase
+    }
+
+    /// Triggers on the `offset`th call of `tick`, counting from zero
+    ///
+    /// Up to four triggers can be pending, further ones get dropped.
+    pub fn trigger_at(&mut self, offset: usize) {
+        self.schedule.schedule(offset, 1.0, false);
+    }
+
+    /// Quickly fades out a ringing hit
+    pub fn choke(&mut self) {
+        self.pitch_env.trigger_off();
+        self.body_env.trigger_off();
+        self.noise_env.trigger_off();
+    }
+
+    pub fn tick(&mut self) -> f32 {
+        if self.schedule.advance().is_some() {
+            self.trigger();
+        }
+
+        if self.body_env.get_state() == EnvelopeState::Idle
+            && self.noise_env.get_state() == EnvelopeState::Idle
+        {
+            return 0.0;
+        }
+
+        self.osc
+            .set_freq_unchecked(self.pitch * (1.0 + SNARE_SWEEP * self.pitch_env.tick()));
+        let body = self.body_env.tick() * self.osc.next();
+
+        // bandlimit the noise between a fixed highpass and the tone lowpass
+        let white = self.noise.tick();
+        self.noise_lp_state += self.noise_lp_coeff * (white - self.noise_lp_state);
+        self.noise_hp_state += self.noise_hp_coeff * (self.noise_lp_state - self.noise_hp_state);
+        let noise = self.noise_env.tick() * (self.noise_lp_state - self.noise_hp_state);
+
+        lerp_unchecked(body, noise, self.snappy)
+    }
+
+    pub fn update_sr(&mut self, sr: f32) {
+        self.pitch_env.set_sr(sr);
+        self.body_env.set_sr(sr);
+        self.noise_env.set_sr(sr);
+        self.osc.set_sr_unchecked(sr);
+        self.sr = sr;
+        self.update_filters();
+    }
+
+    /// Only accepts values between 0.0 and 1.0, otherwise clamps
+    pub fn set_pitch(&mut self, val: f32) {
+        self.pitch = lerp_unchecked(LOWEST_SNARE_PITCH, HIGHEST_SNARE_PITCH, val.clamp(0.0, 1.0));
+    }
+
+    /// Only accepts values between 0.0 and 1.0, otherwise clamps
+    pub fn set_decay(&mut self, val: f32) {
+        let decay = lerp_unchecked(
+            SHORTEST_SNARE_DECAY,
+            LONGEST_SNARE_DECAY,
+            val.clamp(0.0, 1.0),
+        );
+
+        self.body_env.set_decay(decay);
+        self.noise_env.set_decay(decay * 1.5); // the snares ring out longer than the shell
+    }
+
+    /// Brightness of the noise. Only accepts values between 0.0 and 1.0, otherwise clamps
+    pub fn set_tone(&mut self, val: f32) {
+        self.tone = val.clamp(0.0, 1.0);
+        self.update_filters();
+    }
+
+    /// Balance between body and noise. Only accepts values between 0.0 and 1.0, otherwise clamps
+    pub fn set_snappy(&mut self, val: f32) {
+        self.snappy = val.clamp(0.0, 1.0);
+    }
+
+    fn update_filters(&mut self) {
+        let cutoff =
+            DARKEST_SNARE_NOISE * powf(BRIGHTEST_SNARE_NOISE / DARKEST_SNARE_NOISE, self.tone);
+        self.noise_lp_coeff = 1.0 - expf(-2.0 * PI * cutoff.min(0.45 * self.sr) / self.sr);
+        self.noise_hp_coeff = 1.0 - expf(-2.0 * PI * SNARE_NOISE_HIGHPASS / self.sr);
+    }
+}
+
+impl DrumVoice for SynthSnare {
+    fn trigger(&mut self) {
+        SynthSnare::trigger(self);
+    }
+
+    fn trigger_at(&mut self, offset: usize) {
+        SynthSnare::trigger_at(self, offset);
+    }
+
+    fn choke(&mut self) {
+        SynthSnare::choke(self);
+    }
+
+    fn tick(&mut self) -> f32 {
+        SynthSnare::tick(self)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    const SR: f32 = 48000.0;
+
+    #[test]
+    fn hit_decays() {
+        let mut snare = SynthSnare::init(SR);
+        assert_eq!(snare.tick(), 0.0);
+
+        snare.set_decay(0.0);
+        snare.trigger();
+        let peak = (0..480).map(|_| snare.tick().abs()).fold(0.0, f32::max);
+        assert!(peak > 0.1);
+
+        for _ in 0..SR as usize / 2 {
+            snare.tick();
+        }
+        assert_eq!(snare.tick(), 0.0);
+    }
+}
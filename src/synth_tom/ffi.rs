@@ -0,0 +1,57 @@
+use crate::{DrumVoice, SynthTom};
+
+/// Initializes `SynthTom` struct
+#[no_mangle]
+extern "C" fn synth_tom_init(sr: f32) -> SynthTom {
+    SynthTom::init(sr)
+}
+
+/// Triggers the tom
+#[no_mangle]
+unsafe extern "C" fn synth_tom_trigger(ptr: *mut SynthTom) {
+    ptr.as_mut().unwrap_unchecked().trigger();
+}
+
+/// Triggers the tom on the `offset`th call of `tick`, counting from zero
+#[no_mangle]
+unsafe extern "C" fn synth_tom_trigger_at(ptr: *mut SynthTom, offset: usize) {
+    ptr.as_mut().unwrap_unchecked().trigger_at(offset);
+}
+
+/// Quickly fades out a ringing hit
+#[no_mangle]
+unsafe extern "C" fn synth_tom_choke(ptr: *mut SynthTom) {
+    ptr.as_mut().unwrap_unchecked().choke();
+}
+
+/// Returns next sample
+#[no_mangle]
+unsafe extern "C" fn synth_tom_tick(ptr: *mut SynthTom) -> f32 {
+    ptr.as_mut().unwrap_unchecked().tick()
+}
+
+/// Fills `out` with the next `length` samples
+#[no_mangle]
+unsafe extern "C" fn synth_tom_process(ptr: *mut SynthTom, out: *mut f32, length: usize) {
+    ptr.as_mut()
+        .unwrap_unchecked()
+        .process(core::slice::from_raw_parts_mut(out, length));
+}
+
+/// Only accepts values between 0.0 and 1.0, otherwise clamps
+#[no_mangle]
+unsafe extern "C" fn synth_tom_set_pitch(ptr: *mut SynthTom, val: f32) {
+    ptr.as_mut().unwrap_unchecked().set_pitch(val);
+}
+
+/// Only accepts values between 0.0 and 1.0, otherwise clamps
+#[no_mangle]
+unsafe extern "C" fn synth_tom_set_decay(ptr: *mut SynthTom, val: f32) {
+    ptr.as_mut().unwrap_unchecked().set_decay(val);
+}
+
+/// Only accepts values between 0.0 and 1.0, otherwise clamps
+#[no_mangle]
+unsafe extern "C" fn synth_tom_set_sweep(ptr: *mut SynthTom, val: f32) {
+    ptr.as_mut().unwrap_unchecked().set_sweep(val);
+}
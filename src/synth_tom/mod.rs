@@ -0,0 +1,155 @@
+mod ffi;
+
+use embedded_audio_tools::{
+    envelopes::{AudioRateADSR, EnvelopeState},
+    float::lerp_unchecked,
+    FunctionalOscillator, PhaseAccumulator, SoftPhaseAccumulator,
+};
+
+use crate::drum_voice::{DrumVoice, TriggerSchedule};
+
+/// cbindgen:ignore
+const LOWEST_TOM_PITCH: f32 = 60.0; // Hz
+/// cbindgen:ignore
+const HIGHEST_TOM_PITCH: f32 = 400.0; // Hz
+/// cbindgen:ignore
+const DEFAULT_TOM_PITCH: f32 = 120.0; // Hz
+/// cbindgen:ignore
+const MAX_TOM_SWEEP: f32 = 1.0; // relative to the pitch
+
+/// cbindgen:ignore
+const SHORTEST_TOM_DECAY: f32 = 0.1; // s
+/// cbindgen:ignore
+const LONGEST_TOM_DECAY: f32 = 1.5; // s
+
+/// Sine tom with a pitch drop
+#[repr(C)]
+pub struct SynthTom {
+    // Audio Tools
+    pitch_env: AudioRateADSR,
+    volume_env: AudioRateADSR,
+    osc: FunctionalOscillator<SoftPhaseAccumulator>,
+    schedule: TriggerSchedule,
+
+    // Params
+    pitch: f32,
+    sweep: f32,
+}
+
+impl SynthTom {
+    pub fn init(sr: f32) -> SynthTom {
+        SynthTom {
+            pitch_env: AudioRateADSR::new(0.001, 0.08, 0.0, 0.01, 0.5, sr),
+            volume_env: AudioRateADSR::new(0.001, 0.4, 0.0, 0.01, 0.5, sr),
+            osc: FunctionalOscillator::new(SoftPhaseAccumulator::new(DEFAULT_TOM_PITCH, sr)),
+            schedule: TriggerSchedule::new(),
+
+            pitch: DEFAULT_TOM_PITCH,
+            sweep: 0.3,
+        }
+    }
+
+    pub fn trigger(&mut self) {
+        self.pitch_env.reset();
+        self.pitch_env.trigger_on();
+
+        self.volume_env.reset();
+        self.volume_env.trigger_on();
+
+        self.osc.set_phase_shift_unchecked(0.0); // reset phase
+    }
+
+    /// Triggers on the `offset`th call of `tick`, counting from zero
+    ///
+    /// Up to four triggers can be pending, further ones get dropped.
+    pub fn trigger_at(&mut self, offset: usize) {
+        self.schedule.schedule(offset, 1.0, false);
+    }
+
+    /// Quickly fades out a ringing hit
+    pub fn choke(&mut self) {
+        self.pitch_env.trigger_off();
+        self.volume_env.trigger_off();
+    }
+
+    pub fn tick(&mut self) -> f32 {
+        if self.schedule.advance().is_some() {
+            self.trigger();
+        }
+
+        if self.volume_env.get_state() == EnvelopeState::Idle {
+            return 0.0;
+        }
+
+        self.osc
+            .set_freq_unchecked(self.pitch * (1.0 + self.sweep * self.pitch_env.tick()));
+
+        self.volume_env.tick() * self.osc.next()
+    }
+
+    pub fn update_sr(&mut self, sr: f32) {
+        self.pitch_env.set_sr(sr);
+        self.volume_env.set_sr(sr);
+        self.osc.set_sr_unchecked(sr);
+    }
+
+    /// Only accepts values between 0.0 and 1.0, otherwise clamps
+    pub fn set_pitch(&mut self, val: f32) {
+        self.pitch = lerp_unchecked(LOWEST_TOM_PITCH, HIGHEST_TOM_PITCH, val.clamp(0.0, 1.0));
+    }
+
+    /// Only accepts values between 0.0 and 1.0, otherwise clamps
+    pub fn set_decay(&mut self, val: f32) {
+        self.volume_env.set_decay(lerp_unchecked(
+            SHORTEST_TOM_DECAY,
+            LONGEST_TOM_DECAY,
+            val.clamp(0.0, 1.0),
+        ));
+    }
+
+    /// Depth of the pitch drop. Only accepts values between 0.0 and 1.0, otherwise clamps
+    pub fn set_sweep(&mut self, val: f32) {
+        self.sweep = val.clamp(0.0, 1.0) * MAX_TOM_SWEEP;
+    }
+}
+
+impl DrumVoice for SynthTom {
+    fn trigger(&mut self) {
+        SynthTom::trigger(self);
+    }
+
+    fn trigger_at(&mut self, offset: usize) {
+        SynthTom::trigger_at(self, offset);
+    }
+
+    fn choke(&mut self) {
+        SynthTom::choke(self);
+    }
+
+    fn tick(&mut self) -> f32 {
+        SynthTom::tick(self)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    const SR: f32 = 48000.0;
+
+    #[test]
+    fn hit_decays() {
+        let mut tom = SynthTom::init(SR);
+        assert_eq!(tom.tick(), 0.0);
+
+        tom.set_decay(0.0);
+        tom.trigger();
+        let peak = (0..4800).map(|_| tom.tick().abs()).fold(0.0, f32::max);
+        assert!(peak > 0.5);
+
+        for _ in 0..SR as usize / 2 {
+            tom.tick();
+        }
+        assert_eq!(tom.tick(), 0.0);
+    }
+}
@@ -0,0 +1,114 @@
+mod ffi;
+
+use crate::lfo::{tempo_sync_freq, Lfo};
+use crate::smoothed_param::{SmoothedParam, SmoothingMode, DEFAULT_SMOOTHING_MS};
+use crate::tools::Waveform;
+
+/// cbindgen:ignore
+const MIN_RATE: f32 = 0.01; // Hz
+
+/// cbindgen:ignore
+const MAX_RATE: f32 = 20.0; // Hz
+
+/// Stereo amplitude modulation
+#[repr(C)]
+pub struct Tremolo {
+    lfo: Lfo,
+
+    sr: f32,
+    smoothing_ms: f32,
+    stereo_phase: f32,
+    depth: SmoothedParam,
+}
+
+impl Tremolo {
+    pub fn init(sr: f32) -> Tremolo {
+        let mut tremolo = Tremolo {
+            lfo: Lfo::new(4.0, sr),
+
+            sr,
+            smoothing_ms: DEFAULT_SMOOTHING_MS,
+            stereo_phase: 0.0,
+            depth: SmoothedParam::new(0.5, SmoothingMode::Linear),
+        };
+
+        tremolo.set_smoothing_time(DEFAULT_SMOOTHING_MS);
+        tremolo
+    }
+
+    pub fn tick(&mut self, input: (f32, f32)) -> (f32, f32) {
+        let depth = self.depth.tick();
+        // the gain dips from 1.0 down to 1.0 - depth
+        let gain = |lfo: f32| 1.0 - depth * (0.5 - 0.5 * lfo);
+
+        let gain_l = gain(self.lfo.value_at(0.0));
+        let gain_r = gain(self.lfo.value_at(0.5 * self.stereo_phase));
+        self.lfo.advance(1);
+
+        (input.0 * gain_l, input.1 * gain_r)
+    }
+
+    /// Modulation speed between 0.01 and 20 Hz, otherwise clamps
+    pub fn set_rate(&mut self, hz: f32) {
+        self.lfo.set_freq(hz.clamp(MIN_RATE, MAX_RATE));
+    }
+
+    /// One period every `beats` beats at `bpm`, within the same limits as the rate
+    pub fn set_tempo_sync(&mut self, bpm: f32, beats: f32) {
+        if let Some(hz) = tempo_sync_freq(bpm, beats) {
+            self.set_rate(hz);
+        }
+    }
+
+    /// Restarts the modulation, e.g. on a bar line
+    pub fn reset_phase(&mut self) {
+        self.lfo.reset_phase(0.0);
+    }
+
+    pub fn set_waveform(&mut self, wave: Waveform) {
+        self.lfo.set_waveform(wave);
+    }
+
+    /// Only accepts values between 0.0 and 1.0, otherwise clamps
+    pub fn set_depth(&mut self, val: f32) {
+        self.depth.set_target(val.clamp(0.0, 1.0));
+    }
+
+    /// LFO phase offset of the right channel, 1.0 is half a period. Only accepts values between 0.0 and 1.0, otherwise clamps
+    pub fn set_stereo_phase(&mut self, val: f32) {
+        self.stereo_phase = val.clamp(0.0, 1.0);
+    }
+
+    /// Time in ms that depth changes take
+    pub fn set_smoothing_time(&mut self, ms: f32) {
+        self.smoothing_ms = ms;
+        self.depth.set_time_ms(ms, self.sr);
+    }
+
+    pub fn update_sr(&mut self, sr: f32) {
+        self.sr = sr;
+        self.set_smoothing_time(self.smoothing_ms);
+        self.lfo.set_sr(sr);
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn opposite_stereo_phase() {
+        let mut tremolo = Tremolo::init(48000.0);
+        tremolo.set_smoothing_time(0.0);
+        tremolo.set_depth(1.0);
+        tremolo.set_stereo_phase(1.0);
+        tremolo.set_waveform(Waveform::Triangle);
+
+        // with half a period offset the gains always add up to 1.0
+        for _ in 0..48000 {
+            let out = tremolo.tick((1.0, 1.0));
+            assert!((0.0..=1.0).contains(&out.0));
+            assert!((out.0 + out.1 - 1.0).abs() < 1e-4);
+        }
+    }
+}
@@ -0,0 +1,178 @@
+use libm::{exp2f, floorf, fmodf, tanhf};
+
+/// Static nonlinearities, shared by all drive stages of the crate
+#[derive(Clone, Copy, PartialEq, Debug)]
+#[repr(C)]
+pub enum Waveshaper {
+    /// Soft clipping
+    Tanh,
+    HardClip,
+    Foldback,
+    /// Asymmetric soft clipping, the softer negative half adds even harmonics
+    Tube,
+}
+
+impl Waveshaper {
+    /// Shapes `input` into the range of -1.0 to 1.0
+    #[inline(always)]
+    pub fn process(&self, input: f32) -> f32 {
+        match self {
+            Waveshaper::Tanh => tanhf(input),
+            Waveshaper::HardClip => input.clamp(-1.0, 1.0),
+            Waveshaper::Foldback => foldback(input),
+            Waveshaper::Tube => tube(input),
+        }
+    }
+
+    /// Gain that brings a sine driven by `drive` back to the RMS level of the clean sine
+    pub fn compensation(&self, drive: f32) -> f32 {
+        const POINTS: usize = 64;
+
+        let (clean, shaped) = (0..POINTS)
+            .map(|i| libm::sinf(2.0 * core::f32::consts::PI * i as f32 / POINTS as f32))
+            .fold((0.0, 0.0), |(clean, shaped), x| {
+                let y = self.process(drive * x);
+                (clean + x * x, shaped + y * y)
+            });
+
+        if shaped > 0.0 {
+            libm::sqrtf(clean / shaped)
+        } else {
+            1.0
+        }
+    }
+}
+
+/// Mirrors everything beyond ±1.0 back into range, like a triangle wave
+fn foldback(input: f32) -> f32 {
+    let mut phase = fmodf(input + 1.0, 4.0);
+    if phase < 0.0 {
+        phase += 4.0;
+    }
+
+    1.0 - (phase - 2.0).abs()
+}
+
+/// `tanh` above zero, a slower rational curve below, both with unity slope at zero
+fn tube(input: f32) -> f32 {
+    if input >= 0.0 {
+        tanhf(input)
+    } else {
+        input / (1.0 - input)
+    }
+}
+
+/// cbindgen:ignore
+const MAX_BITS: f32 = 24.0;
+
+/// cbindgen:ignore
+const MAX_DOWNSAMPLE: f32 = 64.0;
+
+/// Bit depth and sample rate reduction, the aliasing of the sample and hold is intended
+#[repr(C)]
+pub struct Bitcrusher {
+    step: f32,
+    rate: f32,
+    phase: f32,
+    hold: f32,
+}
+
+impl Bitcrusher {
+    pub fn new() -> Bitcrusher {
+        Bitcrusher {
+            step: 0.0,
+            rate: 1.0,
+            phase: 1.0,
+            hold: 0.0,
+        }
+    }
+
+    #[inline(always)]
+    pub fn process(&mut self, input: f32) -> f32 {
+        if self.phase >= 1.0 {
+            self.phase -= 1.0;
+            self.hold = if self.step > 0.0 {
+                floorf(input / self.step + 0.5) * self.step
+            } else {
+                input
+            };
+        }
+        self.phase += self.rate;
+
+        self.hold
+    }
+
+    /// Resolution between 1 and 24 bits, otherwise clamps. 24 bits bypasses the quantization.
+    pub fn set_bits(&mut self, bits: f32) {
+        let bits = bits.clamp(1.0, MAX_BITS);
+        self.step = if bits < MAX_BITS {
+            2.0 / exp2f(bits)
+        } else {
+            0.0
+        };
+    }
+
+    /// Holds every sample for `factor` samples, between 1 and 64, otherwise clamps
+    pub fn set_downsample(&mut self, factor: f32) {
+        self.rate = 1.0 / factor.clamp(1.0, MAX_DOWNSAMPLE);
+    }
+}
+
+impl Default for Bitcrusher {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn shapes_stay_in_range() {
+        for shaper in [
+            Waveshaper::Tanh,
+            Waveshaper::HardClip,
+            Waveshaper::Foldback,
+            Waveshaper::Tube,
+        ] {
+            for i in -100..=100 {
+                let out = shaper.process(i as f32 * 0.1);
+                assert!((-1.0..=1.0).contains(&out), "{:?} out of range", shaper);
+            }
+            assert_eq!(shaper.process(0.0), 0.0);
+        }
+    }
+
+    #[test]
+    fn foldback_mirrors() {
+        assert_eq!(foldback(0.5), 0.5);
+        assert_eq!(foldback(1.5), 0.5);
+        assert_eq!(foldback(-1.5), -0.5);
+        assert_eq!(foldback(3.0), -1.0);
+    }
+
+    #[test]
+    fn compensation_keeps_loudness() {
+        assert!((Waveshaper::HardClip.compensation(1.0) - 1.0).abs() < 1e-6);
+        assert!(Waveshaper::Tanh.compensation(10.0) < 1.0);
+    }
+
+    #[test]
+    fn tube_is_asymmetric() {
+        assert!((tube(0.001) + tube(-0.001)).abs() < 1e-5);
+        assert!(tube(2.0) > -tube(-2.0));
+    }
+
+    #[test]
+    fn bitcrusher_quantizes_and_holds() {
+        let mut crusher = Bitcrusher::new();
+        assert_eq!(crusher.process(0.3), 0.3);
+
+        crusher.set_bits(2.0);
+        crusher.set_downsample(2.0);
+        assert_eq!(crusher.process(0.3), 0.5);
+        assert_eq!(crusher.process(-0.9), 0.5);
+        assert_eq!(crusher.process(-0.9), -1.0);
+    }
+}
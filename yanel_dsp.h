@@ -17,6 +17,31 @@ typedef enum ButterworthType {
     ButterworthType_LowShelf = 5,
 } ButterworthType;
 
+/*
+ How the level of the sidechain is measured
+ */
+typedef enum DetectorMode {
+    /*
+     Absolute sample value, reacts to every transient
+     */
+    DetectorMode_Peak,
+    /*
+     Average power over 10 ms, closer to the perceived loudness
+     */
+    DetectorMode_Rms,
+} DetectorMode;
+
+/*
+ Drum synth behind a `DrumVoicePtr`
+ */
+typedef enum DrumKind {
+    DrumKind_Kick,
+    DrumKind_Snare,
+    DrumKind_Hat,
+    DrumKind_Clap,
+    DrumKind_Tom,
+} DrumKind;
+
 typedef enum EnvelopeState {
     EnvelopeState_Idle,
     EnvelopeState_Attack,
@@ -25,11 +50,86 @@ typedef enum EnvelopeState {
     EnvelopeState_Sustain,
 } EnvelopeState;
 
-typedef enum KickState {
-    KickState_Idle,
-    KickState_Triggered,
-    KickState_Retriggered,
-} KickState;
+/*
+ Footswitch events driving the `Looper` state machine
+ */
+typedef enum LooperEvent {
+    /*
+     Record, then cycles between play and overdub. Restarts a stopped loop.
+     */
+    LooperEvent_RecordPlay,
+    /*
+     Stops playback, closes a running recording first
+     */
+    LooperEvent_Stop,
+    /*
+     Erases the loop
+     */
+    LooperEvent_Clear,
+    /*
+     Reverts the last overdub, pressed again it redoes it
+     */
+    LooperEvent_Undo,
+    /*
+     Toggles backwards playback
+     */
+    LooperEvent_Reverse,
+    /*
+     Toggles playback at half speed, an octave down
+     */
+    LooperEvent_HalfSpeed,
+} LooperEvent;
+
+typedef enum LooperState {
+    LooperState_Empty,
+    LooperState_Recording,
+    LooperState_Playing,
+    LooperState_Overdubbing,
+    LooperState_Stopped,
+} LooperState;
+
+/*
+ What happens when the kick gets triggered while a hit is still ringing
+ */
+typedef enum RetriggerPolicy {
+    /*
+     Restarts the ringing hit immediately
+     */
+    RetriggerPolicy_HardCut,
+    /*
+     Crossfades from the ringing hit to the new one within 5 ms
+     */
+    RetriggerPolicy_FastFade,
+    /*
+     Lets the ringing hit decay underneath the new one
+     */
+    RetriggerPolicy_Layered,
+} RetriggerPolicy;
+
+typedef enum SmoothingMode {
+    /*
+     Ramps to the target in exactly the smoothing time
+     */
+    SmoothingMode_Linear,
+    /*
+     Approaches the target exponentially, settling to 99 % within the smoothing time
+     */
+    SmoothingMode_OnePole,
+} SmoothingMode;
+
+/*
+ Unit of the pitch sweep depth
+ */
+typedef enum SweepUnit {
+    /*
+     Added linearly on top of the pitch
+     */
+    SweepUnit_Hertz,
+    /*
+     Relative to the pitch, sweeps exponentially
+     */
+    SweepUnit_Semitones,
+} SweepUnit;
 
 typedef enum Waveform {
     Waveform_Sine = 0,
@@ -38,6 +138,67 @@ typedef enum Waveform {
     Waveform_Triangle = 3,
 } Waveform;
 
+/*
+ Static nonlinearities, shared by all drive stages of the crate
+ */
+typedef enum Waveshaper {
+    /*
+     Soft clipping
+     */
+    Waveshaper_Tanh,
+    Waveshaper_HardClip,
+    Waveshaper_Foldback,
+    /*
+     Asymmetric soft clipping, the softer negative half adds even harmonics
+     */
+    Waveshaper_Tube,
+} Waveshaper;
+
+typedef enum Window {
+    /*
+     Good frequency resolution, sidelobes at -31 dB
+     */
+    Window_Hann,
+    /*
+     Wider main lobe, sidelobes at -58 dB
+     */
+    Window_Blackman,
+} Window;
+
+/*
+ Bipolar low frequency oscillator for modulation effects
+ */
+typedef struct Lfo {
+    float phase;
+    float freq;
+    float increment;
+    float sr;
+    enum Waveform wave;
+} Lfo;
+
+/*
+ Parameter that glides to new values instead of jumping, which avoids zipper noise
+ */
+typedef struct SmoothedParam {
+    float current;
+    float target;
+    enum SmoothingMode mode;
+    float time_samples;
+    float step;
+    size_t steps_left;
+    float coeff;
+} SmoothedParam;
+
+/*
+ Moves a signal between the channels with an equal-power pan law
+ */
+typedef struct AutoPan {
+    struct Lfo lfo;
+    float sr;
+    float smoothing_ms;
+    struct SmoothedParam depth;
+} AutoPan;
+
 /*
  Raw mutable pointer that implements the `Send` trait since it's only acting on stack memory
  */
@@ -56,6 +217,148 @@ typedef struct DelayLine {
     size_t index;
 } DelayLine;
 
+/*
+ Stereo chorus with up to four voices reading from modulated delay taps
+ */
+typedef struct Chorus {
+    struct DelayLine delay_l;
+    struct DelayLine delay_r;
+    struct Lfo lfo;
+    float sr;
+    float smoothing_ms;
+    size_t voices;
+    float spread;
+    struct SmoothedParam depth;
+    struct SmoothedParam mix;
+} Chorus;
+
+/*
+ Level detection and attack/release ballistics shared by all dynamics processors
+ */
+typedef struct Detector {
+    enum DetectorMode mode;
+    bool stereo_link;
+    bool expanding;
+    float sr;
+    float attack;
+    float release;
+    float attack_coeff;
+    float release_coeff;
+    float rms_coeff;
+    float mean_square[2];
+    float reduction[2];
+} Detector;
+
+/*
+ Feed-forward stereo compressor with soft knee and makeup gain
+ */
+typedef struct Compressor {
+    struct Detector detector;
+    float threshold;
+    float ratio;
+    float knee;
+    struct SmoothedParam makeup;
+} Compressor;
+
+typedef struct BiquadCoeffs_Butterworth {
+    float b0;
+    float b1;
+    float b2;
+    float a1;
+    float a2;
+} BiquadCoeffs_Butterworth;
+
+/*
+ Little suite of filters in a `Biquad` topology.
+ */
+typedef struct Biquad_Butterworth {
+    float z1;
+    float z2;
+    struct BiquadCoeffs_Butterworth coeffs;
+} Biquad_Butterworth;
+
+/*
+ Bit depth and sample rate reduction, the aliasing of the sample and hold is intended
+ */
+typedef struct Bitcrusher {
+    float step;
+    float rate;
+    float phase;
+    float hold;
+} Bitcrusher;
+
+/*
+ One 2x up- and downsampling stage with polyphase halfband filters
+ */
+typedef struct HalfbandStage {
+    float up[24];
+    float down_even[24];
+    float down_odd[24];
+} HalfbandStage;
+
+/*
+ Runs a nonlinear mono processor at 2, 4 or 8 times the sample rate to reduce aliasing
+ */
+typedef struct Oversampler {
+    struct HalfbandStage stages[3];
+    size_t active;
+    float dry[64];
+    size_t dry_index;
+} Oversampler;
+
+/*
+ Waveshaping distortion with pre- and post-emphasis and a bitcrusher
+ */
+typedef struct Distortion {
+    struct Biquad_Butterworth pre;
+    struct Biquad_Butterworth post;
+    struct Bitcrusher crusher;
+    struct Oversampler oversampler;
+    float sr;
+    float smoothing_ms;
+    enum Waveshaper shape;
+    float emphasis_freq;
+    float emphasis_gain;
+    struct SmoothedParam drive;
+    struct SmoothedParam compensation;
+    struct SmoothedParam output;
+    struct SmoothedParam mix;
+} Distortion;
+
+/*
+ Pointer to any drum synth, so C can build choke groups of mixed voices
+ */
+typedef struct DrumVoicePtr {
+    enum DrumKind kind;
+    void *ptr;
+} DrumVoicePtr;
+
+/*
+ Feed-forward stereo downward expander, attenuates signals below the threshold
+ */
+typedef struct Expander {
+    struct Detector detector;
+    float threshold;
+    float ratio;
+    float knee;
+    float range;
+} Expander;
+
+/*
+ Flanger with feedback on a single modulated delay tap
+ */
+typedef struct Flanger {
+    struct DelayLine delay_line;
+    struct Lfo lfo;
+    float sr;
+    float smoothing_ms;
+    bool through_zero;
+    struct SmoothedParam manual;
+    struct SmoothedParam depth;
+    struct SmoothedParam feedback;
+    struct SmoothedParam mix;
+} Flanger;
+
 typedef struct Comb {
     struct DelayLine delay_line;
     float feedback;
@@ -82,51 +385,333 @@ typedef struct Freeverb {
     struct AllPass allpasses_l[4];
     struct AllPass allpasses_r[4];
     struct FreeverbParams params;
-    float wet_gain_l;
-    float wet_gain_r;
-    float input_gain;
-    float dry;
+    float sr;
+    struct SmoothedParam feedback;
+    struct SmoothedParam dampening;
+    struct SmoothedParam wet_gain_l;
+    struct SmoothedParam wet_gain_r;
+    struct SmoothedParam input_gain;
+    struct SmoothedParam dry;
     float wet;
 } Freeverb;
 
-typedef struct BiquadCoeffs_Butterworth {
-    float b0;
-    float b1;
-    float b2;
-    float a1;
-    float a2;
-} BiquadCoeffs_Butterworth;
+typedef struct Grain {
+    bool active;
+    float age;
+    float length;
+    float delay;
+    /*
+     Change of the delay per sample, negative while reading faster than the input
+     */
+    float drift;
+    float gain_l;
+    float gain_r;
+} Grain;
 
 /*
- Little suite of filters in a `Biquad` topology.
+ Xorshift white noise, cheap and deterministic for a given seed
  */
-typedef struct Biquad_Butterworth {
-    float z1;
-    float z2;
-    struct BiquadCoeffs_Butterworth coeffs;
-} Biquad_Butterworth;
+typedef struct WhiteNoise {
+    uint32_t state;
+} WhiteNoise;
+
+/*
+ Granular delay, records into a ring buffer and plays back overlapping windowed grains
+ */
+typedef struct Granular_16 {
+    struct DelayLine delay_line;
+    struct Grain grains[16];
+    struct WhiteNoise noise;
+    float sr;
+    float smoothing_ms;
+    float spawn_countdown;
+    float grain_size;
+    float density;
+    float position;
+    float jitter;
+    float pitch_ratio;
+    float spread;
+    struct SmoothedParam feedback;
+    struct SmoothedParam mix;
+} Granular_16;
+
+/*
+ `Granular` with 16 grains, as used by the C API
+ */
+typedef struct Granular_16 Granular16;
+
+/*
+ Zero-delay-feedback one-pole in topology-preserving transform form
+ */
+typedef struct OnePole {
+    float state;
+    /*
+     `g / (1 + g)`
+     */
+    float alpha;
+    /*
+     Scales the state for the global feedback path
+     */
+    float beta;
+} OnePole;
+
+/*
+ Moog-style 4-pole lowpass ladder with zero-delay feedback
+ */
+typedef struct LadderFilter {
+    struct OnePole stages[4];
+    float sr;
+    struct SmoothedParam fc;
+    /*
+     `1 / (1 + k * G^4)`, resolves the delay-free loop
+     */
+    float alpha_0;
+    struct SmoothedParam feedback;
+    struct SmoothedParam drive;
+    float smoothing_ms;
+} LadderFilter;
+
+/*
+ Running maximum over a sliding window, rounded up to whole blocks
+ */
+typedef struct PeakHold {
+    float blocks[16];
+    float current;
+    size_t index;
+    size_t filled;
+    size_t block_len;
+} PeakHold;
+
+/*
+ Stereo peak limiter with lookahead
+ */
+typedef struct Limiter {
+    struct DelayLine delay_l;
+    struct DelayLine delay_r;
+    struct Detector detector;
+    struct PeakHold hold[2];
+    float sr;
+    float lookahead;
+    float lookahead_samples;
+    float threshold;
+    float ceiling;
+    float knee;
+} Limiter;
+
+/*
+ Looper with overdub, one level of undo, reverse and half speed
+ */
+typedef struct Looper {
+    float *current;
+    float *backup;
+    size_t capacity;
+    float sr;
+    float smoothing_ms;
+    enum LooperState state;
+    size_t length;
+    float position;
+    float speed;
+    bool reverse;
+    bool half_speed;
+    size_t fade_length;
+    size_t fade_left;
+    bool has_undo;
+    size_t sync_cursor;
+    size_t sync_left;
+    bool sync_backwards;
+    struct SmoothedParam dub_gain;
+    struct SmoothedParam level;
+} Looper;
 
 typedef struct MultiFilter {
     struct Biquad_Butterworth biquad;
+    /*
+     Coefficients of the settled parameters, used by the response functions
+     */
+    struct BiquadCoeffs_Butterworth target_coeffs;
     enum ButterworthType filter;
     float sr;
     float fc;
     float q;
+    /*
+     Linear amplitude gain of the bell and shelf filters
+     */
     float gain;
+    /*
+     Shelf slope that `q` is derived from, `0.0` when `q` was set directly
+     */
+    float shelf_slope;
+    struct SmoothedParam smooth_fc;
+    struct SmoothedParam smooth_q;
+    struct SmoothedParam smooth_gain;
+    size_t coeff_countdown;
 } MultiFilter;
 
+/*
+ Stereo noise gate with hysteresis and hold time
+ */
+typedef struct NoiseGate {
+    struct Detector detector;
+    float sr;
+    bool open[2];
+    size_t hold_left[2];
+    float threshold;
+    float hysteresis;
+    float hold;
+    size_t hold_samples;
+    float range;
+} NoiseGate;
+
+typedef struct EqBand {
+    struct MultiFilter filter;
+    bool bypass;
+} EqBand;
+
+/*
+ Chain of `MultiFilter` bands, processed in ascending order
+ */
+typedef struct ParametricEq_4 {
+    struct EqBand bands[4];
+    float sr;
+    struct SmoothedParam output_gain;
+} ParametricEq_4;
+
+/*
+ Parametric EQ with four bands, as exposed through the C API
+ */
+typedef struct ParametricEq_4 ParametricEq4;
+
+/*
+ Stereo phaser from a chain of swept allpass biquads
+ */
+typedef struct Phaser {
+    struct Biquad_Butterworth stages_l[12];
+    struct Biquad_Butterworth stages_r[12];
+    struct Lfo lfo;
+    float sr;
+    float smoothing_ms;
+    size_t block_countdown;
+    float feedback_l;
+    float feedback_r;
+    size_t stages;
+    float stereo_phase;
+    struct SmoothedParam center;
+    struct SmoothedParam depth;
+    struct SmoothedParam feedback;
+    struct SmoothedParam mix;
+} Phaser;
+
+/*
+ Two read heads half a window apart, sweeping through the delay line at the pitch ratio
+ */
+typedef struct ShiftVoice {
+    float phase;
+    float ratio;
+    struct SmoothedParam gain;
+} ShiftVoice;
+
+/*
+ Delay line based pitch shifter with up to four harmonizer voices
+ */
+typedef struct PitchShifter {
+    struct DelayLine delay_line;
+    struct ShiftVoice voices[4];
+    float sr;
+    float smoothing_ms;
+    size_t active_voices;
+    float window_ms;
+    float window;
+    struct SmoothedParam mix;
+} PitchShifter;
+
+/*
+ Multiplies the input with an internal carrier or with a sidechain signal
+ */
+typedef struct RingModulator {
+    struct Lfo carrier;
+    float sr;
+    float smoothing_ms;
+    struct SmoothedParam mix;
+} RingModulator;
+
+/*
+ MS-20-style 2-pole Sallen-Key lowpass (Korg35) with zero-delay feedback
+ */
+typedef struct SallenKeyFilter {
+    struct OnePole lpf_1;
+    struct OnePole lpf_2;
+    struct OnePole hpf_3;
+    float sr;
+    struct SmoothedParam fc;
+    /*
+     `1 / (1 - k * G + k * G^2)`, resolves the delay-free loop
+     */
+    float alpha_0;
+    struct SmoothedParam feedback;
+    struct SmoothedParam drive;
+    float smoothing_ms;
+} SallenKeyFilter;
+
 typedef struct SimpleDelay {
     struct DelayLine delay_line;
     float delay_samples;
-    float feedback;
-    float dry_gain;
-    float wet_gain;
+    struct SmoothedParam feedback;
+    struct SmoothedParam dry_gain;
+    struct SmoothedParam wet_gain;
+    float feedback_drive;
+    enum Waveshaper feedback_shape;
     bool delay_time_changed;
     float last_delay_samples;
     size_t crossfade_counter;
     size_t crossfade_samples;
+    float sr;
+    float smoothing_ms;
 } SimpleDelay;
 
+/*
+ Overlap-add short-time Fourier transform in a caller-provided buffer
+ */
+typedef struct Stft {
+    float *input;
+    float *output;
+    float *frame;
+    float *window_table;
+    float *twiddles;
+    float *norm;
+    size_t size;
+    enum Window window;
+    size_t position;
+    size_t hop_countdown;
+} Stft;
+
+/*
+ Noise reduction that attenuates every frequency bin below the threshold on its own
+ */
+typedef struct SpectralGate {
+    struct Stft stft;
+    float threshold;
+    float floor;
+    float amplitude_scale;
+} SpectralGate;
+
+/*
+ Magnitude spectrum in dB for displays, with a falloff like a peak meter
+ */
+typedef struct SpectrumAnalyzer {
+    float *input;
+    float *frame;
+    float *window_table;
+    float *twiddles;
+    float *bins;
+    size_t size;
+    float sr;
+    enum Window window;
+    float amplitude_scale;
+    size_t position;
+    size_t hop_countdown;
+    float falloff;
+} SpectrumAnalyzer;
+
 typedef struct AudioRateADSR {
     float attack;
     float decay;
@@ -140,6 +725,41 @@ typedef struct AudioRateADSR {
     float sr;
 } AudioRateADSR;
 
+typedef struct ScheduledTrigger {
+    size_t offset;
+    float velocity;
+    bool accent;
+} ScheduledTrigger;
+
+/*
+ Pending triggers with sample offsets, so they don't get quantized to the processing block
+ */
+typedef struct TriggerSchedule {
+    struct ScheduledTrigger triggers[4];
+    size_t len;
+} TriggerSchedule;
+
+/*
+ Bandpassed noise with a few quick bursts and a reverberant tail
+ */
+typedef struct SynthClap {
+    struct AudioRateADSR tail_env;
+    struct WhiteNoise noise;
+    struct TriggerSchedule schedule;
+    float sr;
+    size_t bursts_left;
+    size_t burst_timer;
+    float burst_amp;
+    float lp_state;
+    float hp_state;
+    float burst_coeff;
+    float lp_coeff;
+    float hp_coeff;
+    size_t burst_spacing;
+    float spread;
+    float tone;
+} SynthClap;
+
 typedef struct SoftPhaseAccumulator {
     uint32_t counter;
     float freq;
@@ -152,112 +772,1479 @@ typedef struct FunctionalOscillator_SoftPhaseAccumulator {
     enum Waveform wave;
 } FunctionalOscillator_SoftPhaseAccumulator;
 
-typedef struct SynthKick {
-    struct AudioRateADSR pitch_env;
+/*
+ Metallic hi-hat with open and closed hits, where a closed hit chokes an open one
+ */
+typedef struct SynthHat {
     struct AudioRateADSR volume_env;
-    struct FunctionalOscillator_SoftPhaseAccumulator osc;
-    float current_sample;
-    enum KickState state;
-    float global_pitch;
-    float retrigger_slope;
-    float retrigger_fade_out_amp;
-    float overdrive;
-    float od_param;
-} SynthKick;
-
-float f32_millis_to_samples(float val, float sr);
-
-float f32_samples_to_millis(float val, float sr);
-
-float f32_samples_to_seconds(float val, float sr);
-
-float f32_seconds_to_samples(float val, float sr);
+    struct FunctionalOscillator_SoftPhaseAccumulator oscs[6];
+    struct TriggerSchedule schedule;
+    float sr;
+    float hp_state;
+    float hp_coeff;
+    float closed_decay;
+    float open_decay;
+    float tone;
+} SynthHat;
 
 /*
- Initializes `Freeverb` struct. `buffer` needs to be `length >= 25450` for `sr = 48000`. Otherwise will panic!
+ Phase accumulator that crossfades from a sine towards one of the `Waveform` shapes
  */
-struct Freeverb freeverb_init(float sr,
-                              float *buffer,
-                              size_t length);
+typedef struct MorphOscillator {
+    float phase;
+    float increment;
+    float sr;
+    enum Waveform wave;
+    float morph;
+} MorphOscillator;
 
 /*
- Sample rate depending calculations should be performed earlier!
+ Short pitched click and lowpassed noise burst on a shared exponential envelope
  */
-void freeverb_set_all_params(struct Freeverb *ptr, struct FreeverbParams *params);
+typedef struct Transient {
+    struct WhiteNoise noise;
+    float sr;
+    float amp;
+    float click_phase;
+    float noise_state;
+    float decay_coeff;
+    float click_increment;
+    float noise_coeff;
+    float click_level;
+    float noise_level;
+    float decay;
+    float tone;
+} Transient;
 
 /*
- Returns next stereo samples. Raw pointer `stereo_samples` assumes to have exactly two elements!
+ Levels of a single hit, derived from its velocity and accent
  */
-void freeverb_tick(struct Freeverb *ptr, float *stereo_samples);
+typedef struct Hit {
+    float amp;
+    float pitch_depth;
+    float click;
+} Hit;
 
 /*
- Initializes `MultiFilter` struct.
+ Everything that rings for a single hit of the kick
  */
-struct MultiFilter multifilter_init(float sr);
+typedef struct KickVoice {
+    struct AudioRateADSR pitch_env;
+    struct AudioRateADSR volume_env;
+    struct AudioRateADSR fm_env;
+    struct MorphOscillator osc;
+    struct MorphOscillator modulator;
+    struct Transient transient;
+    struct Hit hit;
+    float choke_amp;
+    float choke_slope;
+    float retrigger_slope;
+    float fade_slope;
+    bool choked;
+    size_t hold_left;
+    bool release_pending;
+    float hold_time;
+    size_t hold_samples;
+} KickVoice;
 
 /*
- Sample rate depending calculations should be performed earlier!
+ Parameters of the kick body, shared by all voices
  */
-void multifilter_set_all_params(struct MultiFilter *ptr,
-                                enum ButterworthType filter,
-                                float freq,
-                                float q,
-                                float gain);
+typedef struct BodyParams {
+    float pitch;
+    float sweep_depth;
+    enum SweepUnit sweep_unit;
+    float sweep_curve;
+    float fm_ratio;
+    float fm_index;
+} BodyParams;
 
 /*
- Returns next stereo samples. Raw pointer `stereo_samples` assumes to have exactly two elements!
+ Maps the velocity of a hit onto the scaling of a parameter
  */
-float multifilter_tick(struct MultiFilter *ptr, float sample);
+typedef struct VelocityCurve {
+    /*
+     0.0 ignores the velocity, 1.0 scales the parameter all the way down to zero
+     */
+    float sensitivity;
+    /*
+     Exponent applied to the velocity, 1.0 is linear
+     */
+    float curve;
+} VelocityCurve;
+
+typedef struct SynthKick {
+    struct KickVoice voices[3];
+    struct BodyParams body;
+    struct TriggerSchedule schedule;
+    float sr;
+    size_t current_voice;
+    float target_pitch;
+    float glide_from;
+    float glide_left;
+    float glide_coeff;
+    struct Oversampler oversampler;
+    float od_tone_coeff;
+    float od_tone_state;
+    float od_compensation;
+    float glide_time;
+    bool gate_mode;
+    enum RetriggerPolicy retrigger_policy;
+    float overdrive;
+    enum Waveshaper od_shape;
+    float od_tone;
+    struct VelocityCurve amp_velocity;
+    struct VelocityCurve pitch_velocity;
+    struct VelocityCurve click_velocity;
+    float accent_amount;
+} SynthKick;
 
 /*
- Initializes `SimpleDelay` struct
+ Tuned drum body with a snappy noise layer
  */
-struct SimpleDelay simple_delay_init(void);
+typedef struct SynthSnare {
+    struct AudioRateADSR pitch_env;
+    struct AudioRateADSR body_env;
+    struct AudioRateADSR noise_env;
+    struct FunctionalOscillator_SoftPhaseAccumulator osc;
+    struct WhiteNoise noise;
+    struct TriggerSchedule schedule;
+    float sr;
+    float noise_lp_state;
+    float noise_hp_state;
+    float noise_lp_coeff;
+    float noise_hp_coeff;
+    float pitch;
+    float tone;
+    float snappy;
+} SynthSnare;
 
 /*
- Initializes `SimpleDelay` delay buffer
+ Sine tom with a pitch drop
  */
-void simple_delay_set_buffer(struct SimpleDelay *ptr, float *buffer, size_t length);
+typedef struct SynthTom {
+    struct AudioRateADSR pitch_env;
+    struct AudioRateADSR volume_env;
+    struct FunctionalOscillator_SoftPhaseAccumulator osc;
+    struct TriggerSchedule schedule;
+    float pitch;
+    float sweep;
+} SynthTom;
 
 /*
- Feedback can reach max. 100%
+ Stereo amplitude modulation
  */
-void simple_delay_set_feedback(struct SimpleDelay *ptr, float feedback);
+typedef struct Tremolo {
+    struct Lfo lfo;
+    float sr;
+    float smoothing_ms;
+    float stereo_phase;
+    struct SmoothedParam depth;
+} Tremolo;
 
 /*
- Sample rate depending calculations should be performed earlier!
+ Initializes `AutoPan` struct
  */
-void simple_delay_set_length(struct SimpleDelay *ptr, float len_in_samples);
+struct AutoPan auto_pan_init(float sr);
 
 /*
- Dry/Wet mixing
+ Restarts the modulation
  */
-void simple_delay_set_mix(struct SimpleDelay *ptr, float mix);
+void auto_pan_reset_phase(struct AutoPan *ptr);
 
 /*
- Returns next sample
+ Only accepts values between 0.0 and 1.0, otherwise clamps
  */
-float simple_delay_tick(struct SimpleDelay *ptr, float sample);
+void auto_pan_set_depth(struct AutoPan *ptr, float val);
 
 /*
- Initializes `SynthKick` struct
+ Modulation speed between 0.01 and 20 Hz, otherwise clamps
  */
-struct SynthKick synth_kick_init(float sr);
+void auto_pan_set_rate(struct AutoPan *ptr, float hz);
 
 /*
- Only accepts values between 0.0 and 1.0, otherwise clamps
+ Time in ms that depth changes take
  */
-void synth_kick_set_attack(struct SynthKick *ptr, float val);
+void auto_pan_set_smoothing_time(struct AutoPan *ptr, float ms);
 
 /*
- Only accepts values between 0.0 and 1.0, otherwise clamps
+ One period every `beats` beats at `bpm`
  */
-void synth_kick_set_decay(struct SynthKick *ptr, float val);
+void auto_pan_set_tempo_sync(struct AutoPan *ptr, float bpm, float beats);
 
 /*
- Only accepts values between 0.0 and 1.0, otherwise clamps
+ Shape of the modulation
  */
-void synth_kick_set_pitch(struct SynthKick *ptr, float val);
+void auto_pan_set_waveform(struct AutoPan *ptr, enum Waveform wave);
+
+/*
+ Returns next stereo samples. Raw pointer `stereo_samples` assumes to have exactly two elements!
+ */
+void auto_pan_tick(struct AutoPan *ptr, float *stereo_samples);
+
+/*
+ Initializes `Chorus` struct
+ */
+struct Chorus chorus_init(float sr);
+
+/*
+ Initializes `Chorus` delay buffers. `length` needs to be `>= 1920` for `sr = 48000`.
+ */
+void chorus_set_buffer(struct Chorus *ptr, float *buffer, size_t length);
+
+/*
+ Only accepts values between 0.0 and 1.0, otherwise clamps
+ */
+void chorus_set_depth(struct Chorus *ptr, float val);
+
+/*
+ Dry/Wet mixing
+ */
+void chorus_set_mix(struct Chorus *ptr, float val);
+
+/*
+ Modulation speed between 0.01 and 10 Hz, otherwise clamps
+ */
+void chorus_set_rate(struct Chorus *ptr, float hz);
+
+/*
+ Time in ms that depth and mix changes take
+ */
+void chorus_set_smoothing_time(struct Chorus *ptr, float ms);
+
+/*
+ Only accepts values between 0.0 and 1.0, otherwise clamps
+ */
+void chorus_set_spread(struct Chorus *ptr, float val);
+
+/*
+ Number of voices between 1 and 4, otherwise clamps
+ */
+void chorus_set_voices(struct Chorus *ptr, size_t voices);
+
+/*
+ Returns next stereo samples. Raw pointer `stereo_samples` assumes to have exactly two elements!
+ */
+void chorus_tick(struct Chorus *ptr, float *stereo_samples);
+
+/*
+ Current gain reduction in dB as a positive value
+ */
+float compressor_gain_reduction_db(const struct Compressor *ptr);
+
+/*
+ Initializes `Compressor` struct
+ */
+struct Compressor compressor_init(float sr);
+
+/*
+ Attack time in ms between 0 and 500, otherwise clamps
+ */
+void compressor_set_attack(struct Compressor *ptr, float ms);
+
+void compressor_set_detector_mode(struct Compressor *ptr, enum DetectorMode mode);
+
+/*
+ Knee width in dB between 0 and 24, otherwise clamps
+ */
+void compressor_set_knee(struct Compressor *ptr, float db);
+
+/*
+ Makeup gain in dB
+ */
+void compressor_set_makeup(struct Compressor *ptr, float db);
+
+/*
+ Ratio between 1 and 100, otherwise clamps
+ */
+void compressor_set_ratio(struct Compressor *ptr, float ratio);
+
+/*
+ Release time in ms between 1 and 5000, otherwise clamps
+ */
+void compressor_set_release(struct Compressor *ptr, float ms);
+
+void compressor_set_stereo_link(struct Compressor *ptr, bool link);
+
+/*
+ Threshold in dB
+ */
+void compressor_set_threshold(struct Compressor *ptr, float db);
+
+/*
+ Returns next stereo samples. Raw pointer `stereo_samples` assumes to have exactly two elements!
+ */
+void compressor_tick(struct Compressor *ptr, float *stereo_samples);
+
+/*
+ Returns next stereo samples compressed by the sidechain level. Both raw pointers assume to have exactly two elements!
+ */
+void compressor_tick_with_sidechain(struct Compressor *ptr,
+                                    float *stereo_samples,
+                                    const float *sidechain_samples);
+
+/*
+ Initializes `Distortion` struct
+ */
+struct Distortion distortion_init(float sr);
+
+/*
+ Delay of the output in samples caused by the oversampling
+ */
+float distortion_latency_samples(const struct Distortion *ptr);
+
+/*
+ Resolution between 1 and 24 bits, otherwise clamps. 24 bits bypasses the quantization.
+ */
+void distortion_set_bits(struct Distortion *ptr, float bits);
+
+/*
+ Sample rate reduction factor between 1 and 64, otherwise clamps
+ */
+void distortion_set_downsample(struct Distortion *ptr, float factor);
+
+/*
+ Drive in dB between 0 and 48, otherwise clamps
+ */
+void distortion_set_drive(struct Distortion *ptr, float db);
+
+/*
+ Center of the emphasized band between 20 Hz and 20 kHz, otherwise clamps
+ */
+void distortion_set_emphasis_freq(struct Distortion *ptr, float hz);
+
+/*
+ Emphasis in dB between -24 and 24, otherwise clamps
+ */
+void distortion_set_emphasis_gain(struct Distortion *ptr, float db);
+
+/*
+ Dry/Wet mixing
+ */
+void distortion_set_mix(struct Distortion *ptr, float val);
+
+/*
+ Output level in dB
+ */
+void distortion_set_output(struct Distortion *ptr, float db);
+
+/*
+ Oversampling of the waveshaper, 1, 2, 4 or 8. Other values round down.
+ */
+void distortion_set_oversampling(struct Distortion *ptr, size_t factor);
+
+/*
+ Curve of the waveshaper
+ */
+void distortion_set_shape(struct Distortion *ptr, enum Waveshaper shape);
+
+/*
+ Time in ms that drive, output and mix changes take
+ */
+void distortion_set_smoothing_time(struct Distortion *ptr, float ms);
+
+/*
+ Returns next sample
+ */
+float distortion_tick(struct Distortion *ptr, float sample);
+
+/*
+ Triggers `voices[index]` and chokes all other voices of the group, like an open and a closed hi-hat
+ */
+void drum_voice_trigger_in_choke_group(struct DrumVoicePtr *voices, size_t length, size_t index);
+
+/*
+ Current gain reduction in dB as a positive value
+ */
+float expander_gain_reduction_db(const struct Expander *ptr);
+
+/*
+ Initializes `Expander` struct
+ */
+struct Expander expander_init(float sr);
+
+/*
+ Attack time in ms between 0 and 500, otherwise clamps
+ */
+void expander_set_attack(struct Expander *ptr, float ms);
+
+void expander_set_detector_mode(struct Expander *ptr, enum DetectorMode mode);
+
+/*
+ Knee width in dB between 0 and 24, otherwise clamps
+ */
+void expander_set_knee(struct Expander *ptr, float db);
+
+/*
+ Maximum attenuation in dB between 0 and 120, otherwise clamps
+ */
+void expander_set_range(struct Expander *ptr, float db);
+
+/*
+ Ratio between 1 and 100, otherwise clamps
+ */
+void expander_set_ratio(struct Expander *ptr, float ratio);
+
+/*
+ Release time in ms between 1 and 5000, otherwise clamps
+ */
+void expander_set_release(struct Expander *ptr, float ms);
+
+void expander_set_stereo_link(struct Expander *ptr, bool link);
+
+/*
+ Threshold in dB
+ */
+void expander_set_threshold(struct Expander *ptr, float db);
+
+/*
+ Returns next stereo samples. Raw pointer `stereo_samples` assumes to have exactly two elements!
+ */
+void expander_tick(struct Expander *ptr, float *stereo_samples);
+
+/*
+ Returns next stereo samples expanded by the sidechain level. Both raw pointers assume to have exactly two elements!
+ */
+void expander_tick_with_sidechain(struct Expander *ptr,
+                                  float *stereo_samples,
+                                  const float *sidechain_samples);
+
+float f32_millis_to_samples(float val, float sr);
+
+float f32_samples_to_millis(float val, float sr);
+
+float f32_samples_to_seconds(float val, float sr);
+
+float f32_seconds_to_samples(float val, float sr);
+
+/*
+ Initializes `Flanger` struct
+ */
+struct Flanger flanger_init(float sr);
+
+/*
+ Initializes `Flanger` delay buffer. `length` needs to be `>= 720` for `sr = 48000`.
+ */
+void flanger_set_buffer(struct Flanger *ptr, float *buffer, size_t length);
+
+/*
+ Only accepts values between 0.0 and 1.0, otherwise clamps
+ */
+void flanger_set_depth(struct Flanger *ptr, float val);
+
+/*
+ Only accepts values between -1.0 and 1.0, otherwise clamps
+ */
+void flanger_set_feedback(struct Flanger *ptr, float val);
+
+/*
+ Only accepts values between 0.0 and 1.0, otherwise clamps
+ */
+void flanger_set_manual(struct Flanger *ptr, float val);
+
+/*
+ Dry/Wet mixing
+ */
+void flanger_set_mix(struct Flanger *ptr, float val);
+
+/*
+ Sweep speed between 0.01 and 10 Hz, otherwise clamps
+ */
+void flanger_set_rate(struct Flanger *ptr, float hz);
+
+/*
+ Time in ms that manual, depth, feedback and mix changes take
+ */
+void flanger_set_smoothing_time(struct Flanger *ptr, float ms);
+
+/*
+ Delays the dry signal as well, so the sweep passes through zero
+ */
+void flanger_set_through_zero(struct Flanger *ptr, bool through_zero);
+
+/*
+ Returns next sample
+ */
+float flanger_tick(struct Flanger *ptr, float sample);
+
+/*
+ Initializes `Freeverb` struct. `buffer` needs to be `length >= 25450` for `sr = 48000`. Otherwise will panic!
+ */
+struct Freeverb freeverb_init(float sr,
+                              float *buffer,
+                              size_t length);
+
+/*
+ Sample rate depending calculations should be performed earlier!
+ */
+void freeverb_set_all_params(struct Freeverb *ptr, struct FreeverbParams *params);
+
+/*
+ Time in ms that parameter changes take
+ */
+void freeverb_set_smoothing_time(struct Freeverb *ptr, float ms);
+
+/*
+ Returns next stereo samples. Raw pointer `stereo_samples` assumes to have exactly two elements!
+ */
+void freeverb_tick(struct Freeverb *ptr, float *stereo_samples);
+
+/*
+ Number of grains playing right now
+ */
+size_t granular_active_grains(const Granular16 *ptr);
+
+/*
+ Initializes `Granular16` struct
+ */
+Granular16 granular_init(float sr);
+
+/*
+ Initializes `Granular16` record buffer, which limits how far behind the input grains can start
+ */
+void granular_set_buffer(Granular16 *ptr, float *buffer, size_t length);
+
+/*
+ New grains per second between 0.5 and 200, otherwise clamps
+ */
+void granular_set_density(Granular16 *ptr, float grains_per_second);
+
+/*
+ Only accepts values between 0.0 and 1.0, otherwise clamps
+ */
+void granular_set_feedback(Granular16 *ptr, float val);
+
+/*
+ Grain length between 10 and 500 ms, otherwise clamps
+ */
+void granular_set_grain_size(Granular16 *ptr, float ms);
+
+/*
+ Only accepts values between 0.0 and 1.0, otherwise clamps
+ */
+void granular_set_jitter(Granular16 *ptr, float val);
+
+/*
+ Dry/Wet mixing
+ */
+void granular_set_mix(Granular16 *ptr, float val);
+
+/*
+ Transposition between -24 and 24 semitones, otherwise clamps
+ */
+void granular_set_pitch(Granular16 *ptr, float semitones);
+
+/*
+ Distance of new grains behind the input in ms
+ */
+void granular_set_position(Granular16 *ptr, float ms);
+
+/*
+ Restarts the random grain placement, the same seed gives the same output
+ */
+void granular_set_seed(Granular16 *ptr, uint32_t seed);
+
+/*
+ Time in ms that feedback and mix changes take
+ */
+void granular_set_smoothing_time(Granular16 *ptr, float ms);
+
+/*
+ Only accepts values between 0.0 and 1.0, otherwise clamps
+ */
+void granular_set_spread(Granular16 *ptr, float val);
+
+/*
+ Returns next stereo samples. Raw pointer `stereo_samples` assumes to have exactly two elements!
+ */
+void granular_tick(Granular16 *ptr, float *stereo_samples);
+
+/*
+ Initializes `LadderFilter` struct
+ */
+struct LadderFilter ladder_filter_init(float sr);
+
+/*
+ Cutoff frequency in Hz
+ */
+void ladder_filter_set_cutoff(struct LadderFilter *ptr, float freq);
+
+/*
+ Only accepts values between 0.0 and 1.0, otherwise clamps
+ */
+void ladder_filter_set_drive(struct LadderFilter *ptr, float val);
+
+/*
+ Only accepts values between 0.0 and 1.0, otherwise clamps
+ */
+void ladder_filter_set_resonance(struct LadderFilter *ptr, float val);
+
+/*
+ Time in ms that cutoff, resonance and drive take to reach a new value
+ */
+void ladder_filter_set_smoothing_time(struct LadderFilter *ptr, float ms);
+
+/*
+ Returns next sample
+ */
+float ladder_filter_tick(struct LadderFilter *ptr, float sample);
+
+/*
+ Current gain reduction in dB as a positive value
+ */
+float limiter_gain_reduction_db(const struct Limiter *ptr);
+
+/*
+ Initializes `Limiter` struct
+ */
+struct Limiter limiter_init(float sr);
+
+/*
+ Delay of the output in samples
+ */
+size_t limiter_latency_samples(const struct Limiter *ptr);
+
+/*
+ Initializes `Limiter` lookahead buffers. `length` needs to be `>= 964` for `sr = 48000`.
+ */
+void limiter_set_buffer(struct Limiter *ptr, float *buffer, size_t length);
+
+/*
+ Knee width in dB between 0 and 12, otherwise clamps
+ */
+void limiter_set_knee(struct Limiter *ptr, float db);
+
+/*
+ Lookahead in ms between 0 and 10, otherwise clamps
+ */
+void limiter_set_lookahead(struct Limiter *ptr, float ms);
+
+/*
+ Release time in ms between 1 and 5000, otherwise clamps
+ */
+void limiter_set_release(struct Limiter *ptr, float ms);
+
+void limiter_set_stereo_link(struct Limiter *ptr, bool link);
+
+/*
+ Threshold and output ceiling in dB
+ */
+void limiter_set_threshold(struct Limiter *ptr, float db);
+
+/*
+ Returns next stereo samples. Raw pointer `stereo_samples` assumes to have exactly two elements!
+ */
+void limiter_tick(struct Limiter *ptr, float *stereo_samples);
+
+/*
+ Returns next stereo samples limited by the sidechain level. Both raw pointers assume to have exactly two elements!
+ */
+void limiter_tick_with_sidechain(struct Limiter *ptr,
+                                 float *stereo_samples,
+                                 const float *sidechain_samples);
+
+enum LooperState looper_get_state(const struct Looper *ptr);
+
+/*
+ Advances the state machine, e.g. on a footswitch press
+ */
+void looper_handle_event(struct Looper *ptr, enum LooperEvent event);
+
+/*
+ Initializes `Looper` struct
+ */
+struct Looper looper_init(float sr);
+
+/*
+ Length of the recorded loop in samples, 0 while empty or recording
+ */
+size_t looper_loop_length(const struct Looper *ptr);
+
+/*
+ Initializes `Looper` buffers, the longest loop is `length / 2` samples. Clears the loop.
+ */
+void looper_set_buffer(struct Looper *ptr, float *buffer, size_t length);
+
+void looper_set_half_speed(struct Looper *ptr, bool half_speed);
+
+/*
+ Only accepts values between 0.0 and 1.0, otherwise clamps
+ */
+void looper_set_level(struct Looper *ptr, float val);
+
+void looper_set_reverse(struct Looper *ptr, bool reverse);
+
+/*
+ Time in ms of the overdub fades and level changes
+ */
+void looper_set_smoothing_time(struct Looper *ptr, float ms);
+
+/*
+ Returns the input with the loop on top
+ */
+float looper_tick(struct Looper *ptr, float sample);
+
+/*
+ Fills `out` with dB values on a log-spaced grid from `f_min` to `f_max`. `out` needs `length` elements!
+ */
+void multifilter_fill_response_db(const struct MultiFilter *ptr,
+                                  float f_min,
+                                  float f_max,
+                                  float *out,
+                                  size_t length);
+
+/*
+ Initializes `MultiFilter` struct.
+ */
+struct MultiFilter multifilter_init(float sr);
+
+/*
+ Frequency of point `index` on a log-spaced grid with `length` points, e.g. for axis labels
+ */
+float multifilter_log_spaced_freq(size_t index, size_t length, float f_min, float f_max);
+
+/*
+ Linear magnitude response at `freq` in Hz
+ */
+float multifilter_magnitude_at(const struct MultiFilter *ptr, float freq);
+
+/*
+ Phase response at `freq` in Hz, in radians
+ */
+float multifilter_phase_at(const struct MultiFilter *ptr, float freq);
+
+/*
+ Sample rate depending calculations should be performed earlier! `gain` is linear.
+ */
+void multifilter_set_all_params(struct MultiFilter *ptr,
+                                enum ButterworthType filter,
+                                float freq,
+                                float q,
+                                float gain);
+
+/*
+ Bandwidth in octaves, replaces Q
+ */
+void multifilter_set_bandwidth(struct MultiFilter *ptr, float octaves);
+
+/*
+ Gain in dB, only used by bell and shelf filters
+ */
+void multifilter_set_gain_db(struct MultiFilter *ptr, float db);
+
+/*
+ Shelf slope between 0.01 and 1.0, replaces Q. Otherwise clamps.
+ */
+void multifilter_set_shelf_slope(struct MultiFilter *ptr, float slope);
+
+/*
+ Time in ms that cutoff, Q and gain take to reach a new value
+ */
+void multifilter_set_smoothing_time(struct MultiFilter *ptr, float ms);
+
+/*
+ Returns next stereo samples. Raw pointer `stereo_samples` assumes to have exactly two elements!
+ */
+float multifilter_tick(struct MultiFilter *ptr, float sample);
+
+/*
+ Current gain reduction in dB as a positive value
+ */
+float noise_gate_gain_reduction_db(const struct NoiseGate *ptr);
+
+/*
+ Initializes `NoiseGate` struct
+ */
+struct NoiseGate noise_gate_init(float sr);
+
+bool noise_gate_is_open(const struct NoiseGate *ptr);
+
+/*
+ Opening time in ms between 0 and 500, otherwise clamps
+ */
+void noise_gate_set_attack(struct NoiseGate *ptr, float ms);
+
+void noise_gate_set_detector_mode(struct NoiseGate *ptr, enum DetectorMode mode);
+
+/*
+ Hold time in ms between 0 and 2000, otherwise clamps
+ */
+void noise_gate_set_hold(struct NoiseGate *ptr, float ms);
+
+/*
+ Hysteresis in dB between 0 and 24, otherwise clamps
+ */
+void noise_gate_set_hysteresis(struct NoiseGate *ptr, float db);
+
+/*
+ Attenuation of the closed gate in dB between 0 and 120, otherwise clamps
+ */
+void noise_gate_set_range(struct NoiseGate *ptr, float db);
+
+/*
+ Closing time in ms between 1 and 5000, otherwise clamps
+ */
+void noise_gate_set_release(struct NoiseGate *ptr, float ms);
+
+void noise_gate_set_stereo_link(struct NoiseGate *ptr, bool link);
+
+/*
+ Threshold in dB
+ */
+void noise_gate_set_threshold(struct NoiseGate *ptr, float db);
+
+/*
+ Returns next stereo samples. Raw pointer `stereo_samples` assumes to have exactly two elements!
+ */
+void noise_gate_tick(struct NoiseGate *ptr, float *stereo_samples);
+
+/*
+ Returns next stereo samples gated by the sidechain level. Both raw pointers assume to have exactly two elements!
+ */
+void noise_gate_tick_with_sidechain(struct NoiseGate *ptr,
+                                    float *stereo_samples,
+                                    const float *sidechain_samples);
+
+/*
+ Fills `out` with dB values on a log-spaced grid from `f_min` to `f_max`. `out` needs `length` elements!
+ */
+void parametric_eq_fill_response_db(const ParametricEq4 *ptr,
+                                    float f_min,
+                                    float f_max,
+                                    float *out,
+                                    size_t length);
+
+/*
+ Initializes `ParametricEq4` struct. All bands are bypassed.
+ */
+ParametricEq4 parametric_eq_init(float sr);
+
+/*
+ Linear magnitude response of all active bands at `freq` in Hz
+ */
+float parametric_eq_magnitude_at(const ParametricEq4 *ptr, float freq);
+
+/*
+ Phase response of all active bands at `freq` in Hz, in radians
+ */
+float parametric_eq_phase_at(const ParametricEq4 *ptr, float freq);
+
+/*
+ Configures and enables band `index`. Out of range indices are ignored.
+ */
+void parametric_eq_set_band(ParametricEq4 *ptr,
+                            size_t index,
+                            enum ButterworthType filter,
+                            float freq,
+                            float q,
+                            float gain);
+
+/*
+ Out of range indices are ignored
+ */
+void parametric_eq_set_band_bypass(ParametricEq4 *ptr, size_t index, bool bypass);
+
+/*
+ Linear gain after the last band
+ */
+void parametric_eq_set_output_gain(ParametricEq4 *ptr, float gain);
+
+/*
+ Time in ms that band settings and the output gain take to reach a new value
+ */
+void parametric_eq_set_smoothing_time(ParametricEq4 *ptr, float ms);
+
+/*
+ Returns next sample
+ */
+float parametric_eq_tick(ParametricEq4 *ptr, float sample);
+
+/*
+ Initializes `Phaser` struct
+ */
+struct Phaser phaser_init(float sr);
+
+/*
+ Center frequency of the sweep between 50 Hz and 5 kHz, otherwise clamps
+ */
+void phaser_set_center(struct Phaser *ptr, float freq);
+
+/*
+ Only accepts values between 0.0 and 1.0, otherwise clamps
+ */
+void phaser_set_depth(struct Phaser *ptr, float val);
+
+/*
+ Only accepts values between -1.0 and 1.0, otherwise clamps
+ */
+void phaser_set_feedback(struct Phaser *ptr, float val);
+
+/*
+ Dry/Wet mixing
+ */
+void phaser_set_mix(struct Phaser *ptr, float val);
+
+/*
+ Sweep speed between 0.01 and 10 Hz, otherwise clamps
+ */
+void phaser_set_rate(struct Phaser *ptr, float hz);
+
+/*
+ Time in ms that center, depth, feedback and mix changes take
+ */
+void phaser_set_smoothing_time(struct Phaser *ptr, float ms);
+
+/*
+ Number of allpass stages between 2 and 12, otherwise clamps
+ */
+void phaser_set_stages(struct Phaser *ptr, size_t stages);
+
+/*
+ Only accepts values between 0.0 and 1.0, otherwise clamps
+ */
+void phaser_set_stereo_phase(struct Phaser *ptr, float val);
+
+/*
+ Returns next stereo samples. Raw pointer `stereo_samples` assumes to have exactly two elements!
+ */
+void phaser_tick(struct Phaser *ptr, float *stereo_samples);
+
+/*
+ Initializes `PitchShifter` struct
+ */
+struct PitchShifter pitch_shifter_init(float sr);
+
+/*
+ Average delay of the shifted voices in samples
+ */
+float pitch_shifter_latency_samples(const struct PitchShifter *ptr);
+
+/*
+ Initializes `PitchShifter` delay buffer. `length` needs to be `>= 4803` for `sr = 48000`.
+ */
+void pitch_shifter_set_buffer(struct PitchShifter *ptr, float *buffer, size_t length);
+
+/*
+ Dry/Wet mixing
+ */
+void pitch_shifter_set_mix(struct PitchShifter *ptr, float val);
+
+/*
+ Shift of the first voice, between -24 and 24 semitones in total, otherwise clamps
+ */
+void pitch_shifter_set_shift(struct PitchShifter *ptr, float semitones, float cents);
+
+/*
+ Time in ms that voice gain and mix changes take
+ */
+void pitch_shifter_set_smoothing_time(struct PitchShifter *ptr, float ms);
+
+/*
+ Level of the voice at `index`. Only accepts values between 0.0 and 1.0, otherwise clamps
+ */
+void pitch_shifter_set_voice_gain(struct PitchShifter *ptr, size_t index, float val);
+
+/*
+ Shift of the voice at `index`, between -24 and 24 semitones in total, otherwise clamps
+ */
+void pitch_shifter_set_voice_shift(struct PitchShifter *ptr,
+                                   size_t index,
+                                   float semitones,
+                                   float cents);
+
+/*
+ Number of harmonizer voices between 1 and 4, otherwise clamps
+ */
+void pitch_shifter_set_voices(struct PitchShifter *ptr, size_t voices);
+
+/*
+ Length of the read head sweep between 10 and 100 ms, otherwise clamps
+ */
+void pitch_shifter_set_window(struct PitchShifter *ptr, float ms);
+
+/*
+ Returns next sample
+ */
+float pitch_shifter_tick(struct PitchShifter *ptr, float sample);
+
+/*
+ Initializes `RingModulator` struct
+ */
+struct RingModulator ring_modulator_init(float sr);
+
+/*
+ Restarts the carrier
+ */
+void ring_modulator_reset_phase(struct RingModulator *ptr);
+
+/*
+ Carrier frequency between 0.1 Hz and 5 kHz, otherwise clamps
+ */
+void ring_modulator_set_freq(struct RingModulator *ptr, float hz);
+
+/*
+ Dry/Wet mixing
+ */
+void ring_modulator_set_mix(struct RingModulator *ptr, float val);
+
+/*
+ Time in ms that mix changes take
+ */
+void ring_modulator_set_smoothing_time(struct RingModulator *ptr, float ms);
+
+/*
+ One carrier period every `beats` beats at `bpm`
+ */
+void ring_modulator_set_tempo_sync(struct RingModulator *ptr, float bpm, float beats);
+
+/*
+ Shape of the carrier
+ */
+void ring_modulator_set_waveform(struct RingModulator *ptr, enum Waveform wave);
+
+/*
+ Returns next sample
+ */
+float ring_modulator_tick(struct RingModulator *ptr, float sample);
+
+/*
+ Returns next sample modulated by `carrier` instead of the internal oscillator
+ */
+float ring_modulator_tick_with_sidechain(struct RingModulator *ptr, float sample, float carrier);
+
+/*
+ Initializes `SallenKeyFilter` struct
+ */
+struct SallenKeyFilter sallen_key_filter_init(float sr);
+
+/*
+ Cutoff frequency in Hz
+ */
+void sallen_key_filter_set_cutoff(struct SallenKeyFilter *ptr, float freq);
+
+/*
+ Only accepts values between 0.0 and 1.0, otherwise clamps
+ */
+void sallen_key_filter_set_drive(struct SallenKeyFilter *ptr, float val);
+
+/*
+ Only accepts values between 0.0 and 1.0, otherwise clamps
+ */
+void sallen_key_filter_set_resonance(struct SallenKeyFilter *ptr, float val);
+
+/*
+ Time in ms that cutoff, resonance and drive take to reach a new value
+ */
+void sallen_key_filter_set_smoothing_time(struct SallenKeyFilter *ptr, float ms);
+
+/*
+ Returns next sample
+ */
+float sallen_key_filter_tick(struct SallenKeyFilter *ptr, float sample);
+
+/*
+ Initializes `SimpleDelay` struct
+ */
+struct SimpleDelay simple_delay_init(void);
+
+/*
+ Initializes `SimpleDelay` delay buffer
+ */
+void simple_delay_set_buffer(struct SimpleDelay *ptr, float *buffer, size_t length);
+
+/*
+ Feedback can reach max. 100%
+ */
+void simple_delay_set_feedback(struct SimpleDelay *ptr, float feedback);
+
+/*
+ Linear drive of the saturation inside the feedback loop. Values below 1.0 are clamped, 1.0 bypasses it
+ */
+void simple_delay_set_feedback_drive(struct SimpleDelay *ptr,
+                                     float drive);
+
+/*
+ Curve of the saturation inside the feedback loop
+ */
+void simple_delay_set_feedback_shape(struct SimpleDelay *ptr, enum Waveshaper shape);
+
+/*
+ Sample rate depending calculations should be performed earlier!
+ */
+void simple_delay_set_length(struct SimpleDelay *ptr, float len_in_samples);
+
+/*
+ Dry/Wet mixing
+ */
+void simple_delay_set_mix(struct SimpleDelay *ptr, float mix);
+
+/*
+ Time in ms that feedback and mix changes take
+ */
+void simple_delay_set_smoothing_time(struct SimpleDelay *ptr, float ms);
+
+/*
+ Returns next sample
+ */
+float simple_delay_tick(struct SimpleDelay *ptr, float sample);
+
+/*
+ Sample rate the smoothing time refers to, 48 kHz by default
+ */
+void simple_delay_update_sr(struct SimpleDelay *ptr, float sr);
+
+/*
+ Initializes `SpectralGate` struct
+ */
+struct SpectralGate spectral_gate_init(void);
+
+/*
+ Delay of the output in samples, the FFT size
+ */
+size_t spectral_gate_latency_samples(const struct SpectralGate *ptr);
+
+/*
+ Initializes `SpectralGate` buffers. The FFT size is `length / 5.25` rounded down to a power of two.
+ */
+void spectral_gate_set_buffer(struct SpectralGate *ptr, float *buffer, size_t length);
+
+/*
+ Largest attenuation in dB between 0 and 80, otherwise clamps
+ */
+void spectral_gate_set_reduction(struct SpectralGate *ptr, float db);
+
+/*
+ Level in dB a sine needs in its bin to pass unchanged
+ */
+void spectral_gate_set_threshold(struct SpectralGate *ptr, float db);
+
+void spectral_gate_set_window(struct SpectralGate *ptr, enum Window window);
+
+/*
+ Returns next sample
+ */
+float spectral_gate_tick(struct SpectralGate *ptr, float sample);
+
+/*
+ Center frequency of bin `index` in Hz
+ */
+float spectrum_analyzer_bin_freq(const struct SpectrumAnalyzer *ptr, size_t index);
+
+/*
+ Copies up to `length` bin levels in dB into `bins`, returns the number copied
+ */
+size_t spectrum_analyzer_get_bins(const struct SpectrumAnalyzer *ptr, float *bins, size_t length);
+
+/*
+ Initializes `SpectrumAnalyzer` struct
+ */
+struct SpectrumAnalyzer spectrum_analyzer_init(float sr);
+
+/*
+ Records a sample
+ */
+void spectrum_analyzer_push(struct SpectrumAnalyzer *ptr, float sample);
+
+/*
+ Initializes `SpectrumAnalyzer` buffers. The FFT size is `length / 4.5` rounded down to a power of two.
+ */
+void spectrum_analyzer_set_buffer(struct SpectrumAnalyzer *ptr,
+                                  float *buffer,
+                                  size_t length);
+
+/*
+ Speed in dB per second at which the bins fall after a peak
+ */
+void spectrum_analyzer_set_falloff(struct SpectrumAnalyzer *ptr, float db_per_second);
+
+void spectrum_analyzer_set_window(struct SpectrumAnalyzer *ptr, enum Window window);
+
+/*
+ Quickly fades out a ringing hit
+ */
+void synth_clap_choke(struct SynthClap *ptr);
+
+/*
+ Initializes `SynthClap` struct
+ */
+struct SynthClap synth_clap_init(float sr);
+
+/*
+ Fills `out` with the next `length` samples
+ */
+void synth_clap_process(struct SynthClap *ptr, float *out, size_t length);
+
+/*
+ Only accepts values between 0.0 and 1.0, otherwise clamps
+ */
+void synth_clap_set_decay(struct SynthClap *ptr, float val);
+
+/*
+ Only accepts values between 0.0 and 1.0, otherwise clamps
+ */
+void synth_clap_set_spread(struct SynthClap *ptr, float val);
+
+/*
+ Only accepts values between 0.0 and 1.0, otherwise clamps
+ */
+void synth_clap_set_tone(struct SynthClap *ptr, float val);
+
+/*
+ Returns next sample
+ */
+float synth_clap_tick(struct SynthClap *ptr);
+
+/*
+ Triggers the clap
+ */
+void synth_clap_trigger(struct SynthClap *ptr);
+
+/*
+ Triggers the clap on the `offset`th call of `tick`, counting from zero
+ */
+void synth_clap_trigger_at(struct SynthClap *ptr, size_t offset);
+
+/*
+ Quickly fades out a ringing hit
+ */
+void synth_hat_choke(struct SynthHat *ptr);
+
+/*
+ Initializes `SynthHat` struct
+ */
+struct SynthHat synth_hat_init(float sr);
+
+/*
+ Fills `out` with the next `length` samples
+ */
+void synth_hat_process(struct SynthHat *ptr, float *out, size_t length);
+
+/*
+ Only accepts values between 0.0 and 1.0, otherwise clamps
+ */
+void synth_hat_set_closed_decay(struct SynthHat *ptr, float val);
+
+/*
+ Only accepts values between 0.0 and 1.0, otherwise clamps
+ */
+void synth_hat_set_open_decay(struct SynthHat *ptr, float val);
+
+/*
+ Only accepts values between 0.0 and 1.0, otherwise clamps
+ */
+void synth_hat_set_pitch(struct SynthHat *ptr, float val);
+
+/*
+ Only accepts values between 0.0 and 1.0, otherwise clamps
+ */
+void synth_hat_set_tone(struct SynthHat *ptr, float val);
+
+/*
+ Returns next sample
+ */
+float synth_hat_tick(struct SynthHat *ptr);
+
+/*
+ Triggers a closed hit, which chokes a ringing open hit
+ */
+void synth_hat_trigger(struct SynthHat *ptr);
+
+/*
+ Triggers the hat on the `offset`th call of `tick`, counting from zero
+ */
+void synth_hat_trigger_at(struct SynthHat *ptr, size_t offset);
+
+/*
+ Triggers an open hit
+ */
+void synth_hat_trigger_open(struct SynthHat *ptr);
+
+/*
+ Quickly fades out all ringing hits
+ */
+void synth_kick_choke(struct SynthKick *ptr);
+
+/*
+ Initializes `SynthKick` struct
+ */
+struct SynthKick synth_kick_init(float sr);
+
+/*
+ Delay of the overdrive stage in samples, 0.0 while it is bypassed
+ */
+float synth_kick_overdrive_latency_samples(const struct SynthKick *ptr);
+
+/*
+ Fills `out` with the next `length` samples
+ */
+void synth_kick_process(struct SynthKick *ptr, float *out, size_t length);
+
+/*
+ Note off, only has an effect in gate mode
+ */
+void synth_kick_release(struct SynthKick *ptr);
+
+/*
+ Only accepts values between 0.0 and 1.0, otherwise clamps
+ */
+void synth_kick_set_accent_amount(struct SynthKick *ptr, float val);
+
+/*
+ Only accepts values between 0.0 and 1.0, otherwise clamps
+ */
+void synth_kick_set_attack(struct SynthKick *ptr, float val);
+
+/*
+ Only accepts values between 0.0 and 1.0, otherwise clamps
+ */
+void synth_kick_set_click_level(struct SynthKick *ptr, float val);
+
+/*
+ Only accepts values between 0.0 and 1.0, otherwise clamps
+ */
+void synth_kick_set_decay(struct SynthKick *ptr, float val);
+
+/*
+ Only accepts values between 0.0 and 1.0, otherwise clamps
+ */
+void synth_kick_set_fm_decay(struct SynthKick *ptr, float val);
+
+/*
+ Only accepts values between 0.0 and 1.0, otherwise clamps. 0.0 turns FM off
+ */
+void synth_kick_set_fm_index(struct SynthKick *ptr, float val);
+
+/*
+ Frequency of the FM modulator relative to the body, between 0.25 and 16.0
+ */
+void synth_kick_set_fm_ratio(struct SynthKick *ptr, float ratio);
+
+/*
+ Holds the kick until `synth_kick_release` when enabled
+ */
+void synth_kick_set_gate_mode(struct SynthKick *ptr, bool gate_mode);
+
+/*
+ Portamento between notes, up to 1 s. Only accepts values between 0.0 and 1.0, otherwise clamps
+ */
+void synth_kick_set_glide(struct SynthKick *ptr, float val);
+
+/*
+ Time at full volume before decaying, up to 2 s. Only accepts values between 0.0 and 1.0, otherwise clamps
+ */
+void synth_kick_set_hold(struct SynthKick *ptr,
+                         float val);
+
+/*
+ Only accepts values between 0.0 and 1.0, otherwise clamps
+ */
+void synth_kick_set_noise_level(struct SynthKick *ptr, float val);
+
+/*
+ Tunes the kick to a MIDI note, detuned by `cents`
+ */
+void synth_kick_set_note(struct SynthKick *ptr, uint8_t midi_note, float cents);
+
+/*
+ Linear drive gain. Values below 1.0 are clamped, 1.0 bypasses the overdrive stage
+ */
+void synth_kick_set_overdrive(struct SynthKick *ptr, float val);
+
+/*
+ Oversampling of the overdrive stage, 1, 2, 4 or 8. Other values round down.
+ */
+void synth_kick_set_overdrive_oversampling(struct SynthKick *ptr, size_t factor);
+
+/*
+ Deprecated, use `synth_kick_set_overdrive_tone` instead
+ */
+void synth_kick_set_overdrive_param(struct SynthKick *ptr, float val);
+
+/*
+ Curve of the overdrive stage
+ */
+void synth_kick_set_overdrive_shape(struct SynthKick *ptr, enum Waveshaper shape);
+
+/*
+ Only accepts values between 0.0 and 1.0, otherwise clamps
+ */
+void synth_kick_set_overdrive_tone(struct SynthKick *ptr, float val);
+
+/*
+ Only accepts values between 0.0 and 1.0, otherwise clamps
+ */
+void synth_kick_set_pitch(struct SynthKick *ptr, float val);
+
+/*
+ Fade out after `synth_kick_release` in gate mode. Only accepts values between 0.0 and 1.0, otherwise clamps
+ */
+void synth_kick_set_release(struct SynthKick *ptr,
+                            float val);
+
+/*
+ Behavior when the kick gets triggered while still ringing
+ */
+void synth_kick_set_retrigger_policy(struct SynthKick *ptr, enum RetriggerPolicy policy);
+
+/*
+ Only accepts values between 0.0 and 1.0, otherwise clamps. 0.5 is linear
+ */
+void synth_kick_set_sweep_curve(struct SynthKick *ptr, float val);
+
+/*
+ Depth of the pitch sweep on top of the pitch in Hz
+ */
+void synth_kick_set_sweep_depth_hz(struct SynthKick *ptr, float hz);
+
+/*
+ Depth of the pitch sweep above the pitch in semitones
+ */
+void synth_kick_set_sweep_depth_semitones(struct SynthKick *ptr, float semitones);
+
+/*
+ Time of the pitch sweep in seconds, between 5 ms and 2 s
+ */
+void synth_kick_set_sweep_time(struct SynthKick *ptr, float seconds);
+
+/*
+ Only accepts values between 0.0 and 1.0, otherwise clamps
+ */
+void synth_kick_set_transient_decay(struct SynthKick *ptr, float val);
+
+/*
+ Only accepts values between 0.0 and 1.0, otherwise clamps
+ */
+void synth_kick_set_transient_tone(struct SynthKick *ptr, float val);
+
+/*
+ Velocity to amplitude curve. Sensitivity between 0.0 and 1.0, curve exponent between 0.1 and 10.0
+ */
+void synth_kick_set_velocity_amp_curve(struct SynthKick *ptr, float sensitivity, float curve);
+
+/*
+ Velocity to transient level curve. Sensitivity between 0.0 and 1.0, curve exponent between 0.1 and 10.0
+ */
+void synth_kick_set_velocity_click_curve(struct SynthKick *ptr,
+                                         float sensitivity,
+                                         float curve);
+
+/*
+ Velocity to pitch sweep depth curve. Sensitivity between 0.0 and 1.0, curve exponent between 0.1 and 10.0
+ */
+void synth_kick_set_velocity_pitch_curve(struct SynthKick *ptr,
+                                         float sensitivity,
+                                         float curve);
+
+/*
+ Shape of the kick body
+ */
+void synth_kick_set_waveform(struct SynthKick *ptr, enum Waveform wave);
+
+/*
+ Blend from a sine (0.0) to the waveform (1.0). Only accepts values between 0.0 and 1.0, otherwise clamps
+ */
+void synth_kick_set_waveform_morph(struct SynthKick *ptr,
+                                   float val);
 
 /*
  Returns next sample
@@ -269,4 +2256,191 @@ float synth_kick_tick(struct SynthKick *ptr);
  */
 void synth_kick_trigger(struct SynthKick *ptr);
 
-#endif  /* _YANEL_DSP_H_ */
+/*
+ Triggers the kick on the `offset`th call of `tick`, counting from zero
+ */
+void synth_kick_trigger_at(struct SynthKick *ptr, size_t offset);
+
+/*
+ Like `synth_kick_trigger_with_accent`, but on the `offset`th call of `tick`
+ */
+void synth_kick_trigger_at_with_accent(struct SynthKick *ptr,
+                                       size_t offset,
+                                       float velocity,
+                                       bool accent);
+
+/*
+ Triggers the kick. Accented hits are louder and sweep deeper.
+ */
+void synth_kick_trigger_with_accent(struct SynthKick *ptr, float velocity, bool accent);
+
+/*
+ Triggers the kick. Only accepts velocities between 0.0 and 1.0, otherwise clamps
+ */
+void synth_kick_trigger_with_velocity(struct SynthKick *ptr, float velocity);
+
+/*
+ Quickly fades out a ringing hit
+ */
+void synth_snare_choke(struct SynthSnare *ptr);
+
+/*
+ Initializes `SynthSnare` struct
+ */
+struct SynthSnare synth_snare_init(float sr);
+
+/*
+ Fills `out` with the next `length` samples
+ */
+void synth_snare_process(struct SynthSnare *ptr, float *out, size_t length);
+
+/*
+ Only accepts values between 0.0 and 1.0, otherwise clamps
+ */
+void synth_snare_set_decay(struct SynthSnare *ptr, float val);
+
+/*
+ Only accepts values between 0.0 and 1.0, otherwise clamps
+ */
+void synth_snare_set_pitch(struct SynthSnare *ptr, float val);
+
+/*
+ Only accepts values between 0.0 and 1.0, otherwise clamps
+ */
+void synth_snare_set_snappy(struct SynthSnare *ptr, float val);
+
+/*
+ Only accepts values between 0.0 and 1.0, otherwise clamps
+ */
+void synth_snare_set_tone(struct SynthSnare *ptr, float val);
+
+/*
+ Returns next sample
+ */
+float synth_snare_tick(struct SynthSnare *ptr);
+
+/*
+ Triggers the snare
+ */
+void synth_snare_trigger(struct SynthSnare *ptr);
+
+/*
+ Triggers the snare on the `offset`th call of `tick`, counting from zero
+ */
+void synth_snare_trigger_at(struct SynthSnare *ptr, size_t offset);
+
+/*
+ Quickly fades out a ringing hit
+ */
+void synth_tom_choke(struct SynthTom *ptr);
+
+/*
+ Initializes `SynthTom` struct
+ */
+struct SynthTom synth_tom_init(float sr);
+
+/*
+ Fills `out` with the next `length` samples
+ */
+void synth_tom_process(struct SynthTom *ptr, float *out, size_t length);
+
+/*
+ Only accepts values between 0.0 and 1.0, otherwise clamps
+ */
+void synth_tom_set_decay(struct SynthTom *ptr, float val);
+
+/*
+ Only accepts values between 0.0 and 1.0, otherwise clamps
+ */
+void synth_tom_set_pitch(struct SynthTom *ptr, float val);
+
+/*
+ Only accepts values between 0.0 and 1.0, otherwise clamps
+ */
+void synth_tom_set_sweep(struct SynthTom *ptr, float val);
+
+/*
+ Returns next sample
+ */
+float synth_tom_tick(struct SynthTom *ptr);
+
+/*
+ Triggers the tom
+ */
+void synth_tom_trigger(struct SynthTom *ptr);
+
+/*
+ Triggers the tom on the `offset`th call of `tick`, counting from zero
+ */
+void synth_tom_trigger_at(struct SynthTom *ptr, size_t offset);
+
+/*
+ Initializes `Tremolo` struct
+ */
+struct Tremolo tremolo_init(float sr);
+
+/*
+ Restarts the modulation
+ */
+void tremolo_reset_phase(struct Tremolo *ptr);
+
+/*
+ Only accepts values between 0.0 and 1.0, otherwise clamps
+ */
+void tremolo_set_depth(struct Tremolo *ptr, float val);
+
+/*
+ Modulation speed between 0.01 and 20 Hz, otherwise clamps
+ */
+void tremolo_set_rate(struct Tremolo *ptr, float hz);
+
+/*
+ Time in ms that depth changes take
+ */
+void tremolo_set_smoothing_time(struct Tremolo *ptr, float ms);
+
+/*
+ LFO phase offset of the right channel, 1.0 is half a period
+ */
+void tremolo_set_stereo_phase(struct Tremolo *ptr, float val);
+
+/*
+ One period every `beats` beats at `bpm`
+ */
+void tremolo_set_tempo_sync(struct Tremolo *ptr, float bpm, float beats);
+
+/*
+ Shape of the modulation
+ */
+void tremolo_set_waveform(struct Tremolo *ptr, enum Waveform wave);
+
+/*
+ Returns next stereo samples. Raw pointer `stereo_samples` assumes to have exactly two elements!
+ */
+void tremolo_tick(struct Tremolo *ptr, float *stereo_samples);
+
+/*
+ Needs to be called once before rendering every sample. Writes the trigger due on that sample to `due` and returns `true`, if there is one. Triggers on the same sample merge into the loudest, accented if any is.
+ */
+bool trigger_schedule_advance(struct TriggerSchedule *ptr,
+                              struct ScheduledTrigger *due);
+
+/*
+ Drops all pending triggers
+ */
+void trigger_schedule_clear(struct TriggerSchedule *ptr);
+
+/*
+ Initializes an empty `TriggerSchedule` struct
+ */
+struct TriggerSchedule trigger_schedule_init(void);
+
+/*
+ Returns `false` if four triggers are already pending and the trigger got dropped
+ */
+bool trigger_schedule_schedule(struct TriggerSchedule *ptr,
+                               size_t offset,
+                               float velocity,
+                               bool accent);
+
+#endif /* _YANEL_DSP_H_ */